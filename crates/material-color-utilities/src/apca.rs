@@ -0,0 +1,310 @@
+use num_traits::{Float, FromPrimitive};
+
+use crate::{hct::Gamut, utils};
+
+/// The minimum lightness contrast for body text that must be read fluently.
+pub const LC_90: f64 = 90.0;
+/// The minimum lightness contrast for columns of body text.
+pub const LC_75: f64 = 75.0;
+/// The minimum lightness contrast for content text that isn't body text.
+pub const LC_60: f64 = 60.0;
+/// The minimum lightness contrast for large or bold text, like headlines.
+pub const LC_45: f64 = 45.0;
+/// The minimum lightness contrast for text that doesn't need to be read, and for icons.
+pub const LC_30: f64 = 30.0;
+/// The minimum lightness contrast for non-text elements, like dividers.
+pub const LC_15: f64 = 15.0;
+
+/// The exponent of the simple transfer function APCA estimates screen luminance with.
+const MAIN_TRC: f64 = 2.4;
+/// The coefficients of sRGB's red, green and blue channels in the screen luminance.
+const SRGB_Y_COEFFICIENTS: [f64; 3] = [0.2126729, 0.7151522, 0.0721750];
+
+const NORMAL_BACKGROUND_EXPONENT: f64 = 0.56;
+const NORMAL_TEXT_EXPONENT: f64 = 0.57;
+const REVERSE_TEXT_EXPONENT: f64 = 0.62;
+const REVERSE_BACKGROUND_EXPONENT: f64 = 0.65;
+
+/// Luminances below this threshold are softly clamped, to account for flare on real screens.
+const BLACK_THRESHOLD: f64 = 0.022;
+const BLACK_CLAMP_EXPONENT: f64 = 1.414;
+
+const SCALE: f64 = 1.14;
+const LOW_OFFSET: f64 = 0.027;
+/// Contrasts below this are clipped to 0.
+const LOW_CLIP: f64 = 0.1;
+/// Luminances closer than this have no contrast.
+const DELTA_Y_MIN: f64 = 0.0005;
+
+/// How far past the tone reaching the requested contrast [`lighter`] and [`darker`] go, so the
+/// contrast is still reached once the tone is rounded to a displayable color. See the tolerance of
+/// [`crate::contrast::lighter`].
+const TONE_TOLERANCE: f64 = 0.4;
+
+/// The number of bisection steps [`lighter`] and [`darker`] take, which narrows the tone down to
+/// well below [`TONE_TOLERANCE`].
+const SEARCH_ITERATIONS: usize = 32;
+
+/// The screen luminance APCA estimates for a color, ranging from 0 to 1.
+///
+/// This isn't the relative luminance Y of WCAG 2: APCA linearizes channels with a simple 2.4
+/// exponent, which is closer to how monitors display dark colors.
+pub fn y_from_argb<T>(argb: u32) -> T
+where
+  T: Float + FromPrimitive,
+{
+  [
+    utils::color::red_from_argb(argb),
+    utils::color::green_from_argb(argb),
+    utils::color::blue_from_argb(argb),
+  ]
+  .iter()
+  .zip(SRGB_Y_COEFFICIENTS)
+  .fold(T::zero(), |y, (&channel, coefficient)| {
+    let normalized = T::from_u8(channel).unwrap() / T::from_f64(255.0).unwrap();
+    y + T::from_f64(coefficient).unwrap() * normalized.powf(T::from_f64(MAIN_TRC).unwrap())
+  })
+}
+
+/// The screen luminance APCA estimates for the gray of a tone, T in HCT, L* in L*a*b*.
+///
+/// Colors of the same tone have nearly the same screen luminance, so this stands in for any color
+/// of the tone.
+pub fn y_from_tone<T>(tone: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let tone = tone.clamp(T::zero(), T::from_f64(100.0).unwrap());
+  let component = Gamut::Srgb.delinearized(utils::color::y_from_lstar(tone));
+  component
+    .clamp(T::zero(), T::one())
+    .powf(T::from_f64(MAIN_TRC).unwrap())
+}
+
+/// The APCA lightness contrast (Lc) of text over a background, given their screen luminances.
+///
+/// Lc ranges from about -108 to 106. Unlike the WCAG 2 contrast ratio, it depends on which color
+/// is the text: it is positive for dark text on a light background, and negative for light text on
+/// a dark background. Compare the absolute value with levels like [`LC_60`].
+pub fn lc_of_ys<T>(text_y: T, background_y: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let text_y = soft_clamp_black(text_y);
+  let background_y = soft_clamp_black(background_y);
+  if (background_y - text_y).abs() < T::from_f64(DELTA_Y_MIN).unwrap() {
+    return T::zero();
+  }
+  let scale = T::from_f64(SCALE).unwrap();
+  let low_clip = T::from_f64(LOW_CLIP).unwrap();
+  let low_offset = T::from_f64(LOW_OFFSET).unwrap();
+  let contrast = if background_y > text_y {
+    let contrast = (background_y.powf(T::from_f64(NORMAL_BACKGROUND_EXPONENT).unwrap())
+      - text_y.powf(T::from_f64(NORMAL_TEXT_EXPONENT).unwrap()))
+      * scale;
+    if contrast < low_clip {
+      T::zero()
+    } else {
+      contrast - low_offset
+    }
+  } else {
+    let contrast = (background_y.powf(T::from_f64(REVERSE_BACKGROUND_EXPONENT).unwrap())
+      - text_y.powf(T::from_f64(REVERSE_TEXT_EXPONENT).unwrap()))
+      * scale;
+    if contrast > -low_clip {
+      T::zero()
+    } else {
+      contrast + low_offset
+    }
+  };
+  contrast * T::from_f64(100.0).unwrap()
+}
+
+/// The APCA lightness contrast (Lc) of text over a background, both represented in ARGB.
+pub fn lc_of_argbs<T>(text: u32, background: u32) -> T
+where
+  T: Float + FromPrimitive,
+{
+  lc_of_ys(y_from_argb(text), y_from_argb(background))
+}
+
+/// The APCA lightness contrast (Lc) of text over a background, given their tones.
+pub fn lc_of_tones<T>(text_tone: T, background_tone: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  lc_of_ys(y_from_tone(text_tone), y_from_tone(background_tone))
+}
+
+/// Returns the tone of text, lighter than the background, whose lightness contrast with the
+/// background has an absolute value of at least `lc`. Returns -1 if `lc` cannot be reached.
+pub fn lighter<T>(background_tone: T, lc: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let max_tone = T::from_f64(100.0).unwrap();
+  if !is_bounded(background_tone) || lc_of_tones(max_tone, background_tone).abs() < lc {
+    return -T::one();
+  }
+  let (mut reaching, mut not_reaching) = (max_tone, background_tone);
+  for _ in 0..SEARCH_ITERATIONS {
+    let tone = (reaching + not_reaching) / T::from_f64(2.0).unwrap();
+    if lc_of_tones(tone, background_tone).abs() >= lc {
+      reaching = tone;
+    } else {
+      not_reaching = tone;
+    }
+  }
+  (reaching + T::from_f64(TONE_TOLERANCE).unwrap()).min(max_tone)
+}
+
+/// Tone of text lighter than the background that ensures `lc`. 100 if `lc` cannot be reached.
+///
+/// This method is unsafe because the returned value is guaranteed to be in bounds, but, the in
+/// bounds return value may not reach the desired contrast.
+pub fn lighter_unsafe<T>(background_tone: T, lc: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let lighter_safe = lighter(background_tone, lc);
+  if lighter_safe < T::zero() {
+    T::from_f64(100.0).unwrap()
+  } else {
+    lighter_safe
+  }
+}
+
+/// Returns the tone of text, darker than the background, whose lightness contrast with the
+/// background is at least `lc`. Returns -1 if `lc` cannot be reached.
+pub fn darker<T>(background_tone: T, lc: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  if !is_bounded(background_tone) || lc_of_tones(T::zero(), background_tone).abs() < lc {
+    return -T::one();
+  }
+  let (mut reaching, mut not_reaching) = (T::zero(), background_tone);
+  for _ in 0..SEARCH_ITERATIONS {
+    let tone = (reaching + not_reaching) / T::from_f64(2.0).unwrap();
+    if lc_of_tones(tone, background_tone).abs() >= lc {
+      reaching = tone;
+    } else {
+      not_reaching = tone;
+    }
+  }
+  (reaching - T::from_f64(TONE_TOLERANCE).unwrap()).max(T::zero())
+}
+
+/// Tone of text darker than the background that ensures `lc`. 0 if `lc` cannot be reached.
+///
+/// This method is unsafe because the returned value is guaranteed to be in bounds, but, the in
+/// bounds return value may not reach the desired contrast.
+pub fn darker_unsafe<T>(background_tone: T, lc: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  T::max(T::zero(), darker(background_tone, lc))
+}
+
+/// Softly clamps luminances near black, which flare makes lighter on real screens.
+fn soft_clamp_black<T>(y: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let threshold = T::from_f64(BLACK_THRESHOLD).unwrap();
+  let y = y.max(T::zero());
+  if y > threshold {
+    y
+  } else {
+    y + (threshold - y).powf(T::from_f64(BLACK_CLAMP_EXPONENT).unwrap())
+  }
+}
+
+/// Whether a tone is within 0 and 100.
+fn is_bounded<T>(tone: T) -> bool
+where
+  T: Float + FromPrimitive,
+{
+  T::zero() <= tone && tone <= T::from_f64(100.0).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::test::{approx_eq, assert_approx_eq};
+
+  #[test]
+  fn matches_reference_implementation() {
+    // Values from the test suite of the APCA reference implementation, 0.0.98G-4g.
+    let pairs: [(u32, u32, f64); 6] = [
+      (0xff000000, 0xffffffff, 106.04067321268862),
+      (0xffffffff, 0xff000000, -107.88473318309848),
+      (0xff888888, 0xffffffff, 63.056469930209424),
+      (0xffffffff, 0xff888888, -68.54146436644962),
+      (0xff000000, 0xffaaaaaa, 58.146262578561334),
+      (0xffaaaaaa, 0xff000000, -56.24113336839742),
+    ];
+    for (text, background, expected) in pairs {
+      assert_approx_eq!(lc_of_argbs(text, background), expected, 6.0);
+    }
+    assert_eq!(lc_of_argbs::<f64>(0xff777777, 0xff777777), 0.0);
+  }
+
+  #[test]
+  fn tones_match_grays() {
+    for argb in [0xff000000, 0xff555555, 0xffaaaaaa, 0xffffffff] {
+      let tone = utils::color::lstar_from_argb(argb);
+      assert_approx_eq!(y_from_tone(tone), y_from_argb::<f64>(argb), 4.0);
+    }
+  }
+
+  #[test]
+  fn finds_tones_reaching_lc() {
+    let darker_tone = darker(100.0, LC_75);
+    assert!(lc_of_tones(darker_tone, 100.0) >= LC_75);
+    assert!(lc_of_tones(darker_tone + 2.0 * TONE_TOLERANCE, 100.0) < LC_75);
+    let lighter_tone = lighter(0.0, LC_75);
+    assert!(-lc_of_tones(lighter_tone, 0.0) >= LC_75);
+    assert!(-lc_of_tones(lighter_tone - 2.0 * TONE_TOLERANCE, 0.0) < LC_75);
+  }
+
+  #[test]
+  fn impossible_lc_errors() {
+    assert_eq!(darker(30.0, LC_90), -1.0);
+    assert_eq!(lighter(70.0, LC_60), -1.0);
+    assert_eq!(lighter(110.0, LC_15), -1.0);
+    assert_eq!(darker_unsafe(30.0, LC_90), 0.0);
+    assert_eq!(lighter_unsafe(70.0, LC_60), 100.0);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn contrast_curves_can_target_lc() {
+    use crate::{
+      dynamiccolor::{
+        ContrastCurve, DynamicColor, DynamicColorBuilder, DynamicScheme, DynamicSchemeBuilder,
+        MaterialDynamicColors,
+      },
+      hct::Hct,
+    };
+
+    let colors = MaterialDynamicColors::new();
+    for is_dark in [false, true] {
+      let scheme = DynamicSchemeBuilder::default()
+        .source_color_hct(Hct::from_int(0xff4285f4))
+        .is_dark(is_dark)
+        .build();
+      let text: DynamicColor = DynamicColorBuilder::new()
+        .name("apca_text")
+        .palette(|s: &DynamicScheme| s.primary_palette())
+        .tone(|_: &DynamicScheme| 50.0)
+        .is_background(false)
+        .background(|_: &DynamicScheme| Some(colors.surface()))
+        .contrast_curve(|_: &DynamicScheme| Some(ContrastCurve::apca(LC_45, LC_75, LC_90, LC_90)))
+        .build()
+        .unwrap();
+      let lc = lc_of_argbs::<f64>(text.get_argb(&scheme), colors.surface().get_argb(&scheme));
+      assert!(lc.abs() >= LC_75, "{lc}");
+    }
+  }
+}
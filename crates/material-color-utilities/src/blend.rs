@@ -1,4 +1,5 @@
 use crate::{
+  color::Argb,
   hct::{Cam16, Hct},
   utils,
 };
@@ -6,7 +7,7 @@ use crate::{
 /// Blend the design color's HCT hue towards the key color's HCT
 /// hue, in a way that leaves the original color recognizable and
 /// recognizably shifted towards the key color.
-pub fn harmonize(design_color: u32, source_color: u32) -> u32 {
+pub fn harmonize(design_color: impl Into<Argb>, source_color: impl Into<Argb>) -> u32 {
   let from_hct = Hct::from_int(design_color);
   let to_hct = Hct::from_int(source_color);
   let difference_degrees = utils::math::difference_degrees(from_hct.hue(), to_hct.hue());
@@ -20,7 +21,8 @@ pub fn harmonize(design_color: u32, source_color: u32) -> u32 {
 
 /// Blends hue from one color into another. The chroma and tone of
 /// the original color are maintained.
-pub fn hct_hue(from: u32, to: u32, amount: f64) -> u32 {
+pub fn hct_hue(from: impl Into<Argb>, to: impl Into<Argb>, amount: f64) -> u32 {
+  let from = from.into().to_int();
   let ucs = cam16_ucs(from, to, amount);
   let ucs_cam = Cam16::from_int(ucs);
   let from_cam = Cam16::from_int(from);
@@ -33,9 +35,9 @@ pub fn hct_hue(from: u32, to: u32, amount: f64) -> u32 {
 }
 
 /// Blend in CAM16-UCS space.
-pub fn cam16_ucs(from: u32, to: u32, amount: f64) -> u32 {
-  let from_cam = Cam16::from_int(from);
-  let to_cam = Cam16::from_int(to);
+pub fn cam16_ucs(from: impl Into<Argb>, to: impl Into<Argb>, amount: f64) -> u32 {
+  let from_cam = Cam16::from_int(from.into().to_int());
+  let to_cam = Cam16::from_int(to.into().to_int());
   let from_j = from_cam.jstar();
   let from_a = from_cam.astar();
   let from_b = from_cam.bstar();
@@ -0,0 +1,217 @@
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{hct::XYZ_TO_CAM16RGB, utils};
+
+const XYZ_TO_BRADFORD: [[f64; 3]; 3] = [
+  [0.8951, 0.2664, -0.1614],
+  [-0.7502, 1.7135, 0.0367],
+  [0.0389, -0.0685, 1.0296],
+];
+
+const XYZ_TO_CAT02: [[f64; 3]; 3] = [
+  [0.7328, 0.4296, -0.1624],
+  [-0.7036, 1.6975, 0.0061],
+  [0.0030, 0.0136, 0.9834],
+];
+
+/// The lowest color temperature, in Kelvin, [`white_point_from_cct`] is defined for.
+pub const MIN_CCT: f64 = 1667.0;
+/// The highest color temperature, in Kelvin, [`white_point_from_cct`] and
+/// [`daylight_white_point`] are defined for.
+pub const MAX_CCT: f64 = 25000.0;
+/// The lowest color temperature, in Kelvin, [`daylight_white_point`] is defined for.
+pub const MIN_DAYLIGHT_CCT: f64 = 4000.0;
+
+/// A chromatic adaptation transform, predicting the color that appears the same to an observer
+/// adapted to another white point, e.g. to proof sRGB colors on paper viewed under D50.
+///
+/// Each transform is a von Kries transform in its own cone-like space, assuming the observer fully
+/// adapts to the white point.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ChromaticAdaptation {
+  /// The transform of ICC profiles, and the most widely used.
+  #[default]
+  Bradford,
+  /// The transform of CIECAM02.
+  Cat02,
+  /// The transform of CAM16, which HCT is built on.
+  Cat16,
+}
+
+impl ChromaticAdaptation {
+  /// Adapts an XYZ color viewed under `source_white` to the color that appears the same under
+  /// `target_white`.
+  ///
+  /// White points are XYZ coordinates, e.g. [`utils::color::white_point_d65`].
+  pub fn adapt<T>(self, xyz: [T; 3], source_white: [T; 3], target_white: [T; 3]) -> [T; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    utils::math::matrix_multiply(&xyz, &self.matrix(source_white, target_white))
+  }
+
+  /// The matrix [`ChromaticAdaptation::adapt`] multiplies XYZ colors by, to adapt many colors
+  /// between the same white points.
+  pub fn matrix<T>(self, source_white: [T; 3], target_white: [T; 3]) -> [[T; 3]; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    let to_cone = self.xyz_to_cone();
+    let source_cone = utils::math::matrix_multiply(&source_white, &to_cone);
+    let target_cone = utils::math::matrix_multiply(&target_white, &to_cone);
+    let scaled_to_cone = [0, 1, 2].map(|i| {
+      let scale = target_cone[i] / source_cone[i];
+      to_cone[i].map(|value| value * scale)
+    });
+    utils::math::matrix_product(&utils::math::matrix_inverse(&to_cone), &scaled_to_cone)
+  }
+
+  fn xyz_to_cone<T>(self) -> [[T; 3]; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    utils::color::matrix_from_f64(match self {
+      Self::Bradford => XYZ_TO_BRADFORD,
+      Self::Cat02 => XYZ_TO_CAT02,
+      Self::Cat16 => XYZ_TO_CAM16RGB,
+    })
+  }
+}
+
+/// The white point of a black body at a color temperature in Kelvin, with a Y of 100.
+///
+/// Uses the cubic spline approximation of the Planckian locus by Kim et al., for the warm whites
+/// of incandescent lights and of displays' "night light" settings. Temperatures are clamped to
+/// [`MIN_CCT`]..=[`MAX_CCT`].
+pub fn white_point_from_cct<T>(kelvin: T) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let kelvin = kelvin.clamp(T::from_f64(MIN_CCT).unwrap(), T::from_f64(MAX_CCT).unwrap());
+  let inverse = T::one() / kelvin;
+  let x = if kelvin <= T::from_f64(4000.0).unwrap() {
+    polynomial(inverse, [0.179910, 0.8776956e3, -0.2343589e6, -0.2661239e9])
+  } else {
+    polynomial(inverse, [0.240390, 0.2226347e3, 2.1070379e6, -3.0258469e9])
+  };
+  let y = if kelvin <= T::from_f64(2222.0).unwrap() {
+    polynomial(x, [-0.20219683, 2.18555832, -1.34811020, -1.1063814])
+  } else if kelvin <= T::from_f64(4000.0).unwrap() {
+    polynomial(x, [-0.16748867, 2.09137015, -1.37418593, -0.9549476])
+  } else {
+    polynomial(x, [-0.37001483, 3.75112997, -5.87338670, 3.0817580])
+  };
+  white_point_from_xy(x, y)
+}
+
+/// The white point of CIE daylight at a correlated color temperature in Kelvin, with a Y of 100.
+///
+/// These are the D series illuminants, so 5003 K is D50 and 6504 K is D65. Temperatures are
+/// clamped to [`MIN_DAYLIGHT_CCT`]..=[`MAX_CCT`].
+pub fn daylight_white_point<T>(kelvin: T) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let kelvin = kelvin.clamp(
+    T::from_f64(MIN_DAYLIGHT_CCT).unwrap(),
+    T::from_f64(MAX_CCT).unwrap(),
+  );
+  let inverse = T::one() / kelvin;
+  let x = if kelvin <= T::from_f64(7000.0).unwrap() {
+    polynomial(inverse, [0.244063, 0.09911e3, 2.9678e6, -4.6070e9])
+  } else {
+    polynomial(inverse, [0.237040, 0.24748e3, 1.9018e6, -2.0064e9])
+  };
+  let y = polynomial(x, [-0.275, 2.870, -3.000, 0.0]);
+  white_point_from_xy(x, y)
+}
+
+/// Evaluates a cubic polynomial, given its coefficients from the constant term up.
+fn polynomial<T>(x: T, coefficients: [f64; 4]) -> T
+where
+  T: Float + FromPrimitive,
+{
+  coefficients
+    .iter()
+    .rev()
+    .fold(T::zero(), |sum, &coefficient| {
+      sum * x + T::from_f64(coefficient).unwrap()
+    })
+}
+
+fn white_point_from_xy<T>(x: T, y: T) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let hundred = T::from_f64(100.0).unwrap();
+  [x / y * hundred, hundred, (T::one() - x - y) / y * hundred]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::test::{approx_eq, assert_approx_eq};
+
+  #[test]
+  fn bradford_matches_icc() {
+    // The D65 to D50 matrix published by Bruce Lindbloom.
+    let expected = [
+      [1.0478112, 0.0228866, -0.0501270],
+      [0.0295424, 0.9904844, -0.0170491],
+      [-0.0092345, 0.0150436, 0.7521316],
+    ];
+    let matrix = ChromaticAdaptation::Bradford.matrix(
+      utils::color::white_point_d65::<f64>(),
+      utils::color::white_point_d50(),
+    );
+    for (row, expected_row) in matrix.iter().zip(expected) {
+      for (value, expected_value) in row.iter().zip(expected_row) {
+        assert_approx_eq!(*value, expected_value, 4.0);
+      }
+    }
+  }
+
+  #[test]
+  fn adapts_white_to_white() {
+    let d65 = utils::color::white_point_d65::<f64>();
+    let warm = white_point_from_cct(2700.0);
+    for transform in [
+      ChromaticAdaptation::Bradford,
+      ChromaticAdaptation::Cat02,
+      ChromaticAdaptation::Cat16,
+    ] {
+      for (component, expected) in transform.adapt(d65, d65, warm).iter().zip(warm) {
+        assert_approx_eq!(*component, expected, 9.0);
+      }
+      let color = [30.0, 20.0, 10.0];
+      let round_trip = transform.adapt(transform.adapt(color, d65, warm), warm, d65);
+      for (component, expected) in round_trip.iter().zip(color) {
+        assert_approx_eq!(*component, expected, 9.0);
+      }
+    }
+  }
+
+  #[test]
+  fn daylight_matches_standard_illuminants() {
+    for (kelvin, expected) in [
+      (6504.0, utils::color::white_point_d65::<f64>()),
+      (5003.0, utils::color::white_point_d50()),
+    ] {
+      for (component, expected) in daylight_white_point(kelvin).iter().zip(expected) {
+        assert!((component - expected).abs() < 0.1);
+      }
+    }
+  }
+
+  #[test]
+  fn warmer_temperatures_are_redder() {
+    let [x_warm, _, z_warm] = white_point_from_cct(2700.0);
+    let [x_cool, _, z_cool] = white_point_from_cct(6500.0);
+    assert!(x_warm > x_cool);
+    assert!(z_warm < z_cool);
+    assert_eq!(white_point_from_cct(1000.0), white_point_from_cct(MIN_CCT));
+  }
+}
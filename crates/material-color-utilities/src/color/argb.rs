@@ -0,0 +1,124 @@
+use num_traits::{Float, FloatConst, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{hct::Hct, utils};
+
+/// A color in ARGB format: alpha, red, green and blue channels, from the highest byte of a `u32` to
+/// the lowest, like the hex code `0xAARRGGBB`.
+///
+/// Functions that take colors accept anything that converts into `Argb`, including a bare `u32`, so
+/// typed colors can be passed straight through. Converting to and from the float color types, like
+/// [`Lab`](super::Lab) and [`Oklch`](super::Oklch), goes through [`From`]; colors converted into
+/// `Argb` are opaque, and clipped to the sRGB gamut.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(transparent))]
+pub struct Argb(pub u32);
+
+impl Argb {
+  pub const fn new(argb: u32) -> Self {
+    Self(argb)
+  }
+
+  /// An opaque color with the given channels.
+  pub const fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+    Self::from_channels(255, red, green, blue)
+  }
+
+  pub const fn from_channels(alpha: u8, red: u8, green: u8, blue: u8) -> Self {
+    Self(((alpha as u32) << 24) | ((red as u32) << 16) | ((green as u32) << 8) | (blue as u32))
+  }
+
+  /// The `u32` representation of the color.
+  pub const fn to_int(self) -> u32 {
+    self.0
+  }
+
+  pub const fn alpha(self) -> u8 {
+    utils::color::alpha_from_argb(self.0)
+  }
+
+  pub const fn red(self) -> u8 {
+    utils::color::red_from_argb(self.0)
+  }
+
+  pub const fn green(self) -> u8 {
+    utils::color::green_from_argb(self.0)
+  }
+
+  pub const fn blue(self) -> u8 {
+    utils::color::blue_from_argb(self.0)
+  }
+
+  pub const fn is_opaque(self) -> bool {
+    self.alpha() == 255
+  }
+
+  /// The same color with a different alpha channel.
+  pub const fn with_alpha(self, alpha: u8) -> Self {
+    Self::from_channels(alpha, self.red(), self.green(), self.blue())
+  }
+}
+
+impl From<u32> for Argb {
+  fn from(argb: u32) -> Self {
+    Self(argb)
+  }
+}
+
+impl From<Argb> for u32 {
+  fn from(argb: Argb) -> Self {
+    argb.0
+  }
+}
+
+impl<T> From<Argb> for Hct<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    <Self as From<u32>>::from(argb.0)
+  }
+}
+
+impl<T> From<Hct<T>> for Argb
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  fn from(hct: Hct<T>) -> Self {
+    Self::from(&hct)
+  }
+}
+
+impl<T> From<&Hct<T>> for Argb
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  fn from(hct: &Hct<T>) -> Self {
+    Self(hct.to_int())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reads_channels() {
+    let argb = Argb::new(0x804285f4);
+    assert_eq!(argb.alpha(), 0x80);
+    assert_eq!(argb.red(), 0x42);
+    assert_eq!(argb.green(), 0x85);
+    assert_eq!(argb.blue(), 0xf4);
+    assert!(!argb.is_opaque());
+    assert_eq!(argb.with_alpha(255), Argb::from_rgb(0x42, 0x85, 0xf4));
+    assert_eq!(u32::from(Argb::from_rgb(0x42, 0x85, 0xf4)), 0xff4285f4);
+  }
+
+  #[test]
+  fn converts_to_and_from_hct() {
+    let hct: Hct = Argb::new(0xff4285f4).into();
+    assert_eq!(hct, Hct::from_int(0xff4285f4));
+    assert_eq!(Argb::from(&hct), Argb::new(0xff4285f4));
+  }
+}
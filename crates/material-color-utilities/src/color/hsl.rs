@@ -0,0 +1,248 @@
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  color::{Argb, Srgb},
+  utils,
+};
+
+/// An sRGB color by hue, saturation and lightness, like `hsl()` in CSS.
+///
+/// Hue is in degrees from 0 to 360, and saturation and lightness range from 0 to 1. Hue is 0 for
+/// grays. HSL is a reshaping of sRGB, not a perceptual color space: colors with the same lightness
+/// can have very different tones.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Hsl<T = f64> {
+  pub hue: T,
+  pub saturation: T,
+  pub lightness: T,
+}
+
+impl<T> Hsl<T> {
+  pub const fn new(hue: T, saturation: T, lightness: T) -> Self {
+    Self {
+      hue,
+      saturation,
+      lightness,
+    }
+  }
+}
+
+impl<T> From<[T; 3]> for Hsl<T> {
+  fn from([hue, saturation, lightness]: [T; 3]) -> Self {
+    Self::new(hue, saturation, lightness)
+  }
+}
+
+impl<T> From<Hsl<T>> for [T; 3] {
+  fn from(hsl: Hsl<T>) -> Self {
+    [hsl.hue, hsl.saturation, hsl.lightness]
+  }
+}
+
+impl<T> From<Srgb<T>> for Hsl<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(srgb: Srgb<T>) -> Self {
+    let (max, min) = extremes(&srgb);
+    let lightness = (max + min) / T::from_f64(2.0).unwrap();
+    let delta = max - min;
+    let saturation = if delta == T::zero() {
+      T::zero()
+    } else {
+      delta / (T::one() - (T::from_f64(2.0).unwrap() * lightness - T::one()).abs())
+    };
+    Self::new(hue(&srgb), saturation, lightness)
+  }
+}
+
+impl<T> From<Hsl<T>> for Srgb<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(hsl: Hsl<T>) -> Self {
+    // The conversion from CSS Color 4.
+    let twelve = T::from_f64(12.0).unwrap();
+    let amount = hsl.saturation * hsl.lightness.min(T::one() - hsl.lightness);
+    let hue = utils::math::sanitize_degrees(hsl.hue);
+    [0.0, 8.0, 4.0]
+      .map(|n| {
+        let k = (T::from_f64(n).unwrap() + hue / T::from_f64(30.0).unwrap()) % twelve;
+        let ramp = (k - T::from_f64(3.0).unwrap())
+          .min(T::from_f64(9.0).unwrap() - k)
+          .clamp(-T::one(), T::one());
+        hsl.lightness - amount * ramp
+      })
+      .into()
+  }
+}
+
+impl<T> From<Argb> for Hsl<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    Srgb::from(argb).into()
+  }
+}
+
+impl<T> From<Hsl<T>> for Argb
+where
+  T: Float + FromPrimitive,
+{
+  fn from(hsl: Hsl<T>) -> Self {
+    Srgb::from(hsl).into()
+  }
+}
+
+/// An sRGB color by hue, saturation and value, also known as HSB.
+///
+/// Hue is in degrees from 0 to 360, and saturation and value range from 0 to 1. Hue is 0 for
+/// grays.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Hsv<T = f64> {
+  pub hue: T,
+  pub saturation: T,
+  pub value: T,
+}
+
+impl<T> Hsv<T> {
+  pub const fn new(hue: T, saturation: T, value: T) -> Self {
+    Self {
+      hue,
+      saturation,
+      value,
+    }
+  }
+}
+
+impl<T> From<[T; 3]> for Hsv<T> {
+  fn from([hue, saturation, value]: [T; 3]) -> Self {
+    Self::new(hue, saturation, value)
+  }
+}
+
+impl<T> From<Hsv<T>> for [T; 3] {
+  fn from(hsv: Hsv<T>) -> Self {
+    [hsv.hue, hsv.saturation, hsv.value]
+  }
+}
+
+impl<T> From<Srgb<T>> for Hsv<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(srgb: Srgb<T>) -> Self {
+    let (max, min) = extremes(&srgb);
+    let saturation = if max == T::zero() {
+      T::zero()
+    } else {
+      (max - min) / max
+    };
+    Self::new(hue(&srgb), saturation, max)
+  }
+}
+
+impl<T> From<Hsv<T>> for Srgb<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(hsv: Hsv<T>) -> Self {
+    let six = T::from_f64(6.0).unwrap();
+    let hue = utils::math::sanitize_degrees(hsv.hue);
+    [5.0, 3.0, 1.0]
+      .map(|n| {
+        let k = (T::from_f64(n).unwrap() + hue / T::from_f64(60.0).unwrap()) % six;
+        let ramp = k
+          .min(T::from_f64(4.0).unwrap() - k)
+          .clamp(T::zero(), T::one());
+        hsv.value - hsv.value * hsv.saturation * ramp
+      })
+      .into()
+  }
+}
+
+impl<T> From<Argb> for Hsv<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    Srgb::from(argb).into()
+  }
+}
+
+impl<T> From<Hsv<T>> for Argb
+where
+  T: Float + FromPrimitive,
+{
+  fn from(hsv: Hsv<T>) -> Self {
+    Srgb::from(hsv).into()
+  }
+}
+
+/// The largest and smallest components of a color.
+fn extremes<T>(srgb: &Srgb<T>) -> (T, T)
+where
+  T: Float,
+{
+  (
+    srgb.red.max(srgb.green).max(srgb.blue),
+    srgb.red.min(srgb.green).min(srgb.blue),
+  )
+}
+
+/// The hue shared by HSL and HSV.
+fn hue<T>(srgb: &Srgb<T>) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let (max, min) = extremes(srgb);
+  let delta = max - min;
+  if delta == T::zero() {
+    return T::zero();
+  }
+  let sextant = if max == srgb.red {
+    (srgb.green - srgb.blue) / delta
+  } else if max == srgb.green {
+    (srgb.blue - srgb.red) / delta + T::from_f64(2.0).unwrap()
+  } else {
+    (srgb.red - srgb.green) / delta + T::from_f64(4.0).unwrap()
+  };
+  utils::math::sanitize_degrees(sextant * T::from_f64(60.0).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::test::{approx_eq, assert_approx_eq};
+
+  #[test]
+  fn matches_css() {
+    // #4285f4 is hsl(217.4 89% 60.8%), and hsb(217.4 72.9% 95.7%).
+    let hsl: Hsl = Argb(0xff4285f4).into();
+    assert!((hsl.hue - 217.4).abs() < 0.1);
+    assert_approx_eq!(hsl.saturation, 0.890, 3.0);
+    assert_approx_eq!(hsl.lightness, 0.608, 3.0);
+    let hsv: Hsv = Argb(0xff4285f4).into();
+    assert!((hsv.hue - 217.4).abs() < 0.1);
+    assert_approx_eq!(hsv.saturation, 0.730, 2.0);
+    assert_approx_eq!(hsv.value, 0.957, 3.0);
+    assert_eq!(Argb::from(Hsl::new(120.0, 1.0, 0.25)), Argb(0xff008000));
+  }
+
+  #[test]
+  fn round_trips_argb() {
+    for argb in [
+      0xff000000, 0xffffffff, 0xff777777, 0xff4285f4, 0xffdb4437, 0xff0f9d58,
+    ] {
+      let hsl: Hsl = Argb(argb).into();
+      assert_eq!(Argb::from(hsl), Argb(argb));
+      let hsv: Hsv = Argb(argb).into();
+      assert_eq!(Argb::from(hsv), Argb(argb));
+    }
+  }
+}
@@ -0,0 +1,167 @@
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  color::{Argb, LinearRgb},
+  hct::Gamut,
+  utils,
+};
+
+/// A color in the CIE 1931 XYZ color space, scaled so the D65 white point has a Y of 100.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Xyz<T = f64> {
+  pub x: T,
+  pub y: T,
+  pub z: T,
+}
+
+impl<T> Xyz<T> {
+  pub const fn new(x: T, y: T, z: T) -> Self {
+    Self { x, y, z }
+  }
+}
+
+impl<T> From<[T; 3]> for Xyz<T> {
+  fn from([x, y, z]: [T; 3]) -> Self {
+    Self::new(x, y, z)
+  }
+}
+
+impl<T> From<Xyz<T>> for [T; 3] {
+  fn from(xyz: Xyz<T>) -> Self {
+    [xyz.x, xyz.y, xyz.z]
+  }
+}
+
+impl<T> From<Argb> for Xyz<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    utils::color::xyz_from_argb(argb.0).into()
+  }
+}
+
+impl<T> From<Xyz<T>> for Argb
+where
+  T: Float + FromPrimitive,
+{
+  fn from(xyz: Xyz<T>) -> Self {
+    Self(utils::color::argb_from_xyz(xyz.x, xyz.y, xyz.z))
+  }
+}
+
+impl<T> From<LinearRgb<T>> for Xyz<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(linear_rgb: LinearRgb<T>) -> Self {
+    Gamut::Srgb.xyz_from_linrgb(linear_rgb.into()).into()
+  }
+}
+
+impl<T> From<Xyz<T>> for LinearRgb<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(xyz: Xyz<T>) -> Self {
+    Gamut::Srgb.linrgb_from_xyz(xyz.into()).into()
+  }
+}
+
+/// A color in the CIE L*a*b* color space, relative to the D65 white point.
+///
+/// L* ranges from 0 to 100, and is the tone of HCT.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Lab<T = f64> {
+  pub l: T,
+  pub a: T,
+  pub b: T,
+}
+
+impl<T> Lab<T> {
+  pub const fn new(l: T, a: T, b: T) -> Self {
+    Self { l, a, b }
+  }
+}
+
+impl<T> From<[T; 3]> for Lab<T> {
+  fn from([l, a, b]: [T; 3]) -> Self {
+    Self::new(l, a, b)
+  }
+}
+
+impl<T> From<Lab<T>> for [T; 3] {
+  fn from(lab: Lab<T>) -> Self {
+    [lab.l, lab.a, lab.b]
+  }
+}
+
+impl<T> From<Argb> for Lab<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    utils::color::lab_from_argb(argb.0).into()
+  }
+}
+
+impl<T> From<Lab<T>> for Argb
+where
+  T: Float + FromPrimitive,
+{
+  fn from(lab: Lab<T>) -> Self {
+    Self(utils::color::argb_from_lab(lab.l, lab.a, lab.b))
+  }
+}
+
+impl<T> From<Xyz<T>> for Lab<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(xyz: Xyz<T>) -> Self {
+    utils::color::lab_from_xyz(xyz.into()).into()
+  }
+}
+
+impl<T> From<Lab<T>> for Xyz<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(lab: Lab<T>) -> Self {
+    utils::color::xyz_from_lab(lab.into()).into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::test::{approx_eq, assert_approx_eq};
+
+  #[test]
+  fn white_has_full_lightness() {
+    let lab: Lab = Argb(0xffffffff).into();
+    assert_approx_eq!(lab.l, 100.0, 3.0);
+    assert_approx_eq!(lab.a, 0.0, 3.0);
+    assert_approx_eq!(lab.b, 0.0, 3.0);
+    let xyz: Xyz = Argb(0xffffffff).into();
+    assert_approx_eq!(xyz.y, 100.0, 3.0);
+  }
+
+  #[test]
+  fn round_trips_through_xyz() {
+    for argb in [0xff000000, 0xff4285f4, 0xffdb4437, 0xff0f9d58] {
+      let lab: Lab = Argb(argb).into();
+      assert_eq!(Argb::from(lab), Argb(argb));
+      let xyz = Xyz::from(lab);
+      assert_eq!(Argb::from(xyz), Argb(argb));
+      let from_linear_rgb = Xyz::from(LinearRgb::from(Argb(argb)));
+      assert_approx_eq!(from_linear_rgb.x, xyz.x, 6.0);
+      assert_approx_eq!(from_linear_rgb.y, xyz.y, 6.0);
+      assert_approx_eq!(from_linear_rgb.z, xyz.z, 6.0);
+    }
+  }
+}
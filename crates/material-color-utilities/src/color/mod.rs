@@ -0,0 +1,11 @@
+mod argb;
+mod hsl;
+mod lab;
+mod oklab;
+mod rgb;
+
+pub use argb::Argb;
+pub use hsl::{Hsl, Hsv};
+pub use lab::{Lab, Xyz};
+pub use oklab::{Oklab, Oklch};
+pub use rgb::{LinearRgb, Srgb};
@@ -0,0 +1,174 @@
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  color::{Argb, LinearRgb},
+  utils,
+};
+
+/// A color in the Oklab color space.
+///
+/// L ranges from 0 to 1, and a and b are roughly within -0.4 and 0.4.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Oklab<T = f64> {
+  pub l: T,
+  pub a: T,
+  pub b: T,
+}
+
+impl<T> Oklab<T> {
+  pub const fn new(l: T, a: T, b: T) -> Self {
+    Self { l, a, b }
+  }
+}
+
+impl<T> From<[T; 3]> for Oklab<T> {
+  fn from([l, a, b]: [T; 3]) -> Self {
+    Self::new(l, a, b)
+  }
+}
+
+impl<T> From<Oklab<T>> for [T; 3] {
+  fn from(oklab: Oklab<T>) -> Self {
+    [oklab.l, oklab.a, oklab.b]
+  }
+}
+
+impl<T> From<Argb> for Oklab<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    utils::color::oklab_from_argb(argb.0).into()
+  }
+}
+
+impl<T> From<Oklab<T>> for Argb
+where
+  T: Float + FromPrimitive,
+{
+  fn from(oklab: Oklab<T>) -> Self {
+    Self(utils::color::argb_from_oklab(oklab.l, oklab.a, oklab.b))
+  }
+}
+
+impl<T> From<LinearRgb<T>> for Oklab<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(linear_rgb: LinearRgb<T>) -> Self {
+    utils::color::oklab_from_linrgb(linear_rgb.into()).into()
+  }
+}
+
+impl<T> From<Oklab<T>> for LinearRgb<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(oklab: Oklab<T>) -> Self {
+    utils::color::linrgb_from_oklab(oklab.into()).into()
+  }
+}
+
+/// A color in Oklch, the polar form of [`Oklab`].
+///
+/// L ranges from 0 to 1, chroma from 0 to roughly 0.4, and hue is in degrees from 0 to 360. Hue is
+/// 0 for neutral colors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Oklch<T = f64> {
+  pub l: T,
+  pub c: T,
+  pub h: T,
+}
+
+impl<T> Oklch<T> {
+  pub const fn new(l: T, c: T, h: T) -> Self {
+    Self { l, c, h }
+  }
+}
+
+impl<T> From<[T; 3]> for Oklch<T> {
+  fn from([l, c, h]: [T; 3]) -> Self {
+    Self::new(l, c, h)
+  }
+}
+
+impl<T> From<Oklch<T>> for [T; 3] {
+  fn from(oklch: Oklch<T>) -> Self {
+    [oklch.l, oklch.c, oklch.h]
+  }
+}
+
+impl<T> From<Oklab<T>> for Oklch<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(oklab: Oklab<T>) -> Self {
+    let hue = if oklab.a == T::zero() && oklab.b == T::zero() {
+      T::zero()
+    } else {
+      utils::math::sanitize_degrees(oklab.b.atan2(oklab.a).to_degrees())
+    };
+    Self::new(oklab.l, oklab.a.hypot(oklab.b), hue)
+  }
+}
+
+impl<T> From<Oklch<T>> for Oklab<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(oklch: Oklch<T>) -> Self {
+    let (sin, cos) = oklch.h.to_radians().sin_cos();
+    Self::new(oklch.l, oklch.c * cos, oklch.c * sin)
+  }
+}
+
+impl<T> From<Argb> for Oklch<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    Oklab::from(argb).into()
+  }
+}
+
+impl<T> From<Oklch<T>> for Argb
+where
+  T: Float + FromPrimitive,
+{
+  fn from(oklch: Oklch<T>) -> Self {
+    Oklab::from(oklch).into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::test::{approx_eq, assert_approx_eq};
+
+  #[test]
+  fn round_trips_argb() {
+    for argb in [0xff000000, 0xffffffff, 0xff4285f4, 0xffdb4437, 0xff0f9d58] {
+      let oklab: Oklab = Argb(argb).into();
+      assert_eq!(Argb::from(oklab), Argb(argb));
+      let oklch: Oklch = Argb(argb).into();
+      assert_eq!(Argb::from(oklch), Argb(argb));
+      let from_linear_rgb = Oklab::from(LinearRgb::from(Argb(argb)));
+      assert_approx_eq!(from_linear_rgb.l, oklab.l, 9.0);
+    }
+  }
+
+  #[test]
+  fn oklch_is_polar_oklab() {
+    // CSS Color 4 gives oklch(0.628 0.2577 29.23) for #ff0000.
+    let red: Oklch = Argb(0xffff0000).into();
+    assert_approx_eq!(red.l, 0.628, 3.0);
+    assert_approx_eq!(red.c, 0.2577, 3.0);
+    assert!((red.h - 29.23).abs() < 0.1);
+    let gray: Oklch = Argb(0xff777777).into();
+    assert!(gray.c < 1e-4);
+  }
+}
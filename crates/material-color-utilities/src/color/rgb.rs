@@ -0,0 +1,163 @@
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{color::Argb, hct::Gamut, utils};
+
+/// An sRGB color with float components ranging from 0 to 1, as displayed, i.e. before
+/// linearization.
+///
+/// Components outside of 0 to 1 are kept, and only clipped when converting to [`Argb`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Srgb<T = f64> {
+  pub red: T,
+  pub green: T,
+  pub blue: T,
+}
+
+impl<T> Srgb<T> {
+  pub const fn new(red: T, green: T, blue: T) -> Self {
+    Self { red, green, blue }
+  }
+}
+
+impl<T> From<[T; 3]> for Srgb<T> {
+  fn from([red, green, blue]: [T; 3]) -> Self {
+    Self::new(red, green, blue)
+  }
+}
+
+impl<T> From<Srgb<T>> for [T; 3] {
+  fn from(srgb: Srgb<T>) -> Self {
+    [srgb.red, srgb.green, srgb.blue]
+  }
+}
+
+impl<T> From<Argb> for Srgb<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    let max = T::from_f64(255.0).unwrap();
+    Self::new(
+      T::from_u8(argb.red()).unwrap() / max,
+      T::from_u8(argb.green()).unwrap() / max,
+      T::from_u8(argb.blue()).unwrap() / max,
+    )
+  }
+}
+
+impl<T> From<Srgb<T>> for Argb
+where
+  T: Float + FromPrimitive,
+{
+  fn from(srgb: Srgb<T>) -> Self {
+    let [red, green, blue] = [srgb.red, srgb.green, srgb.blue].map(|component| {
+      (component.clamp(T::zero(), T::one()) * T::from_f64(255.0).unwrap())
+        .round()
+        .to_u8()
+        .unwrap()
+    });
+    Self::from_rgb(red, green, blue)
+  }
+}
+
+impl<T> From<LinearRgb<T>> for Srgb<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(linear_rgb: LinearRgb<T>) -> Self {
+    [linear_rgb.red, linear_rgb.green, linear_rgb.blue]
+      .map(|component| Gamut::Srgb.delinearized(component))
+      .into()
+  }
+}
+
+/// A linear sRGB color, with components ranging from 0 to 100 like the linear RGB colors in the
+/// rest of the library.
+///
+/// Components outside of 0 to 100 are kept, and only clipped when converting to [`Argb`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct LinearRgb<T = f64> {
+  pub red: T,
+  pub green: T,
+  pub blue: T,
+}
+
+impl<T> LinearRgb<T> {
+  pub const fn new(red: T, green: T, blue: T) -> Self {
+    Self { red, green, blue }
+  }
+}
+
+impl<T> From<[T; 3]> for LinearRgb<T> {
+  fn from([red, green, blue]: [T; 3]) -> Self {
+    Self::new(red, green, blue)
+  }
+}
+
+impl<T> From<LinearRgb<T>> for [T; 3] {
+  fn from(linear_rgb: LinearRgb<T>) -> Self {
+    [linear_rgb.red, linear_rgb.green, linear_rgb.blue]
+  }
+}
+
+impl<T> From<Argb> for LinearRgb<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(argb: Argb) -> Self {
+    Self::new(
+      utils::color::linearized(argb.red()),
+      utils::color::linearized(argb.green()),
+      utils::color::linearized(argb.blue()),
+    )
+  }
+}
+
+impl<T> From<LinearRgb<T>> for Argb
+where
+  T: Float + FromPrimitive,
+{
+  fn from(linear_rgb: LinearRgb<T>) -> Self {
+    Self(utils::color::argb_from_linrgb(linear_rgb.into()))
+  }
+}
+
+impl<T> From<Srgb<T>> for LinearRgb<T>
+where
+  T: Float + FromPrimitive,
+{
+  fn from(srgb: Srgb<T>) -> Self {
+    Gamut::Srgb.linrgb_from_rgb(srgb.into()).into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::test::{approx_eq, assert_approx_eq};
+
+  #[test]
+  fn round_trips_argb() {
+    for argb in [0xff000000, 0xffffffff, 0xff4285f4, 0xffdb4437, 0xff0f9d58] {
+      let srgb: Srgb = Argb(argb).into();
+      assert_eq!(Argb::from(srgb), Argb(argb));
+      let linear_rgb: LinearRgb = Argb(argb).into();
+      assert_eq!(Argb::from(linear_rgb), Argb(argb));
+      let through_srgb = LinearRgb::from(srgb);
+      assert_approx_eq!(through_srgb.red, linear_rgb.red, 9.0);
+      assert_approx_eq!(through_srgb.green, linear_rgb.green, 9.0);
+      assert_approx_eq!(through_srgb.blue, linear_rgb.blue, 9.0);
+    }
+  }
+
+  #[test]
+  fn clips_out_of_gamut_components() {
+    let srgb = Srgb::new(1.2, 0.5, -0.1);
+    assert_eq!(Argb::from(srgb), Argb(0xffff8000));
+    assert_approx_eq!(Srgb::from(LinearRgb::from(srgb)).red, 1.2, 9.0);
+  }
+}
@@ -0,0 +1,470 @@
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  color::{Argb, LinearRgb},
+  hct::Gamut,
+  utils,
+};
+
+/// The Hunt-Pointer-Estevez transform from XYZ to the LMS cone responses.
+const XYZ_TO_LMS: [[f64; 3]; 3] = [
+  [0.38971, 0.68898, -0.07868],
+  [-0.22981, 1.18340, 0.04641],
+  [0.0, 0.0, 1.0],
+];
+
+/// The CIE 1931 chromaticities of the monochromatic lights Brettel et al. anchor the colors seen
+/// by dichromats on: 475 nm and 575 nm for protans and deutans, 485 nm and 660 nm for tritans.
+const CHROMATICITY_475_NM: [f64; 2] = [0.1096, 0.0868];
+const CHROMATICITY_575_NM: [f64; 2] = [0.4788, 0.5202];
+const CHROMATICITY_485_NM: [f64; 2] = [0.0687, 0.2007];
+const CHROMATICITY_660_NM: [f64; 2] = [0.7300, 0.2700];
+
+/// The matrices of Machado et al. in linear sRGB, for severities 0.1, 0.2, ..., 1.0.
+const MACHADO_PROTAN: [[[f64; 3]; 3]; 10] = [
+  [
+    [0.856167, 0.182038, -0.038205],
+    [0.029342, 0.955115, 0.015544],
+    [-0.002880, -0.001563, 1.004443],
+  ],
+  [
+    [0.734766, 0.334872, -0.069637],
+    [0.051840, 0.919198, 0.028963],
+    [-0.004928, -0.004209, 1.009137],
+  ],
+  [
+    [0.630323, 0.465641, -0.095964],
+    [0.069181, 0.890046, 0.040773],
+    [-0.006308, -0.007724, 1.014032],
+  ],
+  [
+    [0.539009, 0.579343, -0.118352],
+    [0.082546, 0.866121, 0.051332],
+    [-0.007136, -0.011959, 1.019095],
+  ],
+  [
+    [0.458064, 0.679578, -0.137642],
+    [0.092785, 0.846313, 0.060902],
+    [-0.007494, -0.016807, 1.024301],
+  ],
+  [
+    [0.385450, 0.769005, -0.154455],
+    [0.100526, 0.829802, 0.069673],
+    [-0.007442, -0.022190, 1.029632],
+  ],
+  [
+    [0.319627, 0.849633, -0.169261],
+    [0.106241, 0.815969, 0.077790],
+    [-0.007025, -0.028051, 1.035076],
+  ],
+  [
+    [0.259411, 0.923008, -0.182420],
+    [0.110296, 0.804340, 0.085364],
+    [-0.006276, -0.034346, 1.040622],
+  ],
+  [
+    [0.203876, 0.990338, -0.194214],
+    [0.112975, 0.794542, 0.092483],
+    [-0.005222, -0.041043, 1.046265],
+  ],
+  [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+  ],
+];
+
+const MACHADO_DEUTAN: [[[f64; 3]; 3]; 10] = [
+  [
+    [0.866435, 0.177704, -0.044139],
+    [0.049567, 0.939063, 0.011370],
+    [-0.003453, 0.007233, 0.996220],
+  ],
+  [
+    [0.760729, 0.319078, -0.079807],
+    [0.090568, 0.889315, 0.020117],
+    [-0.006027, 0.013325, 0.992702],
+  ],
+  [
+    [0.675425, 0.433850, -0.109275],
+    [0.125303, 0.847755, 0.026942],
+    [-0.007950, 0.018572, 0.989378],
+  ],
+  [
+    [0.605511, 0.528560, -0.134071],
+    [0.155318, 0.812366, 0.032316],
+    [-0.009376, 0.023176, 0.986200],
+  ],
+  [
+    [0.547494, 0.607765, -0.155259],
+    [0.181692, 0.781742, 0.036566],
+    [-0.010410, 0.027275, 0.983136],
+  ],
+  [
+    [0.498864, 0.674741, -0.173604],
+    [0.205199, 0.754872, 0.039929],
+    [-0.011131, 0.030969, 0.980162],
+  ],
+  [
+    [0.457771, 0.731899, -0.189670],
+    [0.226409, 0.731012, 0.042579],
+    [-0.011595, 0.034333, 0.977261],
+  ],
+  [
+    [0.422823, 0.781057, -0.203881],
+    [0.245752, 0.709602, 0.044646],
+    [-0.011843, 0.037423, 0.974421],
+  ],
+  [
+    [0.392952, 0.823610, -0.216562],
+    [0.263559, 0.690210, 0.046232],
+    [-0.011910, 0.040281, 0.971630],
+  ],
+  [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+  ],
+];
+
+const MACHADO_TRITAN: [[[f64; 3]; 3]; 10] = [
+  [
+    [0.926670, 0.092514, -0.019184],
+    [0.021191, 0.964503, 0.014306],
+    [0.008437, 0.054813, 0.936750],
+  ],
+  [
+    [0.895720, 0.133330, -0.029050],
+    [0.029997, 0.945400, 0.024603],
+    [0.013027, 0.104707, 0.882266],
+  ],
+  [
+    [0.905871, 0.127791, -0.033662],
+    [0.026856, 0.941251, 0.031893],
+    [0.013410, 0.148296, 0.838294],
+  ],
+  [
+    [0.948035, 0.089490, -0.037526],
+    [0.014364, 0.946792, 0.038844],
+    [0.010853, 0.193991, 0.795156],
+  ],
+  [
+    [1.017277, 0.027029, -0.044306],
+    [-0.006113, 0.958479, 0.047634],
+    [0.006379, 0.248708, 0.744913],
+  ],
+  [
+    [1.104996, -0.046633, -0.058363],
+    [-0.032137, 0.971635, 0.060503],
+    [0.001336, 0.317922, 0.680742],
+  ],
+  [
+    [1.193214, -0.109812, -0.083402],
+    [-0.058496, 0.979410, 0.079086],
+    [-0.002346, 0.403492, 0.598854],
+  ],
+  [
+    [1.257728, -0.139648, -0.118081],
+    [-0.078003, 0.975409, 0.102594],
+    [-0.003316, 0.501214, 0.502102],
+  ],
+  [
+    [1.278864, -0.125333, -0.153531],
+    [-0.084748, 0.957674, 0.127074],
+    [-0.000989, 0.601151, 0.399838],
+  ],
+  [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+  ],
+];
+
+/// A color vision deficiency, named after the type of cone it affects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Deficiency {
+  /// Affects the long-wavelength (L) cones: protanomaly, or protanopia at full severity. Reds
+  /// look darker, and are confused with greens.
+  Protan,
+  /// Affects the medium-wavelength (M) cones: deuteranomaly, or deuteranopia at full severity.
+  /// The most common deficiency, confusing reds and greens.
+  Deutan,
+  /// Affects the short-wavelength (S) cones: tritanomaly, or tritanopia at full severity. Blues
+  /// are confused with greens, and yellows with violets.
+  Tritan,
+}
+
+impl Deficiency {
+  pub const ALL: [Self; 3] = [Self::Protan, Self::Deutan, Self::Tritan];
+
+  /// The index of the affected cone in LMS.
+  const fn cone(self) -> usize {
+    match self {
+      Self::Protan => 0,
+      Self::Deutan => 1,
+      Self::Tritan => 2,
+    }
+  }
+}
+
+/// How a [`CvdSimulation`] predicts the colors seen with a deficiency.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum SimulationMethod {
+  /// Brettel, Viénot and Mollon (1997): projects colors onto two half-planes in LMS, and is the
+  /// most accurate for tritans. Partial severities blend with the original color.
+  Brettel1997,
+  /// Viénot, Brettel and Mollon (1999): a single plane and so a single matrix, close to Brettel
+  /// for protans and deutans. Partial severities blend with the original color.
+  Vienot1999,
+  /// Machado, Oliveira and Fernandes (2009): models anomalous trichromacy by shifting the
+  /// sensitivity of the affected cones, so partial severities are physiologically based.
+  #[default]
+  Machado2009,
+}
+
+/// A simulation of how colors look to people with a color vision deficiency (CVD).
+///
+/// Simulations work on linear sRGB, and colors that leave the sRGB gamut are clipped when
+/// converted back to ARGB.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct CvdSimulation {
+  deficiency: Deficiency,
+  severity: f64,
+  method: SimulationMethod,
+}
+
+impl CvdSimulation {
+  /// A simulation of a deficiency at full severity, i.e. dichromacy, with the default method.
+  pub const fn new(deficiency: Deficiency) -> Self {
+    Self {
+      deficiency,
+      severity: 1.0,
+      method: SimulationMethod::Machado2009,
+    }
+  }
+
+  /// The same simulation with a different severity, from 0 for normal vision to 1 for dichromacy.
+  /// Severity is clamped to that range.
+  pub fn with_severity(mut self, severity: f64) -> Self {
+    self.severity = severity.clamp(0.0, 1.0);
+    self
+  }
+
+  /// The same simulation with a different method.
+  pub const fn with_method(mut self, method: SimulationMethod) -> Self {
+    self.method = method;
+    self
+  }
+
+  pub const fn deficiency(&self) -> Deficiency {
+    self.deficiency
+  }
+
+  pub const fn severity(&self) -> f64 {
+    self.severity
+  }
+
+  pub const fn method(&self) -> SimulationMethod {
+    self.method
+  }
+
+  /// The color seen with the deficiency, in ARGB. Alpha is kept.
+  pub fn simulate(&self, argb: impl Into<Argb>) -> u32 {
+    let argb = argb.into();
+    let simulated = Argb::from(self.simulate_linear_rgb(LinearRgb::<f64>::from(argb)));
+    (argb.0 & 0xff000000) | (simulated.0 & 0x00ffffff)
+  }
+
+  /// The color seen with the deficiency, in linear sRGB. The result isn't clipped.
+  pub fn simulate_linear_rgb<T>(&self, linear_rgb: LinearRgb<T>) -> LinearRgb<T>
+  where
+    T: Float + FromPrimitive,
+  {
+    let linrgb: [T; 3] = linear_rgb.into();
+    let severity = T::from_f64(self.severity).unwrap();
+    match self.method {
+      SimulationMethod::Machado2009 => {
+        utils::math::matrix_multiply(&linrgb, &self.machado_matrix()).into()
+      }
+      SimulationMethod::Brettel1997 | SimulationMethod::Vienot1999 => {
+        let dichromat = self.dichromat_linrgb(linrgb);
+        [0, 1, 2]
+          .map(|i| utils::math::lerp(linrgb[i], dichromat[i], severity))
+          .into()
+      }
+    }
+  }
+
+  /// The Machado matrix for the severity, interpolated between the precomputed severities.
+  fn machado_matrix<T>(&self) -> [[T; 3]; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    let matrices = match self.deficiency {
+      Deficiency::Protan => &MACHADO_PROTAN,
+      Deficiency::Deutan => &MACHADO_DEUTAN,
+      Deficiency::Tritan => &MACHADO_TRITAN,
+    };
+    let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let matrix_at = |step: usize| {
+      if step == 0 {
+        identity
+      } else {
+        matrices[step - 1]
+      }
+    };
+    let position = self.severity * 10.0;
+    let lower = (position.floor() as usize).min(9);
+    let amount = position - lower as f64;
+    let (start, stop) = (matrix_at(lower), matrix_at(lower + 1));
+    utils::color::matrix_from_f64(
+      [0, 1, 2].map(|i| [0, 1, 2].map(|j| utils::math::lerp(start[i][j], stop[i][j], amount))),
+    )
+  }
+
+  /// The color seen by a dichromat, with Brettel's or Viénot's method.
+  ///
+  /// Both replace the response of the missing cone so the color lands on a plane through black
+  /// and white, that dichromats and trichromats see alike. Brettel uses two half-planes, through
+  /// the white point and the spectral anchors on either side of it.
+  fn dichromat_linrgb<T>(&self, linrgb: [T; 3]) -> [T; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    let xyz_to_lms = utils::color::matrix_from_f64(XYZ_TO_LMS);
+    let lms_from_linrgb = |linrgb: [T; 3]| {
+      utils::math::matrix_multiply(&Gamut::Srgb.xyz_from_linrgb(linrgb), &xyz_to_lms)
+    };
+    let lms_from_chromaticity = |[x, y]: [f64; 2]| {
+      let xyz = [x / y, 1.0, (1.0 - x - y) / y].map(|value| T::from_f64(value).unwrap());
+      utils::math::matrix_multiply(&xyz, &xyz_to_lms)
+    };
+
+    let cone = self.deficiency.cone();
+    let lms = lms_from_linrgb(linrgb);
+    let white = lms_from_linrgb([T::from_f64(100.0).unwrap(); 3]);
+    let normal = match self.method {
+      SimulationMethod::Vienot1999 => {
+        // Viénot's plane goes through sRGB's blue for protans and deutans, and red for tritans.
+        let anchor = match self.deficiency {
+          Deficiency::Protan | Deficiency::Deutan => [T::zero(), T::zero(), T::one()],
+          Deficiency::Tritan => [T::one(), T::zero(), T::zero()],
+        };
+        cross(white, lms_from_linrgb(anchor))
+      }
+      _ => {
+        let (first, second) = match self.deficiency {
+          Deficiency::Protan | Deficiency::Deutan => (CHROMATICITY_475_NM, CHROMATICITY_575_NM),
+          Deficiency::Tritan => (CHROMATICITY_485_NM, CHROMATICITY_660_NM),
+        };
+        let (first, second) = (lms_from_chromaticity(first), lms_from_chromaticity(second));
+        // Colors on the side of the first anchor, split by the plane through white and the axis
+        // of the missing cone, land on the first half-plane.
+        let mut axis = [T::zero(); 3];
+        axis[cone] = T::one();
+        let separation = cross(white, axis);
+        if (dot(lms, separation) >= T::zero()) == (dot(first, separation) >= T::zero()) {
+          cross(white, first)
+        } else {
+          cross(white, second)
+        }
+      }
+    };
+
+    let mut projected = lms;
+    projected[cone] = -(0..3)
+      .filter(|&i| i != cone)
+      .fold(T::zero(), |sum, i| sum + normal[i] * lms[i])
+      / normal[cone];
+    let xyz = utils::math::matrix_multiply(&projected, &utils::math::matrix_inverse(&xyz_to_lms));
+    Gamut::Srgb.linrgb_from_xyz(xyz)
+  }
+}
+
+fn cross<T>(a: [T; 3], b: [T; 3]) -> [T; 3]
+where
+  T: Float,
+{
+  [
+    a[1] * b[2] - a[2] * b[1],
+    a[2] * b[0] - a[0] * b[2],
+    a[0] * b[1] - a[1] * b[0],
+  ]
+}
+
+fn dot<T>(a: [T; 3], b: [T; 3]) -> T
+where
+  T: Float,
+{
+  a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::difference::DeltaE;
+
+  const METHODS: [SimulationMethod; 3] = [
+    SimulationMethod::Brettel1997,
+    SimulationMethod::Vienot1999,
+    SimulationMethod::Machado2009,
+  ];
+
+  #[test]
+  fn keeps_neutral_colors() {
+    for deficiency in Deficiency::ALL {
+      for method in METHODS {
+        let simulation = CvdSimulation::new(deficiency).with_method(method);
+        for argb in [0xff000000, 0xff777777, 0xffffffff, 0x80ffffff] {
+          assert!(DeltaE::Ciede2000.between(simulation.simulate(argb), argb) < 1.0);
+          assert_eq!(simulation.simulate(argb) >> 24, argb >> 24);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn no_severity_is_normal_vision() {
+    for deficiency in Deficiency::ALL {
+      for method in METHODS {
+        let simulation = CvdSimulation::new(deficiency)
+          .with_method(method)
+          .with_severity(0.0);
+        assert_eq!(simulation.simulate(0xff4285f4), 0xff4285f4);
+      }
+    }
+  }
+
+  #[test]
+  fn confuses_colors_along_confusion_lines() {
+    // Red and green for protans and deutans, blue and green for tritans.
+    let pairs = [
+      (Deficiency::Protan, 0xffcc3333, 0xff669900),
+      (Deficiency::Deutan, 0xffcc3333, 0xff669900),
+      (Deficiency::Tritan, 0xff3366ff, 0xff33aa99),
+    ];
+    for (deficiency, one, two) in pairs {
+      let normal = DeltaE::Ciede2000.between(one, two);
+      let mut previous = normal;
+      for severity in [0.5, 1.0] {
+        for method in METHODS {
+          let simulation = CvdSimulation::new(deficiency)
+            .with_method(method)
+            .with_severity(severity);
+          let simulated =
+            DeltaE::Ciede2000.between(simulation.simulate(one), simulation.simulate(two));
+          assert!(simulated < normal, "{deficiency:?} {method:?} {severity}");
+        }
+        let machado = CvdSimulation::new(deficiency).with_severity(severity);
+        let simulated = DeltaE::Ciede2000.between(machado.simulate(one), machado.simulate(two));
+        assert!(simulated < previous, "{deficiency:?} {severity}");
+        previous = simulated;
+      }
+    }
+  }
+}
@@ -1,3 +1,7 @@
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::utils;
 
 /// The minimum contrast ratio of two colors.
@@ -15,6 +19,40 @@ pub const RATIO_30: f64 = 3.0;
 pub const RATIO_45: f64 = 4.5;
 pub const RATIO_70: f64 = 7.0;
 
+/// The WCAG 2 conformance level a contrast ratio between text and its background reaches.
+///
+/// Levels are ordered, so a ratio reaching [`WcagLevel::Aa`] also reaches [`WcagLevel::AaLarge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum WcagLevel {
+  /// Below [`RATIO_30`]: not even large text is accessible.
+  Fail,
+  /// At least [`RATIO_30`]: level AA for large text, and for non-text elements.
+  AaLarge,
+  /// At least [`RATIO_45`]: level AA for all text, and level AAA for large text.
+  Aa,
+  /// At least [`RATIO_70`]: level AAA for all text.
+  Aaa,
+}
+
+impl WcagLevel {
+  /// The highest level a contrast ratio reaches.
+  pub fn of_ratio<T>(ratio: T) -> Self
+  where
+    T: Float + FromPrimitive,
+  {
+    if ratio >= T::from_f64(RATIO_70).unwrap() {
+      Self::Aaa
+    } else if ratio >= T::from_f64(RATIO_45).unwrap() {
+      Self::Aa
+    } else if ratio >= T::from_f64(RATIO_30).unwrap() {
+      Self::AaLarge
+    } else {
+      Self::Fail
+    }
+  }
+}
+
 /// Given a color and a contrast ratio to reach, the luminance of a color that reaches that ratio
 /// with the color can be calculated. However, that luminance may not contrast as desired, i.e. the
 /// contrast ratio of the input color and the returned luminance may not reach the contrast ratio
@@ -59,10 +97,13 @@ const LUMINANCE_GAMUT_MAP_TOLERANCE: f64 = 0.4;
 /// also known as relative luminance.
 ///
 /// The equation is ratio = lighter Y + 5 / darker Y + 5.
-pub fn ratio_of_ys(y1: f64, y2: f64) -> f64 {
-  let lighter = f64::max(y1, y2);
+pub fn ratio_of_ys<T>(y1: T, y2: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let lighter = T::max(y1, y2);
   let darker = if lighter == y2 { y1 } else { y2 };
-  (lighter + 5.0) / (darker + 5.0)
+  (lighter + T::from_f64(5.0).unwrap()) / (darker + T::from_f64(5.0).unwrap())
 }
 
 /// Contrast ratio of two tones. T in HCT, L* in L*a*b*. Also known as luminance or perpectual
@@ -78,37 +119,55 @@ pub fn ratio_of_ys(y1: f64, y2: f64) -> f64 {
 /// of a ratio, a linear difference. This allows a designer to determine what they need to adjust a
 /// color's lightness to in order to reach their desired contrast, instead of guessing & checking
 /// with hex codes.
-pub fn ratio_of_tones(t1: f64, t2: f64) -> f64 {
-  let t1 = t1.clamp(0.0, 100.0);
-  let t2 = t2.clamp(0.0, 100.0);
+pub fn ratio_of_tones<T>(t1: T, t2: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let t1 = t1.clamp(T::zero(), T::from_f64(100.0).unwrap());
+  let t2 = t2.clamp(T::zero(), T::from_f64(100.0).unwrap());
   ratio_of_ys(
     utils::color::y_from_lstar(t1),
     utils::color::y_from_lstar(t2),
   )
 }
 
+/// Contrast ratio of two colors represented in ARGB, as defined by WCAG 2. Alpha is ignored.
+pub fn ratio_of_argbs<T>(argb1: u32, argb2: u32) -> T
+where
+  T: Float + FromPrimitive,
+{
+  ratio_of_tones(
+    utils::color::lstar_from_argb(argb1),
+    utils::color::lstar_from_argb(argb2),
+  )
+}
+
 /// Returns T in HCT, L* in L*a*b* >= tone parameter that ensures ratio with input T/L*. Returns -1
 /// if ratio cannot be achieved.
-pub fn lighter(tone: f64, ratio: f64) -> f64 {
-  if !(0.0..=100.0).contains(&tone) {
-    return -1.0;
+pub fn lighter<T>(tone: T, ratio: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  if !is_bounded(tone) {
+    return -T::one();
   }
   // Invert the contrast ratio equation to determine lighter Y given a ratio and darker Y.
   let dark_y = utils::color::y_from_lstar(tone);
-  let light_y = ratio * (dark_y + 5.0) - 5.0;
-  if !(0.0..=100.0).contains(&light_y) {
-    return -1.0;
+  let light_y = ratio * (dark_y + T::from_f64(5.0).unwrap()) - T::from_f64(5.0).unwrap();
+  if !is_bounded(light_y) {
+    return -T::one();
   }
   let real_contrast = ratio_of_ys(light_y, dark_y);
   let delta = (real_contrast - ratio).abs();
-  if real_contrast < ratio && delta > CONTRAST_RATIO_EPSILON {
-    return -1.0;
+  if real_contrast < ratio && delta > T::from_f64(CONTRAST_RATIO_EPSILON).unwrap() {
+    return -T::one();
   }
 
-  let return_value = utils::color::lstar_from_y(light_y) + LUMINANCE_GAMUT_MAP_TOLERANCE;
+  let return_value =
+    utils::color::lstar_from_y(light_y) + T::from_f64(LUMINANCE_GAMUT_MAP_TOLERANCE).unwrap();
   // NOMUTANTS--important validation step; functions it is calling may change implementation.
-  if !(0.0..=100.0).contains(&return_value) {
-    return -1.0;
+  if !is_bounded(return_value) {
+    return -T::one();
   }
   return_value
 }
@@ -117,10 +176,13 @@ pub fn lighter(tone: f64, ratio: f64) -> f64 {
 ///
 /// This method is unsafe because the returned value is guaranteed to be in bounds, but, the in
 /// bounds return value may not reach the desired ratio.
-pub fn lighter_unsafe(tone: f64, ratio: f64) -> f64 {
+pub fn lighter_unsafe<T>(tone: T, ratio: T) -> T
+where
+  T: Float + FromPrimitive,
+{
   let lighter_safe = lighter(tone, ratio);
-  if lighter_safe < 0.0 {
-    100.0
+  if lighter_safe < T::zero() {
+    T::from_f64(100.0).unwrap()
   } else {
     lighter_safe
   }
@@ -128,27 +190,31 @@ pub fn lighter_unsafe(tone: f64, ratio: f64) -> f64 {
 
 /// Returns T in HCT, L* in L*a*b* <= tone parameter that ensures ratio with input T/L*. Returns -1
 /// if ratio cannot be achieved.
-pub fn darker(tone: f64, ratio: f64) -> f64 {
-  if !(0.0..=100.0).contains(&tone) {
-    return -1.0;
+pub fn darker<T>(tone: T, ratio: T) -> T
+where
+  T: Float + FromPrimitive,
+{
+  if !is_bounded(tone) {
+    return -T::one();
   }
   // Invert the contrast ratio equation to determine darker Y given a ratio and lighter Y.
   let light_y = utils::color::y_from_lstar(tone);
-  let dark_y = ((light_y + 5.0) / ratio) - 5.0;
-  if !(0.0..=100.0).contains(&dark_y) {
-    return -1.0;
+  let dark_y = ((light_y + T::from_f64(5.0).unwrap()) / ratio) - T::from_f64(5.0).unwrap();
+  if !is_bounded(dark_y) {
+    return -T::one();
   }
   let real_contrast = ratio_of_ys(light_y, dark_y);
   let delta = (real_contrast - ratio).abs();
-  if real_contrast < ratio && delta > CONTRAST_RATIO_EPSILON {
-    return -1.0;
+  if real_contrast < ratio && delta > T::from_f64(CONTRAST_RATIO_EPSILON).unwrap() {
+    return -T::one();
   }
 
   // For information on 0.4 constant, see comment in lighter(tone, ratio).
-  let return_value = utils::color::lstar_from_y(dark_y) - LUMINANCE_GAMUT_MAP_TOLERANCE;
+  let return_value =
+    utils::color::lstar_from_y(dark_y) - T::from_f64(LUMINANCE_GAMUT_MAP_TOLERANCE).unwrap();
   // NOMUTANTS--important validation step; functions it is calling may change implementation.
-  if !(0.0..=100.0).contains(&return_value) {
-    return -1.0;
+  if !is_bounded(return_value) {
+    return -T::one();
   }
   return_value
 }
@@ -157,9 +223,20 @@ pub fn darker(tone: f64, ratio: f64) -> f64 {
 ///
 /// This method is unsafe because the returned value is guaranteed to be in bounds, but, the in
 /// bounds return value may not reach the desired ratio.
-pub fn darker_unsafe(tone: f64, ratio: f64) -> f64 {
+pub fn darker_unsafe<T>(tone: T, ratio: T) -> T
+where
+  T: Float + FromPrimitive,
+{
   let darker_safe = darker(tone, ratio);
-  f64::max(0.0, darker_safe)
+  T::max(T::zero(), darker_safe)
+}
+
+/// Whether a tone or Y is within 0 and 100.
+fn is_bounded<T>(value: T) -> bool
+where
+  T: Float + FromPrimitive,
+{
+  T::zero() <= value && value <= T::from_f64(100.0).unwrap()
 }
 
 #[cfg(test)]
@@ -211,4 +288,34 @@ mod tests {
   fn darker_unsafe_returns_min_tone() {
     assert_approx_eq!(0.0, darker_unsafe(0.0, 2.0), 0.001)
   }
+
+  #[test]
+  fn ratio_of_argbs_reaches_wcag_levels() {
+    assert_approx_eq!(21.0, ratio_of_argbs(0xff000000, 0xffffffff), 3.0);
+    assert_eq!(
+      WcagLevel::of_ratio(ratio_of_argbs::<f64>(0xff000000, 0xffffffff)),
+      WcagLevel::Aaa
+    );
+    // #767676 is the lightest gray that reaches 4.5 with white.
+    assert_eq!(
+      WcagLevel::of_ratio(ratio_of_argbs::<f64>(0xff767676, 0xffffffff)),
+      WcagLevel::Aa
+    );
+    assert_eq!(
+      WcagLevel::of_ratio(ratio_of_argbs::<f64>(0xff777777, 0xffffffff)),
+      WcagLevel::AaLarge
+    );
+    assert_eq!(WcagLevel::of_ratio(1.0), WcagLevel::Fail);
+    assert!(WcagLevel::Aa > WcagLevel::AaLarge);
+  }
+
+  #[test]
+  fn f32_matches_f64() {
+    assert_approx_eq!(
+      ratio_of_tones(40.0_f32, 90.0) as f64,
+      ratio_of_tones(40.0, 90.0),
+      3.0
+    );
+    assert_approx_eq!(darker(90.0_f32, 4.5) as f64, darker(90.0, 4.5), 3.0);
+  }
 }
@@ -0,0 +1,310 @@
+use num_traits::{Float, FloatConst, FromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  hct::{Cam16, Hct},
+  utils,
+};
+
+/// A formula for the perceptual difference between two colors, known as delta E.
+///
+/// Differences are measured between sRGB colors in the default viewing conditions, and are on
+/// comparable scales: a difference of about [`DeltaE::noticeable_difference`] is the smallest
+/// most observers can see side by side.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum DeltaE {
+  /// Euclidean distance in L*a*b*. Fast, but overstates differences between saturated colors.
+  Cie76,
+  /// The CIE 1994 formula, with the weights for graphic arts. Asymmetric, the first color is the
+  /// reference.
+  Cie94,
+  /// The CIE 2000 formula, the most accurate for small differences.
+  #[default]
+  Ciede2000,
+  /// Euclidean distance in CAM16-UCS, the uniform color space of the model HCT is based on. Unlike
+  /// [`Cam16::distance`], the result is not remapped.
+  Cam16Ucs,
+  /// Euclidean distance in Oklab, with coordinates scaled by 100 so lightness ranges from 0 to 100
+  /// like L* does.
+  Oklab,
+}
+
+impl DeltaE {
+  /// The difference between two colors represented in ARGB. Alpha is ignored.
+  pub fn between(self, one: u32, two: u32) -> f64 {
+    match self {
+      Self::Cie76 => euclidean(
+        &utils::color::lab_from_argb(one),
+        &utils::color::lab_from_argb(two),
+      ),
+      Self::Cie94 => cie94(
+        &utils::color::lab_from_argb(one),
+        &utils::color::lab_from_argb(two),
+      ),
+      Self::Ciede2000 => ciede2000(
+        &utils::color::lab_from_argb(one),
+        &utils::color::lab_from_argb(two),
+      ),
+      Self::Cam16Ucs => euclidean(&Self::ucs_from_argb(one), &Self::ucs_from_argb(two)),
+      Self::Oklab => {
+        euclidean(
+          &utils::color::oklab_from_argb::<f64>(one),
+          &utils::color::oklab_from_argb(two),
+        ) * 100.0
+      }
+    }
+  }
+
+  /// The difference between the sRGB colors of two HCT colors.
+  pub fn between_hct<T>(self, one: &Hct<T>, two: &Hct<T>) -> f64
+  where
+    T: Float + FloatConst + FromPrimitive,
+  {
+    self.between(one.to_int(), two.to_int())
+  }
+
+  /// The smallest difference most observers can see between two colors side by side, also known
+  /// as the just noticeable difference.
+  pub fn noticeable_difference(self) -> f64 {
+    match self {
+      Self::Cie76 => 2.3,
+      Self::Cie94 | Self::Ciede2000 | Self::Cam16Ucs => 1.0,
+      Self::Oklab => 2.0,
+    }
+  }
+
+  /// Whether two colors represented in ARGB differ by at least
+  /// [`DeltaE::noticeable_difference`].
+  pub fn are_distinguishable(self, one: u32, two: u32) -> bool {
+    self.between(one, two) >= self.noticeable_difference()
+  }
+
+  fn ucs_from_argb(argb: u32) -> [f64; 3] {
+    let cam = Cam16::from_int(argb);
+    [cam.jstar(), cam.astar(), cam.bstar()]
+  }
+}
+
+/// The Euclidean distance between two colors, which is delta E in L*a*b* (CIE76), CAM16-UCS and
+/// Oklab.
+pub fn euclidean<T>(one: &[T; 3], two: &[T; 3]) -> T
+where
+  T: Float,
+{
+  squared_euclidean(one, two).sqrt()
+}
+
+/// The squared Euclidean distance between two colors.
+///
+/// Orders pairs of colors the same as [`euclidean`], and is cheaper to compute, which makes it the
+/// distance quantizers compare pixels with.
+pub fn squared_euclidean<T>(one: &[T; 3], two: &[T; 3]) -> T
+where
+  T: Float,
+{
+  let d_0 = one[0] - two[0];
+  let d_1 = one[1] - two[1];
+  let d_2 = one[2] - two[2];
+  d_0 * d_0 + d_1 * d_1 + d_2 * d_2
+}
+
+/// The CIE 1994 color difference between two L*a*b* colors, with the weights for graphic arts.
+///
+/// Chroma and hue differences are weighted by the chroma of `reference`, so swapping the colors can
+/// change the result.
+pub fn cie94<T>(reference: &[T; 3], sample: &[T; 3]) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let [l_1, a_1, b_1] = *reference;
+  let [l_2, a_2, b_2] = *sample;
+  let c_1 = a_1.hypot(b_1);
+  let c_2 = a_2.hypot(b_2);
+  let d_l = l_1 - l_2;
+  let d_c = c_1 - c_2;
+  let d_a = a_1 - a_2;
+  let d_b = b_1 - b_2;
+  // Rounding can make the squared hue difference slightly negative for colors of the same hue.
+  let d_h_squared = (d_a * d_a + d_b * d_b - d_c * d_c).max(T::zero());
+  let s_c = T::one() + T::from_f64(0.045).unwrap() * c_1;
+  let s_h = T::one() + T::from_f64(0.015).unwrap() * c_1;
+  (d_l * d_l + (d_c / s_c).powi(2) + d_h_squared / (s_h * s_h)).sqrt()
+}
+
+/// The CIEDE2000 color difference between two L*a*b* colors, with unit weights.
+///
+/// Follows the implementation notes of Sharma, Wu and Dalal (2005).
+pub fn ciede2000<T>(one: &[T; 3], two: &[T; 3]) -> T
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  let [l_1, a_1, b_1] = *one;
+  let [l_2, a_2, b_2] = *two;
+  let two_f = T::from_f64(2.0).unwrap();
+  let pow_25_7 = T::from_f64(25.0f64.powi(7)).unwrap();
+
+  let c_mean = (a_1.hypot(b_1) + a_2.hypot(b_2)) / two_f;
+  let g =
+    T::from_f64(0.5).unwrap() * (T::one() - (c_mean.powi(7) / (c_mean.powi(7) + pow_25_7)).sqrt());
+  let a_1_prime = (T::one() + g) * a_1;
+  let a_2_prime = (T::one() + g) * a_2;
+  let c_1_prime = a_1_prime.hypot(b_1);
+  let c_2_prime = a_2_prime.hypot(b_2);
+  let h_1_prime = hue_degrees(a_1_prime, b_1);
+  let h_2_prime = hue_degrees(a_2_prime, b_2);
+
+  let d_l_prime = l_2 - l_1;
+  let d_c_prime = c_2_prime - c_1_prime;
+  let achromatic = c_1_prime * c_2_prime == T::zero();
+  let d_h_prime = if achromatic {
+    T::zero()
+  } else {
+    let d_h = h_2_prime - h_1_prime;
+    if d_h > T::from_f64(180.0).unwrap() {
+      d_h - T::from_f64(360.0).unwrap()
+    } else if d_h < T::from_f64(-180.0).unwrap() {
+      d_h + T::from_f64(360.0).unwrap()
+    } else {
+      d_h
+    }
+  };
+  let d_big_h_prime =
+    two_f * (c_1_prime * c_2_prime).sqrt() * (d_h_prime / two_f).to_radians().sin();
+
+  let l_mean_prime = (l_1 + l_2) / two_f;
+  let c_mean_prime = (c_1_prime + c_2_prime) / two_f;
+  let h_mean_prime = if achromatic {
+    h_1_prime + h_2_prime
+  } else if (h_1_prime - h_2_prime).abs() <= T::from_f64(180.0).unwrap() {
+    (h_1_prime + h_2_prime) / two_f
+  } else if h_1_prime + h_2_prime < T::from_f64(360.0).unwrap() {
+    (h_1_prime + h_2_prime + T::from_f64(360.0).unwrap()) / two_f
+  } else {
+    (h_1_prime + h_2_prime - T::from_f64(360.0).unwrap()) / two_f
+  };
+
+  let t = T::one()
+    - T::from_f64(0.17).unwrap() * cos_degrees(h_mean_prime - T::from_f64(30.0).unwrap())
+    + T::from_f64(0.24).unwrap() * cos_degrees(two_f * h_mean_prime)
+    + T::from_f64(0.32).unwrap()
+      * cos_degrees(T::from_f64(3.0).unwrap() * h_mean_prime + T::from_f64(6.0).unwrap())
+    - T::from_f64(0.20).unwrap()
+      * cos_degrees(T::from_f64(4.0).unwrap() * h_mean_prime - T::from_f64(63.0).unwrap());
+  let d_theta = T::from_f64(30.0).unwrap()
+    * (-((h_mean_prime - T::from_f64(275.0).unwrap()) / T::from_f64(25.0).unwrap()).powi(2)).exp();
+  let r_c = two_f * (c_mean_prime.powi(7) / (c_mean_prime.powi(7) + pow_25_7)).sqrt();
+  let l_offset_squared = (l_mean_prime - T::from_f64(50.0).unwrap()).powi(2);
+  let s_l = T::one()
+    + T::from_f64(0.015).unwrap() * l_offset_squared
+      / (T::from_f64(20.0).unwrap() + l_offset_squared).sqrt();
+  let s_c = T::one() + T::from_f64(0.045).unwrap() * c_mean_prime;
+  let s_h = T::one() + T::from_f64(0.015).unwrap() * c_mean_prime * t;
+  let r_t = -(two_f * d_theta).to_radians().sin() * r_c;
+
+  let l_term = d_l_prime / s_l;
+  let c_term = d_c_prime / s_c;
+  let h_term = d_big_h_prime / s_h;
+  (l_term * l_term + c_term * c_term + h_term * h_term + r_t * c_term * h_term).sqrt()
+}
+
+/// The hue angle of a* and b* in degrees, from 0 to 360, or 0 for neutral colors.
+fn hue_degrees<T>(a: T, b: T) -> T
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  if a == T::zero() && b == T::zero() {
+    T::zero()
+  } else {
+    utils::math::sanitize_degrees(b.atan2(a).to_degrees())
+  }
+}
+
+fn cos_degrees<T>(degrees: T) -> T
+where
+  T: Float,
+{
+  degrees.to_radians().cos()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::test::{approx_eq, assert_approx_eq};
+
+  #[test]
+  fn ciede2000_matches_sharma() {
+    // Pairs from the test data of Sharma, Wu and Dalal (2005).
+    let pairs = [
+      ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+      ([50.0, -1.3802, -84.2814], [50.0, 0.0, -82.7485], 1.0),
+      ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+      ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0011], 7.2195),
+      ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+      (
+        [60.2574, -34.0099, 36.2677],
+        [60.4626, -34.1751, 39.4387],
+        1.2644,
+      ),
+      ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082),
+    ];
+    for (one, two, expected) in pairs {
+      assert_approx_eq!(ciede2000(&one, &two), expected, 4.0);
+      assert_approx_eq!(ciede2000(&two, &one), expected, 4.0);
+    }
+  }
+
+  #[test]
+  fn cie94_weights_by_reference() {
+    let reference = [50.0, 2.6772, -79.7751];
+    let sample = [50.0, 0.0, -82.7485];
+    assert_approx_eq!(cie94(&reference, &sample), 1.395, 3.0);
+    assert!(cie94(&sample, &reference) < cie94(&reference, &sample));
+  }
+
+  #[test]
+  fn metrics_agree_on_order() {
+    let metrics = [
+      DeltaE::Cie76,
+      DeltaE::Cie94,
+      DeltaE::Ciede2000,
+      DeltaE::Cam16Ucs,
+      DeltaE::Oklab,
+    ];
+    for delta_e in metrics {
+      assert_eq!(delta_e.between(0xff4285f4, 0xff4285f4), 0.0);
+      assert!(!delta_e.are_distinguishable(0xff4285f4, 0xff4285f4));
+      let near = delta_e.between(0xff4285f4, 0xff4286f4);
+      let far = delta_e.between(0xff4285f4, 0xffdb4437);
+      assert!(near < delta_e.noticeable_difference());
+      assert!(far > 10.0 * delta_e.noticeable_difference());
+      assert!(delta_e.are_distinguishable(0xff4285f4, 0xffdb4437));
+    }
+    assert_eq!(
+      DeltaE::Ciede2000.between_hct(&Hct::from_int(0xff4285f4), &Hct::from_int(0xffdb4437)),
+      DeltaE::Ciede2000.between(0xff4285f4, 0xffdb4437)
+    );
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn scheme_roles_are_distinguishable() {
+    use crate::dynamiccolor::{DynamicSchemeBuilder, MaterialDynamicColors};
+
+    let scheme = DynamicSchemeBuilder::default()
+      .source_color_hct(Hct::from_int(0xff4285f4))
+      .build();
+    let colors = MaterialDynamicColors::new();
+    assert!(scheme.are_distinguishable(&colors.primary(), &colors.on_primary(), DeltaE::Ciede2000));
+    assert!(!scheme.are_distinguishable(
+      &colors.primary(),
+      &colors.surface_tint(),
+      DeltaE::Ciede2000
+    ));
+    assert_eq!(
+      scheme.difference(&colors.primary(), &colors.surface_tint(), DeltaE::Ciede2000),
+      0.0
+    );
+  }
+}
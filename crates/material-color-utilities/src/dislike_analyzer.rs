@@ -1,17 +1,249 @@
-use crate::hct::Hct;
+use ordered_float::NotNan;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+use crate::{hct::Hct, utils};
+
+/// Check whether a color is disliked, using the default [`DislikeAnalyzer`].
+///
+/// Disliked is defined as a dark yellow-green that is not neutral.
 pub fn is_disliked(hct: &Hct) -> bool {
-  let hue_passes = hct.hue().round() >= 90.0 && hct.hue().round() <= 111.0;
-  let chroma_passes = hct.chroma().round() > 16.0;
-  let tone_passes = hct.tone().round() < 65.0;
-  hue_passes && chroma_passes && tone_passes
+  DislikeAnalyzer::default().is_disliked(hct)
 }
 
+/// If a color is disliked, lighten it to make it likable, using the default [`DislikeAnalyzer`].
 pub fn fix_if_disliked(hct: Hct) -> Hct {
-  if is_disliked(&hct) {
-    Hct::from(hct.hue(), hct.chroma(), 70.0)
-  } else {
-    hct
+  DislikeAnalyzer::default().fix_if_disliked(hct)
+}
+
+/// Checks and fixes universally disliked colors.
+///
+/// Color science studies of color preference indicate universal distaste for dark yellow-greens,
+/// and also show this is correlated to distaste for biological waste and rotting food. The default
+/// analyzer has a single region covering those colors, which it fixes by raising their tone.
+///
+/// Custom regions can be added, e.g. for colors a brand never wants to ship.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct DislikeAnalyzer {
+  regions: Vec<DislikeRegion>,
+}
+
+impl Default for DislikeAnalyzer {
+  fn default() -> Self {
+    Self::new(vec![DislikeRegion::bile()])
+  }
+}
+
+impl DislikeAnalyzer {
+  pub fn new(regions: Vec<DislikeRegion>) -> Self {
+    Self { regions }
+  }
+
+  /// An analyzer that doesn't dislike any color.
+  pub fn empty() -> Self {
+    Self::new(vec![])
+  }
+
+  /// Add a region to the analyzer, checked after the existing ones.
+  pub fn region(mut self, region: DislikeRegion) -> Self {
+    self.regions.push(region);
+    self
+  }
+
+  pub fn regions(&self) -> &[DislikeRegion] {
+    &self.regions
+  }
+
+  /// Returns whether the color falls in any of the disliked regions.
+  pub fn is_disliked(&self, hct: &Hct) -> bool {
+    self.regions.iter().any(|region| region.contains(hct))
+  }
+
+  /// Fixes the color if it is disliked.
+  ///
+  /// Regions are checked in order, and each region containing the color applies its fix. Colors
+  /// that aren't disliked are returned unchanged.
+  pub fn fix_if_disliked(&self, hct: Hct) -> Hct {
+    self.regions.iter().fold(hct, |hct, region| {
+      if region.contains(&hct) {
+        region.fix().apply(&hct)
+      } else {
+        hct
+      }
+    })
+  }
+}
+
+/// A region of HCT colors that are disliked, with how to fix them.
+///
+/// Colors are compared after rounding their hue, chroma and tone to integers. The hue range is
+/// inclusive, and wraps around 0° when `min_hue` is greater than `max_hue`. The chroma and tone
+/// ranges are exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct DislikeRegion {
+  min_hue: NotNan<f64>,
+  max_hue: NotNan<f64>,
+  min_chroma: NotNan<f64>,
+  max_chroma: NotNan<f64>,
+  min_tone: NotNan<f64>,
+  max_tone: NotNan<f64>,
+  fix: DislikeFix,
+}
+
+impl DislikeRegion {
+  /// A region covering every chroma and tone of the hues from `min_hue` to `max_hue`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a hue is NaN.
+  pub fn new(min_hue: f64, max_hue: f64, fix: DislikeFix) -> Self {
+    Self {
+      min_hue: NotNan::new(min_hue).unwrap(),
+      max_hue: NotNan::new(max_hue).unwrap(),
+      min_chroma: NotNan::new(f64::NEG_INFINITY).unwrap(),
+      max_chroma: NotNan::new(f64::INFINITY).unwrap(),
+      min_tone: NotNan::new(f64::NEG_INFINITY).unwrap(),
+      max_tone: NotNan::new(f64::INFINITY).unwrap(),
+      fix,
+    }
+  }
+
+  /// Dark yellow-greens that are not neutral, fixed by raising their tone to 70.
+  pub fn bile() -> Self {
+    Self::new(90.0, 111.0, DislikeFix::raise_tone(70.0))
+      .chroma_above(16.0)
+      .tone_below(65.0)
+  }
+
+  /// Only cover colors with a chroma above `chroma`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `chroma` is NaN.
+  pub fn chroma_above(mut self, chroma: f64) -> Self {
+    self.min_chroma = NotNan::new(chroma).unwrap();
+    self
+  }
+
+  /// Only cover colors with a chroma below `chroma`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `chroma` is NaN.
+  pub fn chroma_below(mut self, chroma: f64) -> Self {
+    self.max_chroma = NotNan::new(chroma).unwrap();
+    self
+  }
+
+  /// Only cover colors with a tone above `tone`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `tone` is NaN.
+  pub fn tone_above(mut self, tone: f64) -> Self {
+    self.min_tone = NotNan::new(tone).unwrap();
+    self
+  }
+
+  /// Only cover colors with a tone below `tone`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `tone` is NaN.
+  pub fn tone_below(mut self, tone: f64) -> Self {
+    self.max_tone = NotNan::new(tone).unwrap();
+    self
+  }
+
+  pub fn min_hue(&self) -> f64 {
+    *self.min_hue
+  }
+
+  pub fn max_hue(&self) -> f64 {
+    *self.max_hue
+  }
+
+  pub fn min_chroma(&self) -> f64 {
+    *self.min_chroma
+  }
+
+  pub fn max_chroma(&self) -> f64 {
+    *self.max_chroma
+  }
+
+  pub fn min_tone(&self) -> f64 {
+    *self.min_tone
+  }
+
+  pub fn max_tone(&self) -> f64 {
+    *self.max_tone
+  }
+
+  pub fn fix(&self) -> DislikeFix {
+    self.fix
+  }
+
+  pub fn contains(&self, hct: &Hct) -> bool {
+    let hue = hct.hue().round();
+    let chroma = hct.chroma().round();
+    let tone = hct.tone().round();
+    let hue_passes = if self.min_hue <= self.max_hue {
+      hue >= *self.min_hue && hue <= *self.max_hue
+    } else {
+      hue >= *self.min_hue || hue <= *self.max_hue
+    };
+    let chroma_passes = chroma > *self.min_chroma && chroma < *self.max_chroma;
+    let tone_passes = tone > *self.min_tone && tone < *self.max_tone;
+    hue_passes && chroma_passes && tone_passes
+  }
+}
+
+/// How a [`DislikeRegion`] fixes the colors it contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum DislikeFix {
+  /// Set the tone to the given tone, if it is lower.
+  RaiseTone(NotNan<f64>),
+  /// Rotate the hue by the given number of degrees.
+  RotateHue(NotNan<f64>),
+  /// Set the chroma to the given chroma, if it is higher.
+  CapChroma(NotNan<f64>),
+}
+
+impl DislikeFix {
+  /// # Panics
+  ///
+  /// Panics if `tone` is NaN.
+  pub fn raise_tone(tone: f64) -> Self {
+    Self::RaiseTone(NotNan::new(tone).unwrap())
+  }
+
+  /// # Panics
+  ///
+  /// Panics if `degrees` is NaN.
+  pub fn rotate_hue(degrees: f64) -> Self {
+    Self::RotateHue(NotNan::new(degrees).unwrap())
+  }
+
+  /// # Panics
+  ///
+  /// Panics if `chroma` is NaN.
+  pub fn cap_chroma(chroma: f64) -> Self {
+    Self::CapChroma(NotNan::new(chroma).unwrap())
+  }
+
+  pub fn apply(&self, hct: &Hct) -> Hct {
+    match *self {
+      Self::RaiseTone(tone) => Hct::from(hct.hue(), hct.chroma(), hct.tone().max(*tone)),
+      Self::RotateHue(degrees) => Hct::from(
+        utils::math::sanitize_degrees(hct.hue() + *degrees),
+        hct.chroma(),
+        hct.tone(),
+      ),
+      Self::CapChroma(chroma) => Hct::from(hct.hue(), hct.chroma().min(*chroma), hct.tone()),
+    }
   }
 }
 
@@ -48,4 +280,25 @@ mod tests {
       assert!(!is_disliked(&likable));
     }
   }
+
+  #[test]
+  fn custom_regions() {
+    let analyzer = DislikeAnalyzer::empty()
+      .region(DislikeRegion::new(350.0, 10.0, DislikeFix::rotate_hue(30.0)).chroma_above(40.0))
+      .region(DislikeRegion::new(200.0, 300.0, DislikeFix::cap_chroma(20.0)).tone_below(40.0));
+
+    let pink = Hct::from(355.0, 60.0, 50.0);
+    assert!(analyzer.is_disliked(&pink));
+    let fixed = analyzer.fix_if_disliked(pink);
+    assert!(!analyzer.is_disliked(&fixed));
+    assert!((fixed.hue() - 25.0).abs() < 2.0);
+
+    let navy = Hct::from(260.0, 40.0, 20.0);
+    assert!(analyzer.is_disliked(&navy));
+    let fixed = analyzer.fix_if_disliked(navy);
+    assert!(fixed.chroma() <= 20.5);
+
+    let bile = Hct::from_int(0xff95884b);
+    assert!(!analyzer.is_disliked(&bile));
+  }
 }
@@ -1,7 +1,6 @@
 #![allow(deprecated)]
 
 use crate::{
-  contrast,
   dynamiccolor::{ColorCalculationSpec, DeltaConstraint, TonePolarity},
   hct::Hct,
 };
@@ -47,28 +46,36 @@ impl ColorCalculationSpec for ColorCalculationSpec2021 {
       // 1st round: solve to min, each
       if let (Some(bg), Some(n_contrast_curve), Some(f_contrast_curve)) = (
         color.background().and_then(|mut f| f(scheme)),
-        nearer.contrast_curve().and_then(|mut f| f(scheme)),
-        farther.contrast_curve().and_then(|mut f| f(scheme)),
+        nearer
+          .contrast_curve()
+          .and_then(|mut f| f(scheme))
+          .map(|curve| curve.in_algorithm(scheme.contrast_algorithm())),
+        farther
+          .contrast_curve()
+          .and_then(|mut f| f(scheme))
+          .map(|curve| curve.in_algorithm(scheme.contrast_algorithm())),
       ) {
         let n_contrast = n_contrast_curve.get(scheme.contrast_level());
         let f_contrast = f_contrast_curve.get(scheme.contrast_level());
+        let n_algorithm = n_contrast_curve.algorithm();
+        let f_algorithm = f_contrast_curve.algorithm();
         let bg_tone = bg.get_tone(scheme);
 
         // If a color is good enough, it is not adjusted.
         // Initial and adjusted tones for `nearer`
-        if contrast::ratio_of_tones(bg_tone, n_tone) < n_contrast {
-          n_tone = DynamicColor::foreground_tone(bg_tone, n_contrast);
+        if n_algorithm.contrast_of_tones(n_tone, bg_tone) < n_contrast {
+          n_tone = DynamicColor::foreground_tone_for(bg_tone, n_contrast, n_algorithm);
         }
         // Initial and adjusted tones for `farther`
-        if contrast::ratio_of_tones(bg_tone, f_tone) < f_contrast {
-          f_tone = DynamicColor::foreground_tone(bg_tone, f_contrast);
+        if f_algorithm.contrast_of_tones(f_tone, bg_tone) < f_contrast {
+          f_tone = DynamicColor::foreground_tone_for(bg_tone, f_contrast, f_algorithm);
         }
 
         if decreasing_contrast {
           // If decreasing contrast, adjust color to the "bare minimum"
           // that satisfies contrast.
-          n_tone = DynamicColor::foreground_tone(bg_tone, n_contrast);
-          f_tone = DynamicColor::foreground_tone(bg_tone, f_contrast);
+          n_tone = DynamicColor::foreground_tone_for(bg_tone, n_contrast, n_algorithm);
+          f_tone = DynamicColor::foreground_tone_for(bg_tone, f_contrast, f_algorithm);
         }
       }
 
@@ -129,25 +136,29 @@ impl ColorCalculationSpec for ColorCalculationSpec2021 {
       // }
       if let (Some(background), Some(contrast_curve)) = (
         color.background().and_then(|mut f| f(scheme)),
-        color.contrast_curve().and_then(|mut f| f(scheme)),
+        color
+          .contrast_curve()
+          .and_then(|mut f| f(scheme))
+          .map(|curve| curve.in_algorithm(scheme.contrast_algorithm())),
       ) {
         let bg_tone = background.get_tone(scheme);
         let desired_ratio = contrast_curve.get(scheme.contrast_level());
+        let algorithm = contrast_curve.algorithm();
 
-        if contrast::ratio_of_tones(bg_tone, answer) >= desired_ratio {
+        if algorithm.contrast_of_tones(answer, bg_tone) >= desired_ratio {
           // Don't "improve" what's good enough.
         } else {
           // Rough improvement.
-          answer = DynamicColor::foreground_tone(bg_tone, desired_ratio);
+          answer = DynamicColor::foreground_tone_for(bg_tone, desired_ratio, algorithm);
         }
 
         if decreasing_contrast {
-          answer = DynamicColor::foreground_tone(bg_tone, desired_ratio);
+          answer = DynamicColor::foreground_tone_for(bg_tone, desired_ratio, algorithm);
         }
 
         if color.is_background() && (50.0..60.0).contains(&answer) {
           // Must adjust
-          if contrast::ratio_of_tones(49.0, bg_tone) >= desired_ratio {
+          if algorithm.contrast_of_tones(49.0, bg_tone) >= desired_ratio {
             answer = 49.0;
           } else {
             answer = 60.0;
@@ -162,19 +173,19 @@ impl ColorCalculationSpec for ColorCalculationSpec2021 {
           let upper = f64::max(bg_tone_1, bg_tone_2);
           let lower = f64::min(bg_tone_1, bg_tone_2);
 
-          if contrast::ratio_of_tones(upper, answer) >= desired_ratio
-            && contrast::ratio_of_tones(lower, answer) >= desired_ratio
+          if algorithm.contrast_of_tones(answer, upper) >= desired_ratio
+            && algorithm.contrast_of_tones(answer, lower) >= desired_ratio
           {
             return answer;
           }
 
           // The darkest light tone that satisfies the desired ratio,
           // or -1 if such ratio cannot be reached.
-          let light_option = contrast::lighter(upper, desired_ratio);
+          let light_option = algorithm.lighter(upper, desired_ratio);
 
           // The lightest dark tone that satisfies the desired ratio,
           // or -1 if such ratio cannot be reached.
-          let dark_option = contrast::darker(lower, desired_ratio);
+          let dark_option = algorithm.darker(lower, desired_ratio);
 
           // Tones suitable for the foreground.
           let mut availables: Vec<f64> = vec![];
@@ -1,5 +1,4 @@
 use crate::{
-  contrast,
   dynamiccolor::{
     ColorCalculationSpec, DeltaConstraint, DynamicColor, DynamicScheme, TonePolarity,
   },
@@ -24,7 +23,12 @@ impl ColorCalculationSpec for ColorCalculationSpec2025 {
       .map(|mut f| f(scheme))
       .unwrap_or(1.0);
     let chroma = palette.chroma() * chroma_multiplier;
-    Hct::from(hue, chroma, tone)
+    match palette.viewing_conditions() {
+      Some(viewing_conditions) => {
+        Hct::from_in_viewing_conditions(hue, chroma, tone, viewing_conditions)
+      }
+      None => Hct::from(hue, chroma, tone),
+    }
   }
 
   fn get_tone<'a>(&self, scheme: &'a DynamicScheme, color: &DynamicColor<'a>) -> f64 {
@@ -76,16 +80,20 @@ impl ColorCalculationSpec for ColorCalculationSpec2025 {
 
       if let (Some(background), Some(contrast_curve)) = (
         color.background().and_then(|mut f| f(scheme)),
-        color.contrast_curve().and_then(|mut f| f(scheme)),
+        color
+          .contrast_curve()
+          .and_then(|mut f| f(scheme))
+          .map(|curve| curve.in_algorithm(scheme.contrast_algorithm())),
       ) {
         let bg_tone = background.get_tone(scheme);
         let self_contrast = contrast_curve.get(scheme.contrast_level());
-        self_tone = if contrast::ratio_of_tones(bg_tone, self_tone) >= self_contrast
+        let algorithm = contrast_curve.algorithm();
+        self_tone = if algorithm.contrast_of_tones(self_tone, bg_tone) >= self_contrast
           && scheme.contrast_level() >= 0.0
         {
           self_tone
         } else {
-          DynamicColor::foreground_tone(bg_tone, self_contrast)
+          DynamicColor::foreground_tone_for(bg_tone, self_contrast, algorithm)
         };
       }
 
@@ -106,20 +114,24 @@ impl ColorCalculationSpec for ColorCalculationSpec2025 {
 
       if let (Some(background), Some(contrast_curve)) = (
         color.background().and_then(|mut f| f(scheme)),
-        color.contrast_curve().and_then(|mut f| f(scheme)),
+        color
+          .contrast_curve()
+          .and_then(|mut f| f(scheme))
+          .map(|curve| curve.in_algorithm(scheme.contrast_algorithm())),
       ) {
         let bg_tone = background.get_tone(scheme);
         let desired_ratio = contrast_curve.get(scheme.contrast_level());
+        let algorithm = contrast_curve.algorithm();
 
         // Recalculate the tone from desired contrast ratio if the current
         // contrast ratio is not enough or desired contrast level is decreasing
         // (<0).
-        answer = if contrast::ratio_of_tones(bg_tone, answer) >= desired_ratio
+        answer = if algorithm.contrast_of_tones(answer, bg_tone) >= desired_ratio
           && scheme.contrast_level() >= 0.0
         {
           answer
         } else {
-          DynamicColor::foreground_tone(bg_tone, desired_ratio)
+          DynamicColor::foreground_tone_for(bg_tone, desired_ratio, algorithm)
         };
 
         // This can avoid the awkward tones for background colors including the access fixed colors.
@@ -139,19 +151,19 @@ impl ColorCalculationSpec for ColorCalculationSpec2025 {
           let upper = f64::max(bg_tone1, bg_tone2);
           let lower = f64::min(bg_tone1, bg_tone2);
 
-          if contrast::ratio_of_tones(upper, answer) >= desired_ratio
-            && contrast::ratio_of_tones(lower, answer) >= desired_ratio
+          if algorithm.contrast_of_tones(answer, upper) >= desired_ratio
+            && algorithm.contrast_of_tones(answer, lower) >= desired_ratio
           {
             return answer;
           }
 
           // The darkest light tone that satisfies the desired ratio,
           // or -1 if such ratio cannot be reached.
-          let light_option = contrast::lighter(upper, desired_ratio);
+          let light_option = algorithm.lighter(upper, desired_ratio);
 
           // The lightest dark tone that satisfies the desired ratio,
           // or -1 if such ratio cannot be reached.
-          let dark_option = contrast::darker(lower, desired_ratio);
+          let dark_option = algorithm.darker(lower, desired_ratio);
 
           // Tones suitable for the foreground.
           let mut availables: Vec<f64> = vec![];
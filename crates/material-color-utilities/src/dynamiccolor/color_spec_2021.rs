@@ -1,7 +1,6 @@
 #![allow(deprecated)]
 
 use crate::{
-  dislike_analyzer,
   dynamiccolor::{
     ColorSpec, ContrastCurve, DeltaConstraint, DynamicColor, DynamicColorBuilder, DynamicScheme,
     ToneDeltaPair, TonePolarity, Variant,
@@ -649,7 +648,7 @@ impl ColorSpec for ColorSpec2021 {
           if s.is_dark() { 30.0 } else { 90.0 }
         } else {
           let proposed_hct = s.tertiary_palette().hct(s.source_color_hct().tone());
-          dislike_analyzer::fix_if_disliked(proposed_hct).tone()
+          s.dislike_analyzer().fix_if_disliked(proposed_hct).tone()
         }
       })
       .is_background(true)
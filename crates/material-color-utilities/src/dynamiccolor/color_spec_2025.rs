@@ -17,20 +17,29 @@ impl ColorSpec2025 {
     Self {}
   }
 
+  /// Searches from `tone` for the tone that best reaches `chroma` at the palette's hue, in the
+  /// palette's viewing conditions.
   fn find_best_tone_for_chroma(
-    hue: f64,
+    palette: &TonalPalette,
     chroma: f64,
     mut tone: f64,
     by_decreasing_tone: bool,
   ) -> f64 {
+    let hue = palette.hue();
+    let solve = |tone| match palette.viewing_conditions() {
+      Some(viewing_conditions) => {
+        Hct::from_in_viewing_conditions(hue, chroma, tone, viewing_conditions)
+      }
+      None => Hct::from(hue, chroma, tone),
+    };
     let mut answer = tone;
-    let mut best_candidate = Hct::from(hue, chroma, answer);
+    let mut best_candidate = solve(answer);
     while best_candidate.chroma() < chroma {
       if !(0.0..=100.0).contains(&tone) {
         break;
       }
       tone += if by_decreasing_tone { -1.0 } else { 1.0 };
-      let new_candidate = Hct::from(hue, chroma, tone);
+      let new_candidate = solve(tone);
       if best_candidate.chroma() < new_candidate.chroma() {
         best_candidate = new_candidate;
         answer = tone;
@@ -53,12 +62,8 @@ impl ColorSpec2025 {
     upper_bound: f64,
     chroma_multiplier: f64,
   ) -> f64 {
-    let answer = Self::find_best_tone_for_chroma(
-      palette.hue(),
-      palette.chroma() * chroma_multiplier,
-      100.0,
-      true,
-    );
+    let answer =
+      Self::find_best_tone_for_chroma(palette, palette.chroma() * chroma_multiplier, 100.0, true);
     answer.clamp(lower_bound, upper_bound)
   }
 
@@ -67,7 +72,7 @@ impl ColorSpec2025 {
   }
 
   fn t_min_c_clamped(palette: &TonalPalette, lower_bound: f64, upper_bound: f64) -> f64 {
-    let answer = Self::find_best_tone_for_chroma(palette.hue(), palette.chroma(), 0.0, false);
+    let answer = Self::find_best_tone_for_chroma(palette, palette.chroma(), 0.0, false);
     answer.clamp(lower_bound, upper_bound)
   }
 
@@ -1957,3 +1962,28 @@ impl ColorSpec for ColorSpec2025 {
     SPEC_2021.highest_surface(s)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hct::ViewingConditions;
+
+  #[test]
+  fn t_max_c_searches_in_palette_viewing_conditions() {
+    let viewing_conditions = ViewingConditions::d50_print_booth();
+    for hue in [30.0, 140.0, 270.0] {
+      // Unreachable chroma, so every tone is searched for the most chromatic one.
+      let palette =
+        TonalPalette::from_hue_and_chroma(hue, 200.0).with_viewing_conditions(&viewing_conditions);
+      let chroma_at =
+        |tone| Hct::from_in_viewing_conditions(hue, 200.0, tone, &viewing_conditions).chroma();
+      let best_chroma = chroma_at(ColorSpec2025::t_max_c(&palette));
+      for tone in 0..=100 {
+        assert!(
+          best_chroma >= chroma_at(tone as f64),
+          "hue {hue}, tone {tone}"
+        );
+      }
+    }
+  }
+}
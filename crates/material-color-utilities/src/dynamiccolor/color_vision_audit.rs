@@ -0,0 +1,131 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  color_vision::CvdSimulation,
+  difference::DeltaE,
+  dynamiccolor::{DynamicColor, DynamicScheme, MaterialDynamicColors},
+};
+
+/// Two roles that can be told apart with normal vision, but not with a color vision deficiency,
+/// returned by [`DynamicScheme::audit_color_vision`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct RoleConfusion {
+  /// The names of the roles.
+  pub roles: (String, String),
+  /// The colors of the roles.
+  pub argbs: (u32, u32),
+  /// The colors of the roles as seen with the deficiency.
+  pub simulated_argbs: (u32, u32),
+  /// The difference between the colors with normal vision.
+  pub difference: f64,
+  /// The difference between the colors as seen with the deficiency, below the noticeable
+  /// difference of the metric.
+  pub simulated_difference: f64,
+}
+
+impl DynamicScheme {
+  /// Finds the pairs of accent roles that become indistinguishable with a color vision deficiency.
+  ///
+  /// Checks every pair of `primary`, `secondary`, `tertiary` and `error`, and every pair of their
+  /// containers, which are meant to be told apart by hue. Pairs that are already indistinguishable
+  /// with normal vision aren't reported. See also [`DynamicScheme::are_distinguishable_with`] to
+  /// check other roles.
+  pub fn audit_color_vision(
+    &self,
+    simulation: CvdSimulation,
+    delta_e: DeltaE,
+  ) -> Vec<RoleConfusion> {
+    let colors = MaterialDynamicColors::new();
+    let accents = [
+      colors.primary(),
+      colors.secondary(),
+      colors.tertiary(),
+      colors.error(),
+    ];
+    let containers = [
+      colors.primary_container(),
+      colors.secondary_container(),
+      colors.tertiary_container(),
+      colors.error_container(),
+    ];
+    [accents, containers]
+      .iter()
+      .flat_map(|roles| {
+        roles
+          .iter()
+          .enumerate()
+          .flat_map(move |(i, one)| roles[i + 1..].iter().map(move |two| (one, two)))
+      })
+      .filter_map(|(one, two)| self.role_confusion(one, two, simulation, delta_e))
+      .collect()
+  }
+
+  fn role_confusion<'a>(
+    &'a self,
+    one: &DynamicColor<'a>,
+    two: &DynamicColor<'a>,
+    simulation: CvdSimulation,
+    delta_e: DeltaE,
+  ) -> Option<RoleConfusion> {
+    let argbs = (one.get_argb(self), two.get_argb(self));
+    let simulated_argbs = (simulation.simulate(argbs.0), simulation.simulate(argbs.1));
+    let difference = delta_e.between(argbs.0, argbs.1);
+    let simulated_difference = delta_e.between(simulated_argbs.0, simulated_argbs.1);
+    let noticeable = delta_e.noticeable_difference();
+    (difference >= noticeable && simulated_difference < noticeable).then(|| RoleConfusion {
+      roles: (one.name().to_owned(), two.name().to_owned()),
+      argbs,
+      simulated_argbs,
+      difference,
+      simulated_difference,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    color_vision::{Deficiency, SimulationMethod},
+    dynamiccolor::DynamicSchemeBuilder,
+    hct::Hct,
+  };
+
+  #[test]
+  fn flags_roles_confused_with_deficiency() {
+    let scheme = DynamicSchemeBuilder::default()
+      .source_color_hct(Hct::from_int(0xff4285f4))
+      .build();
+    let deutan = CvdSimulation::new(Deficiency::Deutan);
+    let confusions = scheme.audit_color_vision(deutan, DeltaE::Ciede2000);
+    let secondary_tertiary = confusions
+      .iter()
+      .find(|confusion| confusion.roles == ("secondary".to_owned(), "tertiary".to_owned()))
+      .unwrap();
+    assert!(secondary_tertiary.difference > 10.0);
+    assert!(secondary_tertiary.simulated_difference < 1.0);
+    let colors = MaterialDynamicColors::new();
+    assert!(scheme.are_distinguishable(&colors.secondary(), &colors.tertiary(), DeltaE::Ciede2000));
+    assert!(!scheme.are_distinguishable_with(
+      &colors.secondary(),
+      &colors.tertiary(),
+      deutan,
+      DeltaE::Ciede2000
+    ));
+
+    for deficiency in Deficiency::ALL {
+      for method in [SimulationMethod::Brettel1997, SimulationMethod::Vienot1999] {
+        let simulation = CvdSimulation::new(deficiency)
+          .with_method(method)
+          .with_severity(0.0);
+        assert!(
+          scheme
+            .audit_color_vision(simulation, DeltaE::Ciede2000)
+            .is_empty()
+        );
+      }
+    }
+  }
+}
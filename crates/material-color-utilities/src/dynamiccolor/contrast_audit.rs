@@ -0,0 +1,286 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  apca,
+  contrast::{self, WcagLevel},
+  dynamiccolor::{ContrastAlgorithm, DynamicColor, DynamicScheme, MaterialDynamicColors},
+};
+
+/// The contrast of every role of a scheme with its backgrounds, returned by
+/// [`DynamicScheme::audit_contrast`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ContrastAudit {
+  /// The contrast level of the audited scheme, which the required contrasts depend on.
+  pub contrast_level: f64,
+  /// A check for each role with a contrast curve and each of its backgrounds, in the order of
+  /// [`MaterialDynamicColors::all_dynamic_colors`].
+  pub checks: Vec<ContrastCheck>,
+}
+
+impl ContrastAudit {
+  /// Whether every role reaches the contrast its curve requires.
+  ///
+  /// At high contrast levels, curves can ask for more contrast than the palettes can reach, such
+  /// as a ratio of 21 against a background that isn't black or white, so this is stricter than
+  /// conformance to a [`WcagLevel`].
+  pub fn passes(&self) -> bool {
+    self.checks.iter().all(|check| check.passes)
+  }
+
+  /// The checks of roles that don't reach the contrast their curve requires.
+  pub fn failures(&self) -> impl Iterator<Item = &ContrastCheck> {
+    self.checks.iter().filter(|check| !check.passes)
+  }
+
+  /// The lowest WCAG 2 level reached by the checked pairs that
+  /// [require legibility](ContrastCheck::requires_legibility), or `None` if there are none.
+  ///
+  /// Decorative pairs, like outlines on surfaces, only need a ratio of 1, and would otherwise make
+  /// every scheme fail.
+  pub fn wcag_level(&self) -> Option<WcagLevel> {
+    self
+      .checks
+      .iter()
+      .filter(|check| check.requires_legibility())
+      .map(|check| check.wcag_level)
+      .min()
+  }
+}
+
+/// The contrast of a role with one of its backgrounds, in a [`ContrastAudit`].
+///
+/// Contrasts are measured on the displayed, opaque colors, not on their tones.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ContrastCheck {
+  /// The name of the role.
+  pub foreground: String,
+  /// The name of the role's background, or second background.
+  pub background: String,
+  pub foreground_argb: u32,
+  pub background_argb: u32,
+  /// What the role's contrast curve measures in the scheme, and so what `achieved` and `required`
+  /// are. See [`DynamicScheme::contrast_algorithm`].
+  pub algorithm: ContrastAlgorithm,
+  /// The contrast the role reaches: a contrast ratio, or the absolute value of an APCA Lc.
+  pub achieved: f64,
+  /// The contrast the role's curve requires at the scheme's contrast level.
+  pub required: f64,
+  /// Whether `achieved` reaches `required`.
+  pub passes: bool,
+  /// The WCAG 2 contrast ratio, whatever the algorithm of the curve.
+  pub ratio: f64,
+  /// The WCAG 2 level `ratio` reaches.
+  pub wcag_level: WcagLevel,
+}
+
+impl ContrastCheck {
+  /// Whether the role's curve requires at least a ratio of [`contrast::RATIO_30`], the WCAG 2
+  /// minimum for large text and non-text elements, or the APCA Lc that ratio maps to,
+  /// [`apca::LC_45`].
+  pub fn requires_legibility(&self) -> bool {
+    match self.algorithm {
+      ContrastAlgorithm::Wcag2 => self.required >= contrast::RATIO_30,
+      ContrastAlgorithm::Apca => self.required >= apca::LC_45,
+    }
+  }
+}
+
+impl DynamicScheme {
+  /// Checks every role of [`MaterialDynamicColors::all_dynamic_colors`] that has a contrast curve
+  /// against its background and second background, at this scheme's contrast level.
+  ///
+  /// Roles without a background or contrast curve, like the palette key colors, aren't checked.
+  pub fn audit_contrast(&self) -> ContrastAudit {
+    let dynamic_colors = MaterialDynamicColors::new();
+    let checks = dynamic_colors
+      .all_dynamic_colors()
+      .iter()
+      .flat_map(|color| self.contrast_checks(color))
+      .collect();
+    ContrastAudit {
+      contrast_level: self.contrast_level(),
+      checks,
+    }
+  }
+
+  fn contrast_checks<'a>(&'a self, color: &DynamicColor<'a>) -> Vec<ContrastCheck> {
+    let Some(contrast_curve) = color
+      .contrast_curve()
+      .and_then(|mut f| f(self))
+      .map(|curve| curve.in_algorithm(self.contrast_algorithm()))
+    else {
+      return vec![];
+    };
+    let algorithm = contrast_curve.algorithm();
+    let required = contrast_curve.get(self.contrast_level());
+    let foreground_argb = color.get_hct(self).to_int();
+    [
+      color.background().and_then(|mut f| f(self)),
+      color.second_background().and_then(|mut f| f(self)),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|background| {
+      let background_argb = background.get_hct(self).to_int();
+      let ratio = contrast::ratio_of_argbs(foreground_argb, background_argb);
+      let achieved = match algorithm {
+        ContrastAlgorithm::Wcag2 => ratio,
+        ContrastAlgorithm::Apca => apca::lc_of_argbs::<f64>(foreground_argb, background_argb).abs(),
+      };
+      ContrastCheck {
+        foreground: color.name().to_owned(),
+        background: background.name().to_owned(),
+        foreground_argb,
+        background_argb,
+        algorithm,
+        achieved,
+        required,
+        passes: achieved >= required,
+        ratio,
+        wcag_level: WcagLevel::of_ratio(ratio),
+      }
+    })
+    .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    dynamiccolor::{ContrastCurve, DynamicSchemeBuilder, SpecVersion},
+    hct::Hct,
+  };
+
+  #[test]
+  fn audits_roles_against_backgrounds() {
+    let scheme = DynamicSchemeBuilder::default()
+      .source_color_hct(Hct::from_int(0xff4285f4))
+      .build();
+    let audit = scheme.audit_contrast();
+    let on_primary = audit
+      .checks
+      .iter()
+      .find(|check| check.foreground == "on_primary" && check.background == "primary")
+      .unwrap();
+    assert_eq!(on_primary.algorithm, ContrastAlgorithm::Wcag2);
+    assert_eq!(on_primary.required, 7.0);
+    assert_eq!(on_primary.passes, on_primary.achieved >= 7.0);
+    assert_eq!(on_primary.achieved, on_primary.ratio);
+    assert!(on_primary.wcag_level >= WcagLevel::Aa);
+    assert!(
+      audit
+        .checks
+        .iter()
+        .all(|check| check.foreground != "primary_palette_key_color")
+    );
+  }
+
+  #[test]
+  fn wcag_level_ignores_decorative_roles() {
+    let scheme = DynamicSchemeBuilder::default()
+      .source_color_hct(Hct::from_int(0xff4285f4))
+      .build();
+    let audit = scheme.audit_contrast();
+    assert!(
+      audit
+        .checks
+        .iter()
+        .any(|check| !check.requires_legibility() && check.wcag_level == WcagLevel::Fail)
+    );
+    assert!(audit.wcag_level() >= Some(WcagLevel::AaLarge));
+    assert_eq!(
+      audit.wcag_level(),
+      audit
+        .checks
+        .iter()
+        .filter(|check| check.required >= contrast::RATIO_30)
+        .map(|check| check.wcag_level)
+        .min()
+    );
+    let empty = ContrastAudit {
+      contrast_level: 0.0,
+      checks: vec![],
+    };
+    assert_eq!(empty.wcag_level(), None);
+  }
+
+  #[test]
+  fn text_roles_reach_aa() {
+    for contrast_level in [-1.0, 0.0, 0.5, 1.0] {
+      for is_dark in [false, true] {
+        let scheme = DynamicSchemeBuilder::default()
+          .source_color_hct(Hct::from_int(0xff4285f4))
+          .is_dark(is_dark)
+          .contrast_level(contrast_level)
+          .build();
+        let audit = scheme.audit_contrast();
+        assert_eq!(audit.passes(), audit.failures().next().is_none());
+        for check in audit
+          .checks
+          .iter()
+          .filter(|check| check.required >= contrast::RATIO_45)
+        {
+          assert!(check.wcag_level >= WcagLevel::Aa, "{check:?}");
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn apca_schemes_reach_lc_targets() {
+    assert_eq!(
+      ContrastCurve::new(1.5, 3.0, 4.5, 7.0).in_algorithm(ContrastAlgorithm::Apca),
+      ContrastCurve::apca(apca::LC_15, apca::LC_45, apca::LC_60, apca::LC_75)
+    );
+    assert_eq!(
+      ContrastCurve::new(1.0, 6.0, 11.0, 21.0).in_algorithm(ContrastAlgorithm::Apca),
+      ContrastCurve::apca(0.0, 69.0, apca::LC_90, apca::LC_90)
+    );
+    let apca_curve = ContrastCurve::apca(apca::LC_30, apca::LC_45, apca::LC_60, apca::LC_75);
+    assert_eq!(
+      apca_curve.in_algorithm(ContrastAlgorithm::Wcag2),
+      apca_curve
+    );
+
+    for spec_version in [SpecVersion::Spec2021, SpecVersion::Spec2025] {
+      for is_dark in [false, true] {
+        let scheme = DynamicSchemeBuilder::default()
+          .source_color_hct(Hct::from_int(0xff4285f4))
+          .is_dark(is_dark)
+          .spec_version(spec_version)
+          .contrast_algorithm(ContrastAlgorithm::Apca)
+          .build();
+        assert_eq!(scheme.contrast_algorithm(), ContrastAlgorithm::Apca);
+        let audit = scheme.audit_contrast();
+        let on_surface_variant = audit
+          .checks
+          .iter()
+          .find(|check| check.foreground == "on_surface_variant")
+          .unwrap();
+        assert_eq!(on_surface_variant.algorithm, ContrastAlgorithm::Apca);
+        assert!(on_surface_variant.required >= apca::LC_60);
+        // Roles are solved on tones, so text roles reach their Lc between tones, unless they are
+        // already black or white.
+        for check in audit
+          .checks
+          .iter()
+          .filter(|check| check.required >= apca::LC_60)
+        {
+          let lc_of_tones = apca::lc_of_tones::<f64>(
+            Hct::from_int(check.foreground_argb).tone(),
+            Hct::from_int(check.background_argb).tone(),
+          );
+          assert!(
+            lc_of_tones.abs() >= check.required
+              || matches!(check.foreground_argb, 0xff000000 | 0xffffffff),
+            "{spec_version:?} {check:?}"
+          );
+        }
+      }
+    }
+  }
+}
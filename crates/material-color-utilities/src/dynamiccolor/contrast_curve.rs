@@ -1,8 +1,80 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::utils;
+use crate::{apca, contrast, utils};
 
+/// The contrast a [`ContrastCurve`] measures.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ContrastAlgorithm {
+  /// The WCAG 2 contrast ratio, from 1 to 21.
+  #[default]
+  Wcag2,
+  /// The absolute value of the APCA lightness contrast (Lc), from 0 to about 108.
+  Apca,
+}
+
+impl ContrastAlgorithm {
+  /// The contrast of a foreground tone over a background tone.
+  ///
+  /// The WCAG 2 ratio is symmetric, but APCA isn't: the foreground is read as text.
+  pub fn contrast_of_tones(self, foreground_tone: f64, background_tone: f64) -> f64 {
+    match self {
+      Self::Wcag2 => contrast::ratio_of_tones(foreground_tone, background_tone),
+      Self::Apca => apca::lc_of_tones(foreground_tone, background_tone).abs(),
+    }
+  }
+
+  /// Returns a tone lighter than the background that reaches the contrast, or -1 if it can't be
+  /// reached.
+  pub fn lighter(self, background_tone: f64, contrast: f64) -> f64 {
+    match self {
+      Self::Wcag2 => contrast::lighter(background_tone, contrast),
+      Self::Apca => apca::lighter(background_tone, contrast),
+    }
+  }
+
+  /// Returns a tone darker than the background that reaches the contrast, or -1 if it can't be
+  /// reached.
+  pub fn darker(self, background_tone: f64, contrast: f64) -> f64 {
+    match self {
+      Self::Wcag2 => contrast::darker(background_tone, contrast),
+      Self::Apca => apca::darker(background_tone, contrast),
+    }
+  }
+
+  /// Like [`Self::lighter`], but returns 100 if the contrast can't be reached.
+  pub fn lighter_unsafe(self, background_tone: f64, contrast: f64) -> f64 {
+    match self {
+      Self::Wcag2 => contrast::lighter_unsafe(background_tone, contrast),
+      Self::Apca => apca::lighter_unsafe(background_tone, contrast),
+    }
+  }
+
+  /// Like [`Self::darker`], but returns 0 if the contrast can't be reached.
+  pub fn darker_unsafe(self, background_tone: f64, contrast: f64) -> f64 {
+    match self {
+      Self::Wcag2 => contrast::darker_unsafe(background_tone, contrast),
+      Self::Apca => apca::darker_unsafe(background_tone, contrast),
+    }
+  }
+}
+
+/// The APCA Lc targets of WCAG 2 contrast ratios, see [`ContrastCurve::in_algorithm`].
+const LC_OF_RATIOS: [(f64, f64); 6] = [
+  (1.0, 0.0),
+  (1.5, apca::LC_15),
+  (3.0, apca::LC_45),
+  (4.5, apca::LC_60),
+  (7.0, apca::LC_75),
+  (11.0, apca::LC_90),
+];
+
+/// The contrast a color needs with its background, at each contrast level of a scheme.
+///
+/// Curves measure the WCAG 2 contrast ratio unless created with [`ContrastCurve::apca`], in which
+/// case role resolution targets APCA Lc values instead. Schemes can also resolve WCAG 2 curves with
+/// APCA, see [`crate::dynamiccolor::DynamicSchemeBuilder::contrast_algorithm`].
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ContrastCurve {
@@ -10,6 +82,8 @@ pub struct ContrastCurve {
   normal: f64,
   medium: f64,
   high: f64,
+  #[cfg_attr(feature = "serde", serde(default))]
+  algorithm: ContrastAlgorithm,
 }
 
 impl ContrastCurve {
@@ -19,7 +93,66 @@ impl ContrastCurve {
       normal,
       medium,
       high,
+      algorithm: ContrastAlgorithm::Wcag2,
+    }
+  }
+
+  /// A curve of APCA Lc values, like [`apca::LC_60`], rather than contrast ratios.
+  pub const fn apca(low: f64, normal: f64, medium: f64, high: f64) -> Self {
+    Self {
+      low,
+      normal,
+      medium,
+      high,
+      algorithm: ContrastAlgorithm::Apca,
+    }
+  }
+
+  pub const fn algorithm(&self) -> ContrastAlgorithm {
+    self.algorithm
+  }
+
+  /// This curve, measuring contrast with the algorithm.
+  ///
+  /// A WCAG 2 curve converted to APCA has each of its contrast ratios mapped to the Lc recommended
+  /// for similar uses:
+  ///
+  /// | Ratio        | Lc              |
+  /// |--------------|-----------------|
+  /// | 1            | 0               |
+  /// | 1.5          | [`apca::LC_15`] |
+  /// | 3            | [`apca::LC_45`] |
+  /// | 4.5          | [`apca::LC_60`] |
+  /// | 7            | [`apca::LC_75`] |
+  /// | 11 and above | [`apca::LC_90`] |
+  ///
+  /// Ratios in between are interpolated linearly. An Lc doesn't determine a contrast ratio, so APCA
+  /// curves are never converted to WCAG 2, and are returned unchanged.
+  pub fn in_algorithm(self, algorithm: ContrastAlgorithm) -> Self {
+    match (self.algorithm, algorithm) {
+      (ContrastAlgorithm::Wcag2, ContrastAlgorithm::Apca) => Self::apca(
+        Self::lc_of_ratio(self.low),
+        Self::lc_of_ratio(self.normal),
+        Self::lc_of_ratio(self.medium),
+        Self::lc_of_ratio(self.high),
+      ),
+      _ => self,
+    }
+  }
+
+  fn lc_of_ratio(ratio: f64) -> f64 {
+    let (last_ratio, last_lc) = LC_OF_RATIOS[LC_OF_RATIOS.len() - 1];
+    if ratio >= last_ratio {
+      return last_lc;
     }
+    LC_OF_RATIOS
+      .windows(2)
+      .find(|pair| ratio < pair[1].0)
+      .map_or(0.0, |pair| {
+        let ((start_ratio, start_lc), (stop_ratio, stop_lc)) = (pair[0], pair[1]);
+        let amount = ((ratio - start_ratio) / (stop_ratio - start_ratio)).max(0.0);
+        utils::math::lerp(start_lc, stop_lc, amount)
+      })
   }
 
   pub fn get(&self, contrast_level: f64) -> f64 {
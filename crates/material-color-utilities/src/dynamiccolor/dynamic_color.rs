@@ -8,8 +8,7 @@ use std::{
 };
 
 use crate::{
-  contrast,
-  dynamiccolor::{ContrastCurve, DynamicScheme, SpecVersion, ToneDeltaPair},
+  dynamiccolor::{ContrastAlgorithm, ContrastCurve, DynamicScheme, SpecVersion, ToneDeltaPair},
   hct::Hct,
   palettes::TonalPalette,
 };
@@ -157,10 +156,16 @@ impl<'a> DynamicColor<'a> {
   }
 
   pub fn foreground_tone(bg_tone: f64, ratio: f64) -> f64 {
-    let lighter_tone = contrast::lighter_unsafe(bg_tone, ratio);
-    let darker_tone = contrast::darker_unsafe(bg_tone, ratio);
-    let lighter_ratio = contrast::ratio_of_tones(lighter_tone, bg_tone);
-    let darker_ratio = contrast::ratio_of_tones(darker_tone, bg_tone);
+    Self::foreground_tone_for(bg_tone, ratio, ContrastAlgorithm::Wcag2)
+  }
+
+  /// Like [`Self::foreground_tone`], but reaching a contrast measured by `algorithm`, such as an
+  /// APCA Lc value.
+  pub fn foreground_tone_for(bg_tone: f64, ratio: f64, algorithm: ContrastAlgorithm) -> f64 {
+    let lighter_tone = algorithm.lighter_unsafe(bg_tone, ratio);
+    let darker_tone = algorithm.darker_unsafe(bg_tone, ratio);
+    let lighter_ratio = algorithm.contrast_of_tones(lighter_tone, bg_tone);
+    let darker_ratio = algorithm.contrast_of_tones(darker_tone, bg_tone);
     let prefer_lighter = Self::tone_prefers_light_foreground(bg_tone);
 
     if prefer_lighter {
@@ -171,8 +176,13 @@ impl<'a> DynamicColor<'a> {
       // This was observed with Tonal Spot's On Primary Container turning black momentarily between
       // high and max contrast in light mode. PC's standard tone was T90, OPC's was T10, it was
       // light mode, and the contrast level was 0.6568521221032331.
-      let negligible_difference =
-        (lighter_ratio - darker_ratio).abs() < 0.1 && lighter_ratio < ratio && darker_ratio < ratio;
+      let negligible = match algorithm {
+        ContrastAlgorithm::Wcag2 => 0.1,
+        ContrastAlgorithm::Apca => 1.0,
+      };
+      let negligible_difference = (lighter_ratio - darker_ratio).abs() < negligible
+        && lighter_ratio < ratio
+        && darker_ratio < ratio;
       if lighter_ratio >= ratio || lighter_ratio >= darker_ratio || negligible_difference {
         lighter_tone
       } else {
@@ -4,8 +4,14 @@ use num_traits::Zero;
 use ordered_float::NotNan;
 
 use crate::{
-  dynamiccolor::{DynamicColor, MaterialDynamicColors, Platform, SpecVersion, Variant},
-  hct::Hct,
+  color::Argb,
+  color_vision::CvdSimulation,
+  difference::DeltaE,
+  dislike_analyzer::DislikeAnalyzer,
+  dynamiccolor::{
+    ContrastAlgorithm, DynamicColor, MaterialDynamicColors, Platform, SpecVersion, Variant,
+  },
+  hct::{Hct, ViewingConditions},
   palettes::TonalPalette,
   utils,
 };
@@ -19,12 +25,14 @@ pub struct DynamicScheme {
   platform: Platform,
   contrast_level: NotNan<f64>,
   spec_version: SpecVersion,
+  contrast_algorithm: ContrastAlgorithm,
   primary_palette: TonalPalette,
   secondary_palette: TonalPalette,
   tertiary_palette: TonalPalette,
   neutral_palette: TonalPalette,
   neutral_variant_palette: TonalPalette,
   error_palette: TonalPalette,
+  dislike_analyzer: DislikeAnalyzer,
 }
 
 impl DynamicScheme {
@@ -99,12 +107,14 @@ impl DynamicScheme {
       platform,
       contrast_level: NotNan::new(contrast_level).unwrap_or_else(|_| NotNan::zero()),
       spec_version: Self::maybe_fallback_spec_version(spec_version, &variant),
+      contrast_algorithm: ContrastAlgorithm::Wcag2,
       primary_palette,
       secondary_palette,
       tertiary_palette,
       neutral_palette,
       neutral_variant_palette,
       error_palette,
+      dislike_analyzer: DislikeAnalyzer::default(),
     }
   }
 
@@ -136,6 +146,11 @@ impl DynamicScheme {
     &self.spec_version
   }
 
+  /// The contrast roles are resolved with, see [`DynamicSchemeBuilder::contrast_algorithm`].
+  pub fn contrast_algorithm(&self) -> ContrastAlgorithm {
+    self.contrast_algorithm
+  }
+
   pub fn primary_palette(&self) -> &TonalPalette {
     &self.primary_palette
   }
@@ -160,6 +175,12 @@ impl DynamicScheme {
     &self.error_palette
   }
 
+  /// The analyzer used to fix disliked colors in palettes and colors derived from the source
+  /// color.
+  pub fn dislike_analyzer(&self) -> &DislikeAnalyzer {
+    &self.dislike_analyzer
+  }
+
   pub fn get_hct<'a>(&'a self, dynamic_color: &'a DynamicColor<'a>) -> Hct {
     dynamic_color.get_hct(self)
   }
@@ -168,6 +189,57 @@ impl DynamicScheme {
     dynamic_color.get_argb(self)
   }
 
+  /// The perceptual difference between the colors of two roles in this scheme.
+  pub fn difference<'a>(
+    &'a self,
+    one: &DynamicColor<'a>,
+    two: &DynamicColor<'a>,
+    delta_e: DeltaE,
+  ) -> f64 {
+    delta_e.between(one.get_argb(self), two.get_argb(self))
+  }
+
+  /// Whether the colors of two roles in this scheme can be told apart, i.e. differ by at least
+  /// [`DeltaE::noticeable_difference`]. Useful to flag roles that are near-duplicates.
+  pub fn are_distinguishable<'a>(
+    &'a self,
+    one: &DynamicColor<'a>,
+    two: &DynamicColor<'a>,
+    delta_e: DeltaE,
+  ) -> bool {
+    delta_e.are_distinguishable(one.get_argb(self), two.get_argb(self))
+  }
+
+  /// Like [`Self::difference`], but between the colors of the roles as seen with a color vision
+  /// deficiency.
+  pub fn simulated_difference<'a>(
+    &'a self,
+    one: &DynamicColor<'a>,
+    two: &DynamicColor<'a>,
+    simulation: CvdSimulation,
+    delta_e: DeltaE,
+  ) -> f64 {
+    delta_e.between(
+      simulation.simulate(one.get_argb(self)),
+      simulation.simulate(two.get_argb(self)),
+    )
+  }
+
+  /// Like [`Self::are_distinguishable`], but for the colors of the roles as seen with a color
+  /// vision deficiency.
+  pub fn are_distinguishable_with<'a>(
+    &'a self,
+    one: &DynamicColor<'a>,
+    two: &DynamicColor<'a>,
+    simulation: CvdSimulation,
+    delta_e: DeltaE,
+  ) -> bool {
+    delta_e.are_distinguishable(
+      simulation.simulate(one.get_argb(self)),
+      simulation.simulate(two.get_argb(self)),
+    )
+  }
+
   pub fn primary_palette_key_color(&self) -> u32 {
     Self::DYNAMIC_COLORS
       .primary_palette_key_color()
@@ -582,12 +654,15 @@ pub struct DynamicSchemeBuilder {
   platform: Option<Platform>,
   contrast_level: Option<f64>,
   spec_version: Option<SpecVersion>,
+  contrast_algorithm: Option<ContrastAlgorithm>,
   primary_palette: Option<DynamicSchemePalette>,
   secondary_palette: Option<DynamicSchemePalette>,
   tertiary_palette: Option<DynamicSchemePalette>,
   neutral_palette: Option<DynamicSchemePalette>,
   neutral_variant_palette: Option<DynamicSchemePalette>,
   error_palette: Option<DynamicSchemePalette>,
+  dislike_analyzer: Option<DislikeAnalyzer>,
+  viewing_conditions: Option<ViewingConditions>,
 }
 
 impl DynamicSchemeBuilder {
@@ -596,6 +671,11 @@ impl DynamicSchemeBuilder {
     self
   }
 
+  /// Set the source color from an ARGB integer, or any color that converts into [`Argb`].
+  pub fn source_color_argb(self, source_color_argb: impl Into<Argb>) -> Self {
+    self.source_color_hct(Hct::from_int(source_color_argb))
+  }
+
   pub fn variant(mut self, variant: Variant) -> Self {
     self.variant = Some(variant);
     self
@@ -621,6 +701,22 @@ impl DynamicSchemeBuilder {
     self
   }
 
+  /// Set the contrast roles are resolved with, [`ContrastAlgorithm::Wcag2`] by default.
+  ///
+  /// With [`ContrastAlgorithm::Apca`], the contrast ratios of the built-in roles' curves are mapped
+  /// to APCA Lc targets, as described in [`ContrastCurve::in_algorithm`], and roles are resolved to
+  /// reach those instead. Curves that already measure APCA are used as they are.
+  ///
+  /// Roles are solved on tones, from the screen luminance of grays, so the Lc of colorful roles can
+  /// be slightly off their targets, and like ratios, targets that the background doesn't allow
+  /// aren't reached.
+  ///
+  /// [`ContrastCurve::in_algorithm`]: crate::dynamiccolor::ContrastCurve::in_algorithm
+  pub fn contrast_algorithm(mut self, contrast_algorithm: ContrastAlgorithm) -> Self {
+    self.contrast_algorithm = Some(contrast_algorithm);
+    self
+  }
+
   pub fn primary_palette(mut self, primary_palette: TonalPalette) -> Self {
     self.primary_palette = Some(DynamicSchemePalette::Palette(primary_palette));
     self
@@ -686,6 +782,21 @@ impl DynamicSchemeBuilder {
     self
   }
 
+  /// Set the analyzer used to fix disliked colors, e.g. to add regions a brand never wants to
+  /// ship. Defaults to [`DislikeAnalyzer::default`].
+  pub fn dislike_analyzer(mut self, dislike_analyzer: DislikeAnalyzer) -> Self {
+    self.dislike_analyzer = Some(dislike_analyzer);
+    self
+  }
+
+  /// Set the viewing conditions the hue and chroma of palettes are measured in, so colors appear as
+  /// intended in a specific environment, e.g. [`ViewingConditions::dim_room`]. Palettes set
+  /// directly are used as is. Defaults to [`ViewingConditions::default`].
+  pub fn viewing_conditions(mut self, viewing_conditions: ViewingConditions) -> Self {
+    self.viewing_conditions = Some(viewing_conditions);
+    self
+  }
+
   pub fn build(self) -> DynamicScheme {
     let source_color_hct = self
       .source_color_hct
@@ -696,6 +807,12 @@ impl DynamicSchemeBuilder {
       .platform
       .unwrap_or_else(DynamicScheme::default_platform);
     let contrast_level = self.contrast_level.unwrap_or(0.0);
+    let dislike_analyzer = self.dislike_analyzer.unwrap_or_default();
+    let viewing_conditions = self.viewing_conditions;
+    let in_viewing_conditions = |palette: TonalPalette| match &viewing_conditions {
+      Some(viewing_conditions) => palette.with_viewing_conditions(viewing_conditions),
+      None => palette,
+    };
     let spec_version = DynamicScheme::maybe_fallback_spec_version(
       self
         .spec_version
@@ -706,7 +823,13 @@ impl DynamicSchemeBuilder {
     let spec = spec_version.palettes_spec();
     let primary_palette = self.primary_palette.tonal_palette(
       |variant, source_color_hct, is_dark, platform, contrast_level| {
-        spec.primary_palette(variant, source_color_hct, is_dark, platform, contrast_level)
+        in_viewing_conditions(spec.primary_palette(
+          variant,
+          source_color_hct,
+          is_dark,
+          platform,
+          contrast_level,
+        ))
       },
       &variant,
       &source_color_hct,
@@ -716,7 +839,13 @@ impl DynamicSchemeBuilder {
     );
     let secondary_palette = self.secondary_palette.tonal_palette(
       |variant, source_color_hct, is_dark, platform, contrast_level| {
-        spec.secondary_palette(variant, source_color_hct, is_dark, platform, contrast_level)
+        in_viewing_conditions(spec.secondary_palette(
+          variant,
+          source_color_hct,
+          is_dark,
+          platform,
+          contrast_level,
+        ))
       },
       &variant,
       &source_color_hct,
@@ -726,7 +855,14 @@ impl DynamicSchemeBuilder {
     );
     let tertiary_palette = self.tertiary_palette.tonal_palette(
       |variant, source_color_hct, is_dark, platform, contrast_level| {
-        spec.tertiary_palette(variant, source_color_hct, is_dark, platform, contrast_level)
+        in_viewing_conditions(spec.tertiary_palette_with_dislike_analyzer(
+          variant,
+          source_color_hct,
+          is_dark,
+          platform,
+          contrast_level,
+          &dislike_analyzer,
+        ))
       },
       &variant,
       &source_color_hct,
@@ -736,7 +872,13 @@ impl DynamicSchemeBuilder {
     );
     let neutral_palette = self.neutral_palette.tonal_palette(
       |variant, source_color_hct, is_dark, platform, contrast_level| {
-        spec.neutral_palette(variant, source_color_hct, is_dark, platform, contrast_level)
+        in_viewing_conditions(spec.neutral_palette(
+          variant,
+          source_color_hct,
+          is_dark,
+          platform,
+          contrast_level,
+        ))
       },
       &variant,
       &source_color_hct,
@@ -746,7 +888,13 @@ impl DynamicSchemeBuilder {
     );
     let neutral_variant_palette = self.neutral_variant_palette.tonal_palette(
       |variant, source_color_hct, is_dark, platform, contrast_level| {
-        spec.neutral_variant_palette(variant, source_color_hct, is_dark, platform, contrast_level)
+        in_viewing_conditions(spec.neutral_variant_palette(
+          variant,
+          source_color_hct,
+          is_dark,
+          platform,
+          contrast_level,
+        ))
       },
       &variant,
       &source_color_hct,
@@ -758,7 +906,9 @@ impl DynamicSchemeBuilder {
       .error_palette
       .tonal_palette_or_none(
         |variant, source_color_hct, is_dark, platform, contrast_level| {
-          spec.error_palette(variant, source_color_hct, is_dark, platform, contrast_level)
+          spec
+            .error_palette(variant, source_color_hct, is_dark, platform, contrast_level)
+            .map(in_viewing_conditions)
         },
         &variant,
         &source_color_hct,
@@ -766,9 +916,9 @@ impl DynamicSchemeBuilder {
         &platform,
         contrast_level,
       )
-      .unwrap_or_else(|| TonalPalette::from_hue_and_chroma(25.0, 84.0));
+      .unwrap_or_else(|| in_viewing_conditions(TonalPalette::from_hue_and_chroma(25.0, 84.0)));
 
-    DynamicScheme::new(
+    let scheme = DynamicScheme::new(
       source_color_hct,
       variant,
       is_dark,
@@ -781,7 +931,12 @@ impl DynamicSchemeBuilder {
       neutral_palette,
       neutral_variant_palette,
       error_palette,
-    )
+    );
+    DynamicScheme {
+      contrast_algorithm: self.contrast_algorithm.unwrap_or_default(),
+      dislike_analyzer,
+      ..scheme
+    }
   }
 }
 
@@ -794,12 +949,14 @@ impl From<&DynamicScheme> for DynamicSchemeBuilder {
       .platform(*value.platform())
       .contrast_level(value.contrast_level())
       .spec_version(*value.spec_version())
+      .contrast_algorithm(value.contrast_algorithm())
       .primary_palette(value.primary_palette().clone())
       .secondary_palette(value.secondary_palette().clone())
       .tertiary_palette(value.tertiary_palette().clone())
       .neutral_palette(value.neutral_palette().clone())
       .neutral_variant_palette(value.neutral_variant_palette().clone())
       .error_palette(value.error_palette().clone())
+      .dislike_analyzer(value.dislike_analyzer().clone())
   }
 }
 
@@ -812,11 +969,13 @@ impl From<DynamicScheme> for DynamicSchemeBuilder {
       .platform(value.platform)
       .contrast_level(*value.contrast_level)
       .spec_version(value.spec_version)
+      .contrast_algorithm(value.contrast_algorithm)
       .primary_palette(value.primary_palette)
       .secondary_palette(value.secondary_palette)
       .tertiary_palette(value.tertiary_palette)
       .neutral_palette(value.neutral_palette)
       .neutral_variant_palette(value.neutral_variant_palette)
       .error_palette(value.error_palette)
+      .dislike_analyzer(value.dislike_analyzer)
   }
 }
@@ -4,6 +4,8 @@ mod color_calculation_spec_2025;
 mod color_spec;
 mod color_spec_2021;
 mod color_spec_2025;
+mod color_vision_audit;
+mod contrast_audit;
 mod contrast_curve;
 mod dynamic_color;
 mod dynamic_scheme;
@@ -28,6 +30,8 @@ pub use color_calculation_spec::*;
 pub(crate) use color_calculation_spec_2021::*;
 pub(crate) use color_calculation_spec_2025::*;
 
+pub use color_vision_audit::*;
+pub use contrast_audit::*;
 pub use contrast_curve::*;
 pub use dynamic_color::*;
 pub use dynamic_scheme::*;
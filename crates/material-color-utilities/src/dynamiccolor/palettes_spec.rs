@@ -1,4 +1,5 @@
 use crate::{
+  dislike_analyzer::DislikeAnalyzer,
   dynamiccolor::{Platform, Variant},
   hct::Hct,
   palettes::TonalPalette,
@@ -32,6 +33,23 @@ pub trait PalettesSpec {
     contrast_level: f64,
   ) -> TonalPalette;
 
+  /// Like [`PalettesSpec::tertiary_palette`], but fixing disliked colors with the given analyzer
+  /// rather than [`DislikeAnalyzer::default`].
+  ///
+  /// Specs that don't fix disliked colors can keep the default implementation, which ignores the
+  /// analyzer.
+  fn tertiary_palette_with_dislike_analyzer(
+    &self,
+    variant: &Variant,
+    source_color_hct: &Hct,
+    is_dark: bool,
+    platform: &Platform,
+    contrast_level: f64,
+    _dislike_analyzer: &DislikeAnalyzer,
+  ) -> TonalPalette {
+    self.tertiary_palette(variant, source_color_hct, is_dark, platform, contrast_level)
+  }
+
   fn neutral_palette(
     &self,
     variant: &Variant,
@@ -1,5 +1,5 @@
 use crate::{
-  dislike_analyzer,
+  dislike_analyzer::DislikeAnalyzer,
   dynamiccolor::{DynamicScheme, PalettesSpec, Platform, Variant},
   hct::Hct,
   palettes::TonalPalette,
@@ -88,21 +88,40 @@ impl PalettesSpec for PalettesSpec2021 {
   }
 
   fn tertiary_palette(
+    &self,
+    variant: &Variant,
+    source_color_hct: &Hct,
+    is_dark: bool,
+    platform: &Platform,
+    contrast_level: f64,
+  ) -> TonalPalette {
+    self.tertiary_palette_with_dislike_analyzer(
+      variant,
+      source_color_hct,
+      is_dark,
+      platform,
+      contrast_level,
+      &DislikeAnalyzer::default(),
+    )
+  }
+
+  fn tertiary_palette_with_dislike_analyzer(
     &self,
     variant: &Variant,
     source_color_hct: &Hct,
     _is_dark: bool,
     _platform: &Platform,
     _contrast_level: f64,
+    dislike_analyzer: &DislikeAnalyzer,
   ) -> TonalPalette {
     let mut temperature_cache = TemperatureCache::new(source_color_hct.clone());
     match *variant {
-      Variant::Content => TonalPalette::from_hct(dislike_analyzer::fix_if_disliked(
-        temperature_cache.analogous_with(3, 6)[2].clone(),
-      )),
-      Variant::Fidelity => TonalPalette::from_hct(dislike_analyzer::fix_if_disliked(
-        temperature_cache.complement().clone(),
-      )),
+      Variant::Content => TonalPalette::from_hct(
+        dislike_analyzer.fix_if_disliked(temperature_cache.analogous_with(3, 6)[2].clone()),
+      ),
+      Variant::Fidelity => TonalPalette::from_hct(
+        dislike_analyzer.fix_if_disliked(temperature_cache.complement().clone()),
+      ),
       Variant::FruitSalad => TonalPalette::from_hue_and_chroma(source_color_hct.hue(), 36.0),
       Variant::Monochrome => TonalPalette::from_hue_and_chroma(source_color_hct.hue(), 0.0),
       Variant::Neutral => TonalPalette::from_hue_and_chroma(source_color_hct.hue(), 16.0),
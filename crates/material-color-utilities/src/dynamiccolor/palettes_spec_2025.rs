@@ -1,4 +1,5 @@
 use crate::{
+  dislike_analyzer::DislikeAnalyzer,
   dynamiccolor::{DynamicScheme, PalettesSpec, PalettesSpec2021, Platform, Variant},
   hct::Hct,
   palettes::TonalPalette,
@@ -173,6 +174,25 @@ impl PalettesSpec for PalettesSpec2025 {
     is_dark: bool,
     platform: &Platform,
     contrast_level: f64,
+  ) -> TonalPalette {
+    self.tertiary_palette_with_dislike_analyzer(
+      variant,
+      source_color_hct,
+      is_dark,
+      platform,
+      contrast_level,
+      &DislikeAnalyzer::default(),
+    )
+  }
+
+  fn tertiary_palette_with_dislike_analyzer(
+    &self,
+    variant: &Variant,
+    source_color_hct: &Hct,
+    is_dark: bool,
+    platform: &Platform,
+    contrast_level: f64,
+    dislike_analyzer: &DislikeAnalyzer,
   ) -> TonalPalette {
     match *variant {
       Variant::Neutral => TonalPalette::from_hue_and_chroma(
@@ -213,12 +233,13 @@ impl PalettesSpec for PalettesSpec2025 {
         ),
         56.0,
       ),
-      _ => self.spec_2021.tertiary_palette(
+      _ => self.spec_2021.tertiary_palette_with_dislike_analyzer(
         variant,
         source_color_hct,
         is_dark,
         platform,
         contrast_level,
+        dislike_analyzer,
       ),
     }
   }
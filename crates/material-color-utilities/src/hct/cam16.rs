@@ -1,5 +1,4 @@
-use std::f64;
-
+use num_traits::{Float, FloatConst, FromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -34,40 +33,42 @@ pub(crate) const CAM16RGB_TO_XYZ: [[f64; 3]; 3] = [
 /// measured as a slightly chromatic blue by CAM16. (roughly, hue 203, chroma 3, lightness 100)
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-pub struct Cam16 {
+pub struct Cam16<T = f64> {
   // CAM16 color dimensions, see getters for documentation.
-  hue: f64,
-  chroma: f64,
-  j: f64,
-  q: f64,
-  m: f64,
-  s: f64,
+  hue: T,
+  chroma: T,
+  j: T,
+  q: T,
+  m: T,
+  s: T,
 
   // Coordinates in UCS space. Used to determine color distance, like delta E equations in L*a*b*.
-  jstar: f64,
-  astar: f64,
-  bstar: f64,
+  jstar: T,
+  astar: T,
+  bstar: T,
   // Avoid allocations during conversion by pre-allocating an array.
   // temp_array: [f64; 3],
 }
 
 impl Cam16 {
+  /// Create a CAM16 color from a color, assuming the color was viewed in default viewing conditions.
+  ///
+  /// For other float types, use [`Cam16::from_int_in_viewing_conditions`] or [`From<u32>`].
+  pub fn from_int(argb: u32) -> Self {
+    Self::from_int_in_viewing_conditions(argb, &ViewingConditions::default())
+  }
+}
+
+impl<T> Cam16<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
   /// All of the CAM16 dimensions can be calculated from 3 of the dimensions, in the following
   /// combinations: - {j or q} and {c, m, or s} and hue - jstar, astar, bstar Prefer using a static
   /// method that constructs from 3 of those dimensions. This constructor is intended for those
   /// methods to use to return all possible dimensions.
   #[allow(clippy::too_many_arguments)]
-  fn new(
-    hue: f64,
-    chroma: f64,
-    j: f64,
-    q: f64,
-    m: f64,
-    s: f64,
-    jstar: f64,
-    astar: f64,
-    bstar: f64,
-  ) -> Self {
+  fn new(hue: T, chroma: T, j: T, q: T, m: T, s: T, jstar: T, astar: T, bstar: T) -> Self {
     Self {
       hue,
       chroma,
@@ -82,35 +83,39 @@ impl Cam16 {
     }
   }
 
-  /// Create a CAM16 color from a color, assuming the color was viewed in default viewing conditions.
-  pub fn from_int(argb: u32) -> Self {
-    Self::from_int_in_viewing_conditions(argb, &ViewingConditions::default())
-  }
-
   /// Create a CAM16 color from a color in defined viewing conditions.
-  pub fn from_int_in_viewing_conditions(argb: u32, viewing_conditions: &ViewingConditions) -> Self {
+  pub fn from_int_in_viewing_conditions(
+    argb: u32,
+    viewing_conditions: &ViewingConditions<T>,
+  ) -> Self {
     // Transform ARGB int to XYZ
     let red = ((argb & 0x00ff0000) >> 16) as u8;
     let green = ((argb & 0x0000ff00) >> 8) as u8;
     let blue = (argb & 0x000000ff) as u8;
-    let red_l: f64 = utils::color::linearized(red);
-    let green_l: f64 = utils::color::linearized(green);
-    let blue_l: f64 = utils::color::linearized(blue);
-    let x = 0.41233895 * red_l + 0.35762064 * green_l + 0.18051042 * blue_l;
-    let y = 0.2126 * red_l + 0.7152 * green_l + 0.0722 * blue_l;
-    let z = 0.01932141 * red_l + 0.11916382 * green_l + 0.95034478 * blue_l;
+    let red_l: T = utils::color::linearized(red);
+    let green_l: T = utils::color::linearized(green);
+    let blue_l: T = utils::color::linearized(blue);
+    let x = T::from_f64(0.41233895).unwrap() * red_l
+      + T::from_f64(0.35762064).unwrap() * green_l
+      + T::from_f64(0.18051042).unwrap() * blue_l;
+    let y = T::from_f64(0.2126).unwrap() * red_l
+      + T::from_f64(0.7152).unwrap() * green_l
+      + T::from_f64(0.0722).unwrap() * blue_l;
+    let z = T::from_f64(0.01932141).unwrap() * red_l
+      + T::from_f64(0.11916382).unwrap() * green_l
+      + T::from_f64(0.95034478).unwrap() * blue_l;
 
     Self::from_xyz_in_viewing_conditions(x, y, z, viewing_conditions)
   }
 
   pub fn from_xyz_in_viewing_conditions(
-    x: f64,
-    y: f64,
-    z: f64,
-    viewing_conditions: &ViewingConditions,
+    x: T,
+    y: T,
+    z: T,
+    viewing_conditions: &ViewingConditions<T>,
   ) -> Self {
     // Transform XYZ to 'cone'/'rgb' responses
-    let matrix = XYZ_TO_CAM16RGB;
+    let matrix = utils::color::matrix_from_f64::<T>(XYZ_TO_CAM16RGB);
     let r_t = (x * matrix[0][0]) + (y * matrix[0][1]) + (z * matrix[0][2]);
     let g_t = (x * matrix[1][0]) + (y * matrix[1][1]) + (z * matrix[1][2]);
     let b_t = (x * matrix[2][0]) + (y * matrix[2][1]) + (z * matrix[2][2]);
@@ -121,29 +126,40 @@ impl Cam16 {
     let b_d = viewing_conditions.rgb_d()[2] * b_t;
 
     // Chromatic adaptation
-    let r_a_f = (viewing_conditions.fl() * r_d.abs() / 100.0).powf(0.42);
-    let g_a_f = (viewing_conditions.fl() * g_d.abs() / 100.0).powf(0.42);
-    let b_a_f = (viewing_conditions.fl() * b_d.abs() / 100.0).powf(0.42);
-    let r_a = r_d.signum() * 400.0 * r_a_f / (r_a_f + 27.13);
-    let g_a = g_d.signum() * 400.0 * g_a_f / (g_a_f + 27.13);
-    let b_a = b_d.signum() * 400.0 * b_a_f / (b_a_f + 27.13);
+    let r_a_f = (viewing_conditions.fl() * r_d.abs() / T::from_f64(100.0).unwrap())
+      .powf(T::from_f64(0.42).unwrap());
+    let g_a_f = (viewing_conditions.fl() * g_d.abs() / T::from_f64(100.0).unwrap())
+      .powf(T::from_f64(0.42).unwrap());
+    let b_a_f = (viewing_conditions.fl() * b_d.abs() / T::from_f64(100.0).unwrap())
+      .powf(T::from_f64(0.42).unwrap());
+    let r_a =
+      r_d.signum() * T::from_f64(400.0).unwrap() * r_a_f / (r_a_f + T::from_f64(27.13).unwrap());
+    let g_a =
+      g_d.signum() * T::from_f64(400.0).unwrap() * g_a_f / (g_a_f + T::from_f64(27.13).unwrap());
+    let b_a =
+      b_d.signum() * T::from_f64(400.0).unwrap() * b_a_f / (b_a_f + T::from_f64(27.13).unwrap());
 
     // redness-greenness
-    let a = (11.0 * r_a + -12.0 * g_a + b_a) / 11.0;
+    let a = (T::from_f64(11.0).unwrap() * r_a + T::from_f64(-12.0).unwrap() * g_a + b_a)
+      / T::from_f64(11.0).unwrap();
     // yellowness-blueness
-    let b = (r_a + g_a - 2.0 * b_a) / 9.0;
+    let b = (r_a + g_a - T::from_f64(2.0).unwrap() * b_a) / T::from_f64(9.0).unwrap();
 
     // auxiliary components
-    let u = (20.0 * r_a + 20.0 * g_a + 21.0 * b_a) / 20.0;
-    let p2 = (40.0 * r_a + 20.0 * g_a + b_a) / 20.0;
+    let u = (T::from_f64(20.0).unwrap() * r_a
+      + T::from_f64(20.0).unwrap() * g_a
+      + T::from_f64(21.0).unwrap() * b_a)
+      / T::from_f64(20.0).unwrap();
+    let p2 = (T::from_f64(40.0).unwrap() * r_a + T::from_f64(20.0).unwrap() * g_a + b_a)
+      / T::from_f64(20.0).unwrap();
 
     // hue
-    let atan2 = f64::atan2(b, a);
+    let atan2 = T::atan2(b, a);
     let atan_degrees = atan2.to_degrees();
-    let hue = if atan_degrees < 0.0 {
-      atan_degrees + 360.0
-    } else if atan_degrees >= 360.0 {
-      atan_degrees - 360.0
+    let hue = if atan_degrees < T::zero() {
+      atan_degrees + T::from_f64(360.0).unwrap()
+    } else if atan_degrees >= T::from_f64(360.0).unwrap() {
+      atan_degrees - T::from_f64(360.0).unwrap()
     } else {
       atan_degrees
     };
@@ -153,96 +169,116 @@ impl Cam16 {
     let ac = p2 * viewing_conditions.nbb();
 
     // CAM16 lightness and brightness
-    let j = 100.0
-      * f64::powf(
+    let j = T::from_f64(100.0).unwrap()
+      * T::powf(
         ac / viewing_conditions.aw(),
         viewing_conditions.c() * viewing_conditions.z(),
       );
-    let q = 4.0 / viewing_conditions.c()
-      * (j / 100.0).sqrt()
-      * (viewing_conditions.aw() + 4.0)
+    let q = T::from_f64(4.0).unwrap() / viewing_conditions.c()
+      * (j / T::from_f64(100.0).unwrap()).sqrt()
+      * (viewing_conditions.aw() + T::from_f64(4.0).unwrap())
       * viewing_conditions.fl_root();
 
     // CAM16 chroma, colorfulness, and saturation.
-    let hue_prime = if hue < 20.14 { hue + 360.0 } else { hue };
-    let e_hue = 0.25 * ((hue_prime.to_radians() + 2.0).cos() + 3.8);
-    let p1 = 50000.0 / 13.0 * e_hue * viewing_conditions.nc() * viewing_conditions.ncb();
-    let t = p1 * f64::hypot(a, b) / (u + 0.305);
-    let alpha = (1.64 - 0.29_f64.powf(viewing_conditions.n())).powf(0.73) * t.powf(0.9);
+    let hue_prime = if hue < T::from_f64(20.14).unwrap() {
+      hue + T::from_f64(360.0).unwrap()
+    } else {
+      hue
+    };
+    let e_hue = T::from_f64(0.25).unwrap()
+      * ((hue_prime.to_radians() + T::from_f64(2.0).unwrap()).cos() + T::from_f64(3.8).unwrap());
+    let p1 = T::from_f64(50000.0 / 13.0).unwrap()
+      * e_hue
+      * viewing_conditions.nc()
+      * viewing_conditions.ncb();
+    let t = p1 * T::hypot(a, b) / (u + T::from_f64(0.305).unwrap());
+    let alpha = (T::from_f64(1.64).unwrap()
+      - T::from_f64(0.29).unwrap().powf(viewing_conditions.n()))
+    .powf(T::from_f64(0.73).unwrap())
+      * t.powf(T::from_f64(0.9).unwrap());
 
     // CAM16 chroma, colorfulness, saturation
-    let c = alpha * (j / 100.0).sqrt();
+    let c = alpha * (j / T::from_f64(100.0).unwrap()).sqrt();
     let m = c * viewing_conditions.fl_root();
-    let s = 50.0 * ((alpha * viewing_conditions.c()) / (viewing_conditions.aw() + 4.0)).sqrt();
+    let s = T::from_f64(50.0).unwrap()
+      * ((alpha * viewing_conditions.c()) / (viewing_conditions.aw() + T::from_f64(4.0).unwrap()))
+        .sqrt();
     // CAM16-UCS components
-    let jstar = (1.0 + 100.0 * 0.007) * j / (1.0 + 0.007 * j);
-    let mstar = 1.0 / 0.0228 * (0.0228 * m).ln_1p();
+    let jstar = (T::one() + T::from_f64(100.0).unwrap() * T::from_f64(0.007).unwrap()) * j
+      / (T::one() + T::from_f64(0.007).unwrap() * j);
+    let mstar =
+      T::one() / T::from_f64(0.0228).unwrap() * (T::from_f64(0.0228).unwrap() * m).ln_1p();
     let astar = mstar * hue_radians.cos();
     let bstar = mstar * hue_radians.sin();
 
     Self::new(hue, c, j, q, m, s, jstar, astar, bstar)
   }
 
-  pub fn from_jch(j: f64, c: f64, h: f64) -> Self {
+  pub fn from_jch(j: T, c: T, h: T) -> Self {
     Self::from_jch_in_viewing_conditions(j, c, h, &ViewingConditions::default())
   }
   pub fn from_jch_in_viewing_conditions(
-    j: f64,
-    c: f64,
-    h: f64,
-    viewing_conditions: &ViewingConditions,
+    j: T,
+    c: T,
+    h: T,
+    viewing_conditions: &ViewingConditions<T>,
   ) -> Self {
-    let q = 4.0 / viewing_conditions.c()
-      * (j / 100.0).sqrt()
-      * (viewing_conditions.aw() + 4.0)
+    let q = T::from_f64(4.0).unwrap() / viewing_conditions.c()
+      * (j / T::from_f64(100.0).unwrap()).sqrt()
+      * (viewing_conditions.aw() + T::from_f64(4.0).unwrap())
       * viewing_conditions.fl_root();
     let m = c * viewing_conditions.fl_root();
-    let alpha = c / (j / 100.0).sqrt();
-    let s = 50.0 * ((alpha * viewing_conditions.c()) / (viewing_conditions.aw() + 4.0)).sqrt();
+    let alpha = c / (j / T::from_f64(100.0).unwrap()).sqrt();
+    let s = T::from_f64(50.0).unwrap()
+      * ((alpha * viewing_conditions.c()) / (viewing_conditions.aw() + T::from_f64(4.0).unwrap()))
+        .sqrt();
 
     let hue_radians = h.to_radians();
-    let jstar = (1.0 + 100.0 * 0.007) * j / (1.0 + 0.007 * j);
-    let mstar = 1.0 / 0.0228 * (0.0228 * m).ln_1p();
+    let jstar = (T::one() + T::from_f64(100.0).unwrap() * T::from_f64(0.007).unwrap()) * j
+      / (T::one() + T::from_f64(0.007).unwrap() * j);
+    let mstar =
+      T::one() / T::from_f64(0.0228).unwrap() * (T::from_f64(0.0228).unwrap() * m).ln_1p();
     let astar = mstar * hue_radians.cos();
     let bstar = mstar * hue_radians.sin();
     Self::new(h, c, j, q, m, s, jstar, astar, bstar)
   }
 
   /// Create a CAM16 color from CAM16-UCS coordinates.
-  pub fn from_ucs(jstar: f64, astar: f64, bstar: f64) -> Self {
+  pub fn from_ucs(jstar: T, astar: T, bstar: T) -> Self {
     Self::from_ucs_in_viewing_conditions(jstar, astar, bstar, &ViewingConditions::default())
   }
 
   /// Create a CAM16 color from CAM16-UCS coordinates in defined viewing conditions.
   pub fn from_ucs_in_viewing_conditions(
-    jstar: f64,
-    astar: f64,
-    bstar: f64,
-    viewing_conditions: &ViewingConditions,
+    jstar: T,
+    astar: T,
+    bstar: T,
+    viewing_conditions: &ViewingConditions<T>,
   ) -> Self {
-    let m = f64::hypot(astar, bstar);
-    let m2 = (m * 0.0228).exp_m1() / 0.0228;
+    let m = T::hypot(astar, bstar);
+    let m2 = (m * T::from_f64(0.0228).unwrap()).exp_m1() / T::from_f64(0.0228).unwrap();
     let c = m2 / viewing_conditions.fl_root();
-    let mut h = f64::atan2(bstar, astar) * (180.0 / f64::consts::PI);
-    if h < 0.0 {
-      h += 360.0;
+    let mut h = T::atan2(bstar, astar) * (T::from_f64(180.0).unwrap() / T::PI());
+    if h < T::zero() {
+      h = h + T::from_f64(360.0).unwrap();
     }
-    let j = jstar / (1.0 - (jstar - 100.0) * 0.007);
+    let j =
+      jstar / (T::one() - (jstar - T::from_f64(100.0).unwrap()) * T::from_f64(0.007).unwrap());
     Self::from_jch_in_viewing_conditions(j, c, h, viewing_conditions)
   }
 
   /// Hue in CAM16
-  pub fn hue(&self) -> f64 {
+  pub fn hue(&self) -> T {
     self.hue
   }
 
   /// Chroma in CAM16
-  pub fn chroma(&self) -> f64 {
+  pub fn chroma(&self) -> T {
     self.chroma
   }
 
   /// Lightness in CAM16
-  pub fn j(&self) -> f64 {
+  pub fn j(&self) -> T {
     self.j
   }
 
@@ -251,7 +287,7 @@ impl Cam16 {
   /// Prefer lightness, brightness is an absolute quantity. For example, a sheet of white paper is
   /// much brighter viewed in sunlight than in indoor light, but it is the lightest object under any
   /// lighting.
-  pub fn q(&self) -> f64 {
+  pub fn q(&self) -> T {
     self.q
   }
 
@@ -259,7 +295,7 @@ impl Cam16 {
   ///
   /// Prefer chroma, colorfulness is an absolute quantity. For example, a yellow toy car is much
   /// more colorful outside than inside, but it has the same chroma in both environments.
-  pub fn m(&self) -> f64 {
+  pub fn m(&self) -> T {
     self.m
   }
 
@@ -267,34 +303,34 @@ impl Cam16 {
   ///
   /// Colorfulness in proportion to brightness. Prefer chroma, saturation measures colorfulness
   /// relative to the color's own brightness, where chroma is colorfulness relative to white.
-  pub fn s(&self) -> f64 {
+  pub fn s(&self) -> T {
     self.s
   }
 
   /// Lightness coordinate in CAM16-UCS
-  pub fn jstar(&self) -> f64 {
+  pub fn jstar(&self) -> T {
     self.jstar
   }
 
   /// a* coordinate in CAM16-UCS
-  pub fn astar(&self) -> f64 {
+  pub fn astar(&self) -> T {
     self.astar
   }
 
   /// b* coordinate in CAM16-UCS
-  pub fn bstar(&self) -> f64 {
+  pub fn bstar(&self) -> T {
     self.bstar
   }
 
   /// CAM16 instances also have coordinates in the CAM16-UCS space, called J*, a*, b*, or jstar,
   /// astar, bstar in code. CAM16-UCS is included in the CAM16 specification, and is used to measure
   /// distances between colors.
-  pub fn distance(&self, other: &Cam16) -> f64 {
+  pub fn distance(&self, other: &Cam16<T>) -> T {
     let d_j = self.jstar() - other.jstar();
     let d_a = self.astar() - other.astar();
     let d_b = self.bstar() - other.bstar();
     let d_e_prime = (d_j * d_j + d_a * d_a + d_b * d_b).sqrt();
-    let d_e = 1.41 * d_e_prime.powf(0.63);
+    let d_e = T::from_f64(1.41).unwrap() * d_e_prime.powf(T::from_f64(0.63).unwrap());
     #[allow(clippy::let_and_return)]
     d_e
   }
@@ -306,54 +342,89 @@ impl Cam16 {
   }
 
   /// ARGB representation of the color, in defined viewing conditions.
-  pub fn viewed(&self, viewing_conditions: &ViewingConditions) -> u32 {
+  pub fn viewed(&self, viewing_conditions: &ViewingConditions<T>) -> u32 {
     let xyz = self.xyz_in_viewing_conditions(viewing_conditions);
     utils::color::argb_from_xyz(xyz[0], xyz[1], xyz[2])
   }
 
-  pub fn xyz_in_viewing_conditions(&self, viewing_conditions: &ViewingConditions) -> [f64; 3] {
-    let alpha = if self.chroma() == 0.0 || self.j() == 0.0 {
-      0.0
+  pub fn xyz_in_viewing_conditions(&self, viewing_conditions: &ViewingConditions<T>) -> [T; 3] {
+    let alpha = if self.chroma() == T::zero() || self.j() == T::zero() {
+      T::zero()
     } else {
-      self.chroma() / (self.j() / 100.0).sqrt()
+      self.chroma() / (self.j() / T::from_f64(100.0).unwrap()).sqrt()
     };
 
-    let t = f64::powf(
-      alpha / f64::powf(1.64 - f64::powf(0.29, viewing_conditions.n()), 0.73),
-      1.0 / 0.9,
+    let t = T::powf(
+      alpha
+        / T::powf(
+          T::from_f64(1.64).unwrap() - T::powf(T::from_f64(0.29).unwrap(), viewing_conditions.n()),
+          T::from_f64(0.73).unwrap(),
+        ),
+      T::from_f64(1.0 / 0.9).unwrap(),
     );
     let h_rad = self.hue.to_radians();
 
-    let e_hue = 0.25 * ((h_rad + 2.0).cos() + 3.8);
+    let e_hue = T::from_f64(0.25).unwrap()
+      * ((h_rad + T::from_f64(2.0).unwrap()).cos() + T::from_f64(3.8).unwrap());
     let ac = viewing_conditions.aw()
-      * f64::powf(
-        self.j() / 100.0,
-        1.0 / viewing_conditions.c() / viewing_conditions.z(),
+      * T::powf(
+        self.j() / T::from_f64(100.0).unwrap(),
+        T::one() / viewing_conditions.c() / viewing_conditions.z(),
       );
-    let p1 = e_hue * (50000.0 / 13.0) * viewing_conditions.nc() * viewing_conditions.ncb();
+    let p1 = e_hue
+      * T::from_f64(50000.0 / 13.0).unwrap()
+      * viewing_conditions.nc()
+      * viewing_conditions.ncb();
     let p2 = ac / viewing_conditions.nbb();
 
     let h_sin = h_rad.sin();
     let h_cos = h_rad.cos();
 
-    let gamma = 23.0 * (p2 + 0.305) * t / (23.0 * p1 + 11.0 * t * h_cos + 108.0 * t * h_sin);
+    let gamma = T::from_f64(23.0).unwrap() * (p2 + T::from_f64(0.305).unwrap()) * t
+      / (T::from_f64(23.0).unwrap() * p1
+        + T::from_f64(11.0).unwrap() * t * h_cos
+        + T::from_f64(108.0).unwrap() * t * h_sin);
     let a = gamma * h_cos;
     let b = gamma * h_sin;
-    let r_a = (460.0 * p2 + 451.0 * a + 288.0 * b) / 1403.0;
-    let g_a = (460.0 * p2 - 891.0 * a - 261.0 * b) / 1403.0;
-    let b_a = (460.0 * p2 - 220.0 * a - 6300.0 * b) / 1403.0;
-
-    let r_c_base = f64::max(0.0, (27.13 * r_a.abs()) / (400.0 - r_a.abs()));
-    let r_c = r_a.signum() * (100.0 / viewing_conditions.fl()) * f64::powf(r_c_base, 1.0 / 0.42);
-    let g_c_base = f64::max(0.0, (27.13 * g_a.abs()) / (400.0 - g_a.abs()));
-    let g_c = g_a.signum() * (100.0 / viewing_conditions.fl()) * f64::powf(g_c_base, 1.0 / 0.42);
-    let b_c_base = f64::max(0.0, (27.13 * b_a.abs()) / (400.0 - b_a.abs()));
-    let b_c = b_a.signum() * (100.0 / viewing_conditions.fl()) * f64::powf(b_c_base, 1.0 / 0.42);
+    let r_a = (T::from_f64(460.0).unwrap() * p2
+      + T::from_f64(451.0).unwrap() * a
+      + T::from_f64(288.0).unwrap() * b)
+      / T::from_f64(1403.0).unwrap();
+    let g_a = (T::from_f64(460.0).unwrap() * p2
+      - T::from_f64(891.0).unwrap() * a
+      - T::from_f64(261.0).unwrap() * b)
+      / T::from_f64(1403.0).unwrap();
+    let b_a = (T::from_f64(460.0).unwrap() * p2
+      - T::from_f64(220.0).unwrap() * a
+      - T::from_f64(6300.0).unwrap() * b)
+      / T::from_f64(1403.0).unwrap();
+
+    let r_c_base = T::max(
+      T::zero(),
+      (T::from_f64(27.13).unwrap() * r_a.abs()) / (T::from_f64(400.0).unwrap() - r_a.abs()),
+    );
+    let r_c = r_a.signum()
+      * (T::from_f64(100.0).unwrap() / viewing_conditions.fl())
+      * T::powf(r_c_base, T::from_f64(1.0 / 0.42).unwrap());
+    let g_c_base = T::max(
+      T::zero(),
+      (T::from_f64(27.13).unwrap() * g_a.abs()) / (T::from_f64(400.0).unwrap() - g_a.abs()),
+    );
+    let g_c = g_a.signum()
+      * (T::from_f64(100.0).unwrap() / viewing_conditions.fl())
+      * T::powf(g_c_base, T::from_f64(1.0 / 0.42).unwrap());
+    let b_c_base = T::max(
+      T::zero(),
+      (T::from_f64(27.13).unwrap() * b_a.abs()) / (T::from_f64(400.0).unwrap() - b_a.abs()),
+    );
+    let b_c = b_a.signum()
+      * (T::from_f64(100.0).unwrap() / viewing_conditions.fl())
+      * T::powf(b_c_base, T::from_f64(1.0 / 0.42).unwrap());
     let r_f = r_c / viewing_conditions.rgb_d()[0];
     let g_f = g_c / viewing_conditions.rgb_d()[1];
     let b_f = b_c / viewing_conditions.rgb_d()[2];
 
-    let matrix = CAM16RGB_TO_XYZ;
+    let matrix = utils::color::matrix_from_f64::<T>(CAM16RGB_TO_XYZ);
     let x = (r_f * matrix[0][0]) + (g_f * matrix[0][1]) + (b_f * matrix[0][2]);
     let y = (r_f * matrix[1][0]) + (g_f * matrix[1][1]) + (b_f * matrix[1][2]);
     let z = (r_f * matrix[2][0]) + (g_f * matrix[2][1]) + (b_f * matrix[2][2]);
@@ -361,3 +432,13 @@ impl Cam16 {
     [x, y, z]
   }
 }
+
+impl<T> From<u32> for Cam16<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  /// Create a CAM16 color from a color, assuming the color was viewed in default viewing conditions.
+  fn from(argb: u32) -> Self {
+    Self::from_int_in_viewing_conditions(argb, &ViewingConditions::default())
+  }
+}
@@ -0,0 +1,232 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use num_traits::{Float, FromPrimitive};
+
+use crate::utils;
+
+const DISPLAY_P3_TO_XYZ: [[f64; 3]; 3] = [
+  [
+    0.48648542650901727,
+    0.26573028993563647,
+    0.19825429355534605,
+  ],
+  [0.22891185790048807, 0.6917910023848297, 0.07929713971468233],
+  [
+    -1.4907223657212979e-05,
+    0.04508282862560925,
+    1.043762088598048,
+  ],
+];
+
+const XYZ_TO_DISPLAY_P3: [[f64; 3]; 3] = [
+  [2.4939616353671252, -0.9317210488729278, -0.4029230344542756],
+  [-0.8293556873590759, 1.7625552959983912, 0.02362390108934105],
+  [0.0358576717583652, -0.07614270396950855, 0.9570466030181867],
+];
+
+const REC_2020_TO_XYZ: [[f64; 3]; 3] = [
+  [0.6368082319540276, 0.14472721423614274, 0.16893456380982946],
+  [0.26259433721599673, 0.6780837733277009, 0.05932188945630235],
+  [
+    -1.7956995970706863e-05,
+    0.028043617670285122,
+    1.0608043493256858,
+  ],
+];
+
+const XYZ_TO_REC_2020: [[f64; 3]; 3] = [
+  [1.717128833326572, -0.35601045442198986, -0.2535464695367874],
+  [-0.6665183767056583, 1.6163511995246835, 0.01575501087440377],
+  [
+    0.01764926870256041,
+    -0.042736182188063396,
+    0.9422601068718227,
+  ],
+];
+
+/// The `α` constant of the Rec. 2020 transfer function.
+const REC_2020_ALPHA: f64 = 1.09929682680944;
+/// The `β` constant of the Rec. 2020 transfer function.
+const REC_2020_BETA: f64 = 0.018053968510807;
+
+/// An RGB color space, with its own primaries and transfer function, that colors can be solved
+/// into.
+///
+/// Every gamut uses the D65 white point. The primaries of wide gamuts are adapted to the white
+/// point used by the rest of the library, so grays have the same HCT coordinates in every gamut.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Gamut {
+  /// The standard RGB color space of the web and most displays.
+  #[default]
+  Srgb,
+  /// The color space of wide-gamut Apple displays, and of `color(display-p3 ...)` in CSS. Uses
+  /// the sRGB transfer function.
+  DisplayP3,
+  /// The ITU-R BT.2020 color space of UHD video, and of `color(rec2020 ...)` in CSS.
+  Rec2020,
+}
+
+impl Gamut {
+  /// Converts a linear RGB color in this gamut, with components ranging from 0 to 100, to XYZ.
+  pub fn xyz_from_linrgb<T>(self, linrgb: [T; 3]) -> [T; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    utils::math::matrix_multiply(
+      &linrgb,
+      &utils::color::matrix_from_f64(*self.linrgb_to_xyz()),
+    )
+  }
+
+  /// Converts an XYZ color to linear RGB in this gamut, with components ranging from 0 to 100 for
+  /// colors inside the gamut.
+  pub fn linrgb_from_xyz<T>(self, xyz: [T; 3]) -> [T; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    utils::math::matrix_multiply(&xyz, &utils::color::matrix_from_f64(*self.xyz_to_linrgb()))
+  }
+
+  /// Linearizes an RGB component of this gamut.
+  ///
+  /// `rgb_component` ranges from 0 to 1, and the result ranges from 0 to 100.
+  pub fn linearized<T>(self, rgb_component: T) -> T
+  where
+    T: Float + FromPrimitive,
+  {
+    let linearized = match self {
+      Self::Srgb | Self::DisplayP3 => {
+        if rgb_component <= T::from_f64(0.040449936).unwrap() {
+          rgb_component / T::from_f64(12.92).unwrap()
+        } else {
+          ((rgb_component + T::from_f64(0.055).unwrap()) / T::from_f64(1.055).unwrap())
+            .powf(T::from_f64(2.4).unwrap())
+        }
+      }
+      Self::Rec2020 => {
+        let alpha = T::from_f64(REC_2020_ALPHA).unwrap();
+        if rgb_component < T::from_f64(REC_2020_BETA * 4.5).unwrap() {
+          rgb_component / T::from_f64(4.5).unwrap()
+        } else {
+          ((rgb_component + alpha - T::one()) / alpha).powf(T::from_f64(1.0 / 0.45).unwrap())
+        }
+      }
+    };
+    linearized * T::from_f64(100.0).unwrap()
+  }
+
+  /// Delinearizes an RGB component of this gamut, without rounding or clamping it.
+  ///
+  /// `rgb_component` ranges from 0 to 100, and the result ranges from 0 to 1.
+  pub fn delinearized<T>(self, rgb_component: T) -> T
+  where
+    T: Float + FromPrimitive,
+  {
+    let normalized = rgb_component / T::from_f64(100.0).unwrap();
+    match self {
+      Self::Srgb | Self::DisplayP3 => {
+        if normalized <= T::from_f64(0.0031308).unwrap() {
+          normalized * T::from_f64(12.92).unwrap()
+        } else {
+          T::from_f64(1.055).unwrap() * normalized.powf(T::from_f64(1.0 / 2.4).unwrap())
+            - T::from_f64(0.055).unwrap()
+        }
+      }
+      Self::Rec2020 => {
+        let alpha = T::from_f64(REC_2020_ALPHA).unwrap();
+        if normalized < T::from_f64(REC_2020_BETA).unwrap() {
+          normalized * T::from_f64(4.5).unwrap()
+        } else {
+          alpha * normalized.powf(T::from_f64(0.45).unwrap()) - (alpha - T::one())
+        }
+      }
+    }
+  }
+
+  /// Converts a linear RGB color in this gamut, with components ranging from 0 to 100, to an RGB
+  /// color with components ranging from 0 to 1, clamping components outside the gamut.
+  pub fn rgb_from_linrgb<T>(self, linrgb: [T; 3]) -> [T; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    linrgb.map(|component| self.delinearized(component).clamp(T::zero(), T::one()))
+  }
+
+  /// Converts an RGB color in this gamut, with components ranging from 0 to 1, to linear RGB with
+  /// components ranging from 0 to 100.
+  pub fn linrgb_from_rgb<T>(self, rgb: [T; 3]) -> [T; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    rgb.map(|component| self.linearized(component))
+  }
+
+  pub(crate) fn linrgb_to_xyz(self) -> &'static [[f64; 3]; 3] {
+    match self {
+      Self::Srgb => &utils::color::SRGB_TO_XYZ,
+      Self::DisplayP3 => &DISPLAY_P3_TO_XYZ,
+      Self::Rec2020 => &REC_2020_TO_XYZ,
+    }
+  }
+
+  fn xyz_to_linrgb(self) -> &'static [[f64; 3]; 3] {
+    match self {
+      Self::Srgb => &utils::color::XYZ_TO_SRGB,
+      Self::DisplayP3 => &XYZ_TO_DISPLAY_P3,
+      Self::Rec2020 => &XYZ_TO_REC_2020,
+    }
+  }
+
+  /// The coefficients giving the Y of a linear RGB color in this gamut.
+  pub(crate) fn y_from_linrgb<T>(self) -> [T; 3]
+  where
+    T: Float + FromPrimitive,
+  {
+    self.linrgb_to_xyz()[1].map(|value| T::from_f64(value).unwrap())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::test::{approx_eq, assert_approx_eq};
+
+  #[test]
+  fn white_is_shared() {
+    for gamut in [Gamut::Srgb, Gamut::DisplayP3, Gamut::Rec2020] {
+      let white = gamut.xyz_from_linrgb([100.0; 3]);
+      for (component, expected) in white.iter().zip(utils::color::white_point_d65::<f64>()) {
+        assert_approx_eq!(*component, expected, 3.0);
+      }
+    }
+  }
+
+  #[test]
+  fn round_trips_components() {
+    for gamut in [Gamut::Srgb, Gamut::DisplayP3, Gamut::Rec2020] {
+      for i in 0..=20 {
+        let component = i as f64 / 20.0;
+        assert_approx_eq!(
+          gamut.delinearized(gamut.linearized(component)),
+          component,
+          9.0
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn srgb_fits_in_wide_gamuts() {
+    let red = Gamut::Srgb.xyz_from_linrgb([100.0, 0.0, 0.0]);
+    for gamut in [Gamut::DisplayP3, Gamut::Rec2020] {
+      let linrgb = gamut.linrgb_from_xyz(red);
+      assert!(
+        linrgb
+          .iter()
+          .all(|&component| (0.0..=100.0).contains(&component))
+      );
+    }
+  }
+}
@@ -0,0 +1,245 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use num_traits::{Float, FloatConst, FromPrimitive};
+use ordered_float::NotNan;
+
+use crate::{
+  hct::{Gamut, Hct, ViewingConditions, hct_solver},
+  utils,
+};
+
+/// Answers how much chroma is available in a gamut, e.g. to design palettes that stay inside it.
+///
+/// Answers are cached, so asking again for the same hue and tone is free. GamutBoundary is intended
+/// for use in a single thread due to its stateful caching.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GamutBoundary<T = f64> {
+  gamut: Gamut,
+  viewing_conditions: Option<ViewingConditions<T>>,
+  max_chroma_cache: BTreeMap<(NotNan<f64>, NotNan<f64>), T>,
+  /// Maps hue to the tone with the most chroma, and that chroma.
+  cusp_cache: BTreeMap<NotNan<f64>, (T, T)>,
+}
+
+impl GamutBoundary {
+  /// For other float types, use [`From<Gamut>`].
+  pub fn new(gamut: Gamut) -> Self {
+    Self::from(gamut)
+  }
+}
+
+impl<T> GamutBoundary<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  /// The largest range of tones the search for the cusp of a hue ends with.
+  const CUSP_TONE_PRECISION: f64 = 0.01;
+
+  /// Measure chroma in the given viewing conditions instead of the default ones.
+  pub fn with_viewing_conditions(mut self, viewing_conditions: ViewingConditions<T>) -> Self {
+    self.max_chroma_cache.clear();
+    self.cusp_cache.clear();
+    self.viewing_conditions = Some(viewing_conditions);
+    self
+  }
+
+  pub fn gamut(&self) -> Gamut {
+    self.gamut
+  }
+
+  /// The viewing conditions chroma is measured in, or `None` for the default ones.
+  pub fn viewing_conditions(&self) -> Option<&ViewingConditions<T>> {
+    self.viewing_conditions.as_ref()
+  }
+
+  /// The most chroma available for the given hue and tone.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the hue or tone is NaN.
+  pub fn max_chroma(&mut self, hue: T, tone: T) -> T {
+    let (gamut, viewing_conditions) = (self.gamut, &self.viewing_conditions);
+    let hue = utils::math::sanitize_degrees(hue);
+    *self
+      .max_chroma_cache
+      .entry((Self::key(hue), Self::key(tone)))
+      .or_insert_with(|| Self::solve_max_chroma(hue, tone, gamut, viewing_conditions.as_ref()))
+  }
+
+  /// The tone with the most chroma available for the given hue.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the hue is NaN.
+  pub fn peak_tone(&mut self, hue: T) -> T {
+    self.cusp_tone_and_chroma(hue).0
+  }
+
+  /// The most chroma available for the given hue, at any tone.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the hue is NaN.
+  pub fn peak_chroma(&mut self, hue: T) -> T {
+    self.cusp_tone_and_chroma(hue).1
+  }
+
+  /// The color with the most chroma available for the given hue, at [`GamutBoundary::peak_tone`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the hue is NaN.
+  pub fn cusp(&mut self, hue: T) -> Hct<T> {
+    let (tone, chroma) = self.cusp_tone_and_chroma(hue);
+    Hct::from_in(
+      hue,
+      chroma,
+      tone,
+      self.gamut,
+      self.viewing_conditions.as_ref(),
+    )
+  }
+
+  /// The cusps of `count` hues, evenly spaced starting from hue 0.
+  pub fn cusps(&mut self, count: usize) -> Vec<Hct<T>> {
+    (0..count)
+      .map(|i| self.cusp(T::from_f64(i as f64 * 360.0 / count as f64).unwrap()))
+      .collect()
+  }
+
+  fn solve_max_chroma(
+    hue: T,
+    tone: T,
+    gamut: Gamut,
+    viewing_conditions: Option<&ViewingConditions<T>>,
+  ) -> T {
+    match viewing_conditions {
+      Some(vc) => hct_solver::max_chroma_in_viewing_conditions(hue, tone, gamut, vc),
+      None => hct_solver::max_chroma(hue, tone, gamut),
+    }
+  }
+
+  /// Cache keys are `f64`, so `f32` components are kept exactly.
+  fn key(value: T) -> NotNan<f64> {
+    NotNan::new(value.to_f64().unwrap()).unwrap()
+  }
+
+  fn cusp_tone_and_chroma(&mut self, hue: T) -> (T, T) {
+    let hue = utils::math::sanitize_degrees(hue);
+    if let Some(&cusp) = self.cusp_cache.get(&Self::key(hue)) {
+      return cusp;
+    }
+    let cusp = self.search_cusp(hue);
+    self.cusp_cache.insert(Self::key(hue), cusp);
+    cusp
+  }
+
+  /// Golden-section search for the tone with the most chroma, which rises with tone up to the cusp
+  /// and falls after it.
+  ///
+  /// Each iteration keeps one probe and only solves the other. Probes bypass the max chroma cache,
+  /// which would otherwise fill with tones no caller asked for.
+  fn search_cusp(&self, hue: T) -> (T, T) {
+    let max_chroma =
+      |tone| Self::solve_max_chroma(hue, tone, self.gamut, self.viewing_conditions.as_ref());
+    let inverse_phi = (T::from_f64(5.0).unwrap().sqrt() - T::one()) / T::from_f64(2.0).unwrap();
+    let (mut lower_tone, mut upper_tone) = (T::zero(), T::from_f64(100.0).unwrap());
+    let mut lower_probe = upper_tone - (upper_tone - lower_tone) * inverse_phi;
+    let mut upper_probe = lower_tone + (upper_tone - lower_tone) * inverse_phi;
+    let (mut lower_chroma, mut upper_chroma) = (max_chroma(lower_probe), max_chroma(upper_probe));
+    while upper_tone - lower_tone > T::from_f64(Self::CUSP_TONE_PRECISION).unwrap() {
+      if lower_chroma < upper_chroma {
+        lower_tone = lower_probe;
+        (lower_probe, lower_chroma) = (upper_probe, upper_chroma);
+        upper_probe = lower_tone + (upper_tone - lower_tone) * inverse_phi;
+        upper_chroma = max_chroma(upper_probe);
+      } else {
+        upper_tone = upper_probe;
+        (upper_probe, upper_chroma) = (lower_probe, lower_chroma);
+        lower_probe = upper_tone - (upper_tone - lower_tone) * inverse_phi;
+        lower_chroma = max_chroma(lower_probe);
+      }
+    }
+    let tone = (lower_tone + upper_tone) / T::from_f64(2.0).unwrap();
+    (tone, max_chroma(tone))
+  }
+}
+
+impl<T> From<Gamut> for GamutBoundary<T> {
+  fn from(gamut: Gamut) -> Self {
+    Self {
+      gamut,
+      viewing_conditions: None,
+      max_chroma_cache: BTreeMap::new(),
+      cusp_cache: BTreeMap::new(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn max_chroma_matches_solver() {
+    let mut boundary = GamutBoundary::new(Gamut::Srgb);
+    for hue in [0.0, 27.0, 149.0, 282.0] {
+      for tone in [0.0, 10.0, 50.0, 87.0, 100.0] {
+        assert_eq!(
+          boundary.max_chroma(hue, tone),
+          Hct::from(hue, 200.0, tone).chroma()
+        );
+      }
+    }
+    assert_eq!(
+      boundary.max_chroma(149.0, 50.0),
+      Hct::from(149.0, 40.0, 50.0).max_chroma()
+    );
+  }
+
+  #[test]
+  fn finds_cusp() {
+    // For hue 149, the chroma peak is 89.6 at tone 87.9.
+    let mut boundary = GamutBoundary::new(Gamut::Srgb);
+    assert!((boundary.peak_tone(149.0) - 87.9).abs() < 0.5);
+    assert!((boundary.peak_chroma(149.0) - 89.6).abs() < 0.5);
+    let cusp = boundary.cusp(149.0);
+    assert!((cusp.chroma() - 89.6).abs() < 0.5);
+  }
+
+  #[test]
+  fn caches_only_cusps() {
+    let mut boundary = GamutBoundary::new(Gamut::Srgb);
+    let cusps = boundary.cusps(36);
+    assert!(boundary.max_chroma_cache.is_empty());
+    assert_eq!(boundary.cusp_cache.len(), 36);
+    assert_eq!(boundary.cusps(36), cusps);
+  }
+
+  #[test]
+  fn max_chroma_in_viewing_conditions() {
+    let dim_room = ViewingConditions::dim_room();
+    let mut boundary = GamutBoundary::new(Gamut::Srgb).with_viewing_conditions(dim_room.clone());
+    assert_eq!(boundary.viewing_conditions(), Some(&dim_room));
+    for hue in [27.0, 149.0, 282.0] {
+      let max_chroma = boundary.max_chroma(hue, 50.0);
+      let hct = Hct::from_in_viewing_conditions(hue, 200.0, 50.0, &dim_room);
+      assert!((max_chroma - hct.chroma()).abs() < 1.0);
+      assert!(max_chroma < hct_solver::max_chroma(hue, 50.0, Gamut::Srgb));
+    }
+    assert_eq!(boundary.cusp(149.0).viewing_conditions(), Some(&dim_room));
+  }
+
+  #[test]
+  fn wide_gamut_cusps() {
+    let mut srgb = GamutBoundary::new(Gamut::Srgb);
+    let mut display_p3 = GamutBoundary::new(Gamut::DisplayP3);
+    let srgb_cusps = srgb.cusps(12);
+    let display_p3_cusps = display_p3.cusps(12);
+    assert_eq!(display_p3_cusps.len(), 12);
+    for (srgb_cusp, display_p3_cusp) in srgb_cusps.iter().zip(&display_p3_cusps) {
+      assert!(display_p3_cusp.chroma() >= srgb_cusp.chroma());
+      assert_eq!(display_p3_cusp.gamut(), Gamut::DisplayP3);
+    }
+  }
+}
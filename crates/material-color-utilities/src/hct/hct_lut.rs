@@ -0,0 +1,190 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use crate::{hct::hct_solver, utils};
+
+/// A lookup table of sRGB colors solved on a grid of hue, chroma and tone, for hot paths that solve
+/// the same colors over and over.
+///
+/// Requested colors are rounded to the nearest point on the grid, which is solved with
+/// [`hct_solver::solve_to_int`] the first time it is looked up, and read from the table after
+/// that. The result is exactly the color solved for the grid point, so the hue, chroma and tone it
+/// was solved for are each off from the request by at most half of their step, see
+/// [`HctLut::error_bound`]. With the default steps of 1, integer tones are solved exactly.
+///
+/// The table can be shared between threads, e.g. in an [`Arc`](std::sync::Arc), and filled ahead
+/// of time with [`HctLut::precompute`].
+#[derive(Debug)]
+pub struct HctLut {
+  hue_step: f64,
+  chroma_step: f64,
+  tone_step: f64,
+  table: RwLock<HashMap<[u32; 3], u32>>,
+}
+
+impl Default for HctLut {
+  fn default() -> Self {
+    Self::new(1.0, 1.0, 1.0)
+  }
+}
+
+impl PartialEq for HctLut {
+  /// Tables with the same steps give the same colors, regardless of what they have cached.
+  fn eq(&self, other: &Self) -> bool {
+    self.hue_step == other.hue_step
+      && self.chroma_step == other.chroma_step
+      && self.tone_step == other.tone_step
+  }
+}
+
+impl HctLut {
+  /// Create an empty table with the given grid steps.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a step isn't positive.
+  pub fn new(hue_step: f64, chroma_step: f64, tone_step: f64) -> Self {
+    assert!(
+      hue_step > 0.0 && chroma_step > 0.0 && tone_step > 0.0,
+      "expected positive steps"
+    );
+    Self {
+      hue_step,
+      chroma_step,
+      tone_step,
+      table: Default::default(),
+    }
+  }
+
+  pub fn hue_step(&self) -> f64 {
+    self.hue_step
+  }
+
+  pub fn chroma_step(&self) -> f64 {
+    self.chroma_step
+  }
+
+  pub fn tone_step(&self) -> f64 {
+    self.tone_step
+  }
+
+  /// The most the hue, chroma and tone a color is solved for can be off from the request.
+  ///
+  /// The solved color itself can have less chroma than requested, like with
+  /// [`hct_solver::solve_to_int`], when the chroma isn't available in sRGB.
+  pub fn error_bound(&self) -> [f64; 3] {
+    [
+      self.hue_step / 2.0,
+      self.chroma_step / 2.0,
+      self.tone_step / 2.0,
+    ]
+  }
+
+  /// The number of grid points solved so far.
+  pub fn len(&self) -> usize {
+    self.table.read().unwrap().len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Looks up the sRGB color at the grid point nearest to the given hue, chroma, and L*.
+  pub fn solve_to_int(&self, hue_degrees: f64, chroma: f64, lstar: f64) -> u32 {
+    let key = self.key(hue_degrees, chroma, lstar);
+    if let Some(&argb) = self.table.read().unwrap().get(&key) {
+      return argb;
+    }
+    let [hue_degrees, chroma, lstar] = self.grid_point(key);
+    let argb = hct_solver::solve_to_int(hue_degrees, chroma, lstar);
+    self.table.write().unwrap().insert(key, argb);
+    argb
+  }
+
+  /// Solves the grid points nearest to many colors, given as `[hue, chroma, L*]`, ahead of time.
+  ///
+  /// Uses [`hct_solver::solve_to_int_batch`], so points are solved in parallel with the
+  /// `parallel` feature enabled.
+  pub fn precompute(&self, colors: &[[f64; 3]]) {
+    let mut keys: Vec<[u32; 3]> = {
+      let table = self.table.read().unwrap();
+      colors
+        .iter()
+        .map(|&[hue_degrees, chroma, lstar]| self.key(hue_degrees, chroma, lstar))
+        .filter(|key| !table.contains_key(key))
+        .collect()
+    };
+    keys.sort_unstable();
+    keys.dedup();
+    let grid_points: Vec<[f64; 3]> = keys.iter().map(|&key| self.grid_point(key)).collect();
+    let argbs = hct_solver::solve_to_int_batch(&grid_points);
+    self
+      .table
+      .write()
+      .unwrap()
+      .extend(keys.into_iter().zip(argbs));
+  }
+
+  fn key(&self, hue_degrees: f64, chroma: f64, lstar: f64) -> [u32; 3] {
+    let hue_count = (360.0 / self.hue_step).round() as u32;
+    let hue_index = (utils::math::sanitize_degrees(hue_degrees) / self.hue_step).round() as u32;
+    [
+      // Hues wrap around, so the last grid point is the same as the first one.
+      if hue_index >= hue_count { 0 } else { hue_index },
+      // Negative chromas and NaN saturate to 0.
+      (chroma / self.chroma_step).round() as u32,
+      (lstar.clamp(0.0, 100.0) / self.tone_step).round() as u32,
+    ]
+  }
+
+  fn grid_point(&self, [hue_index, chroma_index, tone_index]: [u32; 3]) -> [f64; 3] {
+    [
+      hue_index as f64 * self.hue_step,
+      chroma_index as f64 * self.chroma_step,
+      (tone_index as f64 * self.tone_step).min(100.0),
+    ]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn matches_solver_on_grid() {
+    let lut = HctLut::default();
+    for hue in (0..360).step_by(30) {
+      for tone in (0..=100).step_by(10) {
+        let (hue, tone) = (hue as f64, tone as f64);
+        assert_eq!(
+          lut.solve_to_int(hue, 48.0, tone),
+          hct_solver::solve_to_int(hue, 48.0, tone)
+        );
+      }
+    }
+    assert_eq!(lut.len(), 12 * 11);
+  }
+
+  #[test]
+  fn rounds_to_grid() {
+    let lut = HctLut::new(2.0, 4.0, 5.0);
+    assert_eq!(lut.error_bound(), [1.0, 2.0, 2.5]);
+    assert_eq!(
+      lut.solve_to_int(359.5, 49.0, 41.0),
+      hct_solver::solve_to_int(0.0, 48.0, 40.0)
+    );
+    assert_eq!(lut.len(), 1);
+  }
+
+  #[test]
+  fn precomputes_colors() {
+    let lut = HctLut::default();
+    let colors: Vec<[f64; 3]> = (0..=360).map(|hue| [hue as f64, 36.0, 50.0]).collect();
+    lut.precompute(&colors);
+    assert_eq!(lut.len(), 360);
+    assert_eq!(
+      lut.solve_to_int(120.0, 36.0, 50.0),
+      hct_solver::solve_to_int(120.0, 36.0, 50.0)
+    );
+    assert_eq!(lut.len(), 360);
+  }
+}
@@ -1,9 +1,11 @@
-use std::f64;
+use alloc::vec::Vec;
 
 use num_traits::{Float, FloatConst, FromPrimitive, ToPrimitive};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use crate::{
-  hct::{Cam16, ViewingConditions},
+  hct::{Cam16, Gamut, ViewingConditions, XYZ_TO_CAM16RGB},
   utils,
 };
 
@@ -31,7 +33,53 @@ const LINRGB_FROM_SCALED_DISCOUNT: [[f64; 3]; 3] = [
   [1.9622899599665666, -57.173814538844006, 308.7233197812385],
 ];
 
-const Y_FROM_LINRGB: [f64; 3] = [0.2126, 0.7152, 0.0722];
+const SCALED_DISCOUNT_FROM_DISPLAY_P3: [[f64; 3]; 3] = [
+  [
+    0.0013648911682992702,
+    0.0021981552058593294,
+    0.0003070559753829067,
+  ],
+  [
+    0.0005898868376046161,
+    0.0029456219819679647,
+    0.00035921671330973657,
+  ],
+  [
+    3.693601703374477e-05,
+    0.00027677031876893976,
+    0.0036221222021036,
+  ],
+];
+
+const DISPLAY_P3_FROM_SCALED_DISCOUNT: [[f64; 3]; 3] = [
+  [1081.2072327986384, -805.7414088637491, -11.748736904495406],
+  [-217.20069742078354, 504.54320297802377, -31.624438024101323],
+  [5.571103449411059, -30.336277626008634, 278.6175068177293],
+];
+
+const SCALED_DISCOUNT_FROM_REC_2020: [[f64; 3]; 3] = [
+  [
+    0.0016910742352882525,
+    0.0019736486949967166,
+    0.0002053794192565367,
+  ],
+  [
+    0.0006011767976213603,
+    0.002995405117233372,
+    0.0002981436180275848,
+  ],
+  [
+    4.177393176831957e-05,
+    0.00021632358578535776,
+    0.0036777310203526077,
+  ],
+];
+
+const REC_2020_FROM_SCALED_DISCOUNT: [[f64; 3]; 3] = [
+  [772.220080492641, -508.6734055147669, -1.8871369648353922],
+  [-155.01883154002996, 437.924001984579, -26.844423431438937],
+  [0.3468335621367613, -19.980798451486603, 273.50720036328136],
+];
 
 const CRITICAL_PLANES: [f64; 255] = [
   0.015176349177441876,
@@ -299,20 +347,83 @@ where
   (angle + T::PI() * T::from_f64(8.0).unwrap()) % (T::PI() * T::from_f64(2.0).unwrap())
 }
 
-/// Delinearizes an RGB component, returning a floating-point number.
-fn true_delinearized<T>(rgb_component: T) -> T
+/// Delinearizes an RGB component of the gamut, returning a floating-point number ranging from 0 to
+/// 255.
+fn true_delinearized<T>(rgb_component: T, gamut: Gamut) -> T
 where
   T: Float + FromPrimitive,
 {
-  let normalized = rgb_component / T::from_f64(100.0).unwrap();
-  let delinearized = if normalized <= T::from_f64(0.0031308).unwrap() {
-    normalized * T::from_f64(12.92).unwrap()
-  } else {
-    T::from_f64(1.055).unwrap()
-      * normalized.powf(T::from_f64(1.0).unwrap() / T::from_f64(2.4).unwrap())
-      - T::from_f64(0.055).unwrap()
-  };
-  delinearized * T::from_f64(255.0).unwrap()
+  gamut.delinearized(rgb_component) * T::from_f64(255.0).unwrap()
+}
+
+/// Returns the linear RGB component halfway between two 8-bit RGB components of the gamut.
+fn critical_plane<T>(index: usize, gamut: Gamut) -> T
+where
+  T: Float + FromPrimitive,
+{
+  match gamut {
+    // Display P3 shares the transfer function of sRGB.
+    Gamut::Srgb | Gamut::DisplayP3 => T::from_f64(CRITICAL_PLANES[index]).unwrap(),
+    Gamut::Rec2020 => gamut.linearized(T::from_f64((index as f64 + 0.5) / 255.0).unwrap()),
+  }
+}
+
+fn scaled_discount_from_linrgb<T>(gamut: Gamut) -> [[T; 3]; 3]
+where
+  T: Float + FromPrimitive,
+{
+  utils::color::matrix_from_f64(match gamut {
+    Gamut::Srgb => SCALED_DISCOUNT_FROM_LINRGB,
+    Gamut::DisplayP3 => SCALED_DISCOUNT_FROM_DISPLAY_P3,
+    Gamut::Rec2020 => SCALED_DISCOUNT_FROM_REC_2020,
+  })
+}
+
+fn linrgb_from_scaled_discount<T>(gamut: Gamut) -> [[T; 3]; 3]
+where
+  T: Float + FromPrimitive,
+{
+  utils::color::matrix_from_f64(match gamut {
+    Gamut::Srgb => LINRGB_FROM_SCALED_DISCOUNT,
+    Gamut::DisplayP3 => DISPLAY_P3_FROM_SCALED_DISCOUNT,
+    Gamut::Rec2020 => REC_2020_FROM_SCALED_DISCOUNT,
+  })
+}
+
+/// The matrices between linear RGB of a gamut and the cone responses of CAM16, discounted and
+/// scaled by the luminance level adaptation factor of some viewing conditions.
+struct ScaledDiscount<T> {
+  from_linrgb: [[T; 3]; 3],
+  to_linrgb: [[T; 3]; 3],
+}
+
+impl<T> ScaledDiscount<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  /// The precomputed matrices of the default viewing conditions.
+  fn new(gamut: Gamut) -> Self {
+    Self {
+      from_linrgb: scaled_discount_from_linrgb(gamut),
+      to_linrgb: linrgb_from_scaled_discount(gamut),
+    }
+  }
+
+  fn in_viewing_conditions(gamut: Gamut, viewing_conditions: &ViewingConditions<T>) -> Self {
+    let cone_from_linrgb: [[T; 3]; 3] = utils::math::matrix_product(
+      &utils::color::matrix_from_f64(XYZ_TO_CAM16RGB),
+      &utils::color::matrix_from_f64(*gamut.linrgb_to_xyz()),
+    );
+    let rgb_d = viewing_conditions.rgb_d();
+    let from_linrgb = [0, 1, 2].map(|i| {
+      let scale = viewing_conditions.fl() * rgb_d[i] / T::from_f64(100.0).unwrap();
+      cone_from_linrgb[i].map(|value| value * scale)
+    });
+    Self {
+      to_linrgb: utils::math::matrix_inverse(&from_linrgb),
+      from_linrgb,
+    }
+  }
 }
 
 fn chromatic_adaptation<T>(component: T) -> T
@@ -323,17 +434,21 @@ where
   component.signum() * T::from_f64(400.0).unwrap() * af / (af + T::from_f64(27.13).unwrap())
 }
 
-/// Returns the hue of a linear RGB color in CAM16.
-fn hue_of(linrgb: [f64; 3]) -> f64 {
-  let scaled_discount = utils::math::matrix_multiply(&linrgb, &SCALED_DISCOUNT_FROM_LINRGB);
+/// Returns the hue of a linear RGB color of the gamut in CAM16.
+fn hue_of<T>(linrgb: [T; 3], scaled_discount: &ScaledDiscount<T>) -> T
+where
+  T: Float + FromPrimitive,
+{
+  let scaled_discount = utils::math::matrix_multiply(&linrgb, &scaled_discount.from_linrgb);
   let r_a = chromatic_adaptation(scaled_discount[0]);
   let g_a = chromatic_adaptation(scaled_discount[1]);
   let b_a = chromatic_adaptation(scaled_discount[2]);
   // redness-greenness
-  let a = (11.0 * r_a + -12.0 * g_a + b_a) / 11.0;
+  let a = (T::from_f64(11.0).unwrap() * r_a + T::from_f64(-12.0).unwrap() * g_a + b_a)
+    / T::from_f64(11.0).unwrap();
   // yellowness-blueness
-  let b = (r_a + g_a - 2.0 * b_a) / 9.0;
-  f64::atan2(b, a)
+  let b = (r_a + g_a - T::from_f64(2.0).unwrap() * b_a) / T::from_f64(9.0).unwrap();
+  T::atan2(b, a)
 }
 
 fn are_in_cyclic_order<T>(a: T, b: T, c: T) -> bool
@@ -381,12 +496,21 @@ where
 }
 
 /// Returns the nth possible vertex of the polygonal intersection.
-fn nth_vertex(y: f64, n: usize) -> [f64; 3] {
-  let k_r = Y_FROM_LINRGB[0];
-  let k_g = Y_FROM_LINRGB[1];
-  let k_b = Y_FROM_LINRGB[2];
-  let coord_a = if n % 4 <= 1 { 0.0 } else { 100.0 };
-  let coord_b = if n % 2 == 0 { 0.0 } else { 100.0 };
+fn nth_vertex<T>(y: T, n: usize, gamut: Gamut) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let [k_r, k_g, k_b]: [T; 3] = gamut.y_from_linrgb();
+  let coord_a = if n % 4 <= 1 {
+    T::zero()
+  } else {
+    T::from_f64(100.0).unwrap()
+  };
+  let coord_b = if n % 2 == 0 {
+    T::zero()
+  } else {
+    T::from_f64(100.0).unwrap()
+  };
   if n < 4 {
     let g = coord_a;
     let b = coord_b;
@@ -394,7 +518,7 @@ fn nth_vertex(y: f64, n: usize) -> [f64; 3] {
     if is_bounded(r) {
       [r, g, b]
     } else {
-      [-1.0, -1.0, -1.0]
+      [-T::one(), -T::one(), -T::one()]
     }
   } else if n < 8 {
     let b = coord_a;
@@ -403,7 +527,7 @@ fn nth_vertex(y: f64, n: usize) -> [f64; 3] {
     if is_bounded(g) {
       [r, g, b]
     } else {
-      [-1.0, -1.0, -1.0]
+      [-T::one(), -T::one(), -T::one()]
     }
   } else {
     let r = coord_a;
@@ -412,25 +536,33 @@ fn nth_vertex(y: f64, n: usize) -> [f64; 3] {
     if is_bounded(b) {
       [r, g, b]
     } else {
-      [-1.0, -1.0, -1.0]
+      [-T::one(), -T::one(), -T::one()]
     }
   }
 }
 
 /// Finds the segment containing the desired color.
-fn bisect_to_segment(y: f64, target_hue: f64) -> [[f64; 3]; 2] {
-  let mut left = [-1.0, -1.0, -1.0];
+fn bisect_to_segment<T>(
+  y: T,
+  target_hue: T,
+  gamut: Gamut,
+  scaled_discount: &ScaledDiscount<T>,
+) -> [[T; 3]; 2]
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  let mut left = [-T::one(), -T::one(), -T::one()];
   let mut right = left;
-  let mut left_hue = 0.0;
-  let mut right_hue = 0.0;
+  let mut left_hue = T::zero();
+  let mut right_hue = T::zero();
   let mut initialized = false;
   let mut uncut = true;
   for n in 0..12 {
-    let mid = nth_vertex(y, n);
-    if mid[0] < 0.0 {
+    let mid = nth_vertex(y, n, gamut);
+    if mid[0] < T::zero() {
       continue;
     }
-    let mid_hue = hue_of(mid);
+    let mid_hue = hue_of(mid, scaled_discount);
     if !initialized {
       left = mid;
       right = mid;
@@ -479,32 +611,40 @@ where
 }
 
 /// Finds a color with the given Y and hue on the boundary of the cube.
-fn bisect_to_limit(y: f64, target_hue: f64) -> [f64; 3] {
-  let segment = bisect_to_segment(y, target_hue);
+fn bisect_to_limit<T>(
+  y: T,
+  target_hue: T,
+  gamut: Gamut,
+  scaled_discount: &ScaledDiscount<T>,
+) -> [T; 3]
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  let segment = bisect_to_segment(y, target_hue, gamut, scaled_discount);
   let mut left = segment[0];
-  let mut left_hue = hue_of(left);
+  let mut left_hue = hue_of(left, scaled_discount);
   let mut right = segment[1];
   for axis in 0..=2 {
     if left[axis] != right[axis] {
       let (mut l_plane, mut r_plane) = if left[axis] < right[axis] {
         (
-          critical_plane_below(true_delinearized(left[axis])),
-          critical_plane_above(true_delinearized(right[axis])),
+          critical_plane_below(true_delinearized(left[axis], gamut)),
+          critical_plane_above(true_delinearized(right[axis], gamut)),
         )
       } else {
         (
-          critical_plane_above(true_delinearized(left[axis])),
-          critical_plane_below(true_delinearized(right[axis])),
+          critical_plane_above(true_delinearized(left[axis], gamut)),
+          critical_plane_below(true_delinearized(right[axis], gamut)),
         )
       };
       for _ in 0..8 {
         if (r_plane - l_plane).abs() <= 1 {
           break;
         } else {
-          let m_plane = ((l_plane + r_plane) as f64 / 2.0).floor() as isize;
-          let mid_plane_coordinate = CRITICAL_PLANES[m_plane as usize];
+          let m_plane = (l_plane + r_plane).div_euclid(2);
+          let mid_plane_coordinate = critical_plane(m_plane as usize, gamut);
           let mid = set_coordinate(left, mid_plane_coordinate, right, axis);
-          let mid_hue = hue_of(mid);
+          let mid_hue = hue_of(mid, scaled_discount);
           if are_in_cyclic_order(left_hue, target_hue, mid_hue) {
             right = mid;
             r_plane = m_plane;
@@ -532,94 +672,414 @@ where
   adapted.signum() * base.powf(T::from_f64(1.0).unwrap() / T::from_f64(0.42).unwrap())
 }
 
-/// Finds a color with the given hue, chroma, and Y.
-fn find_result_by_j(hue_radians: f64, chroma: f64, y: f64) -> u32 {
+/// Finds a linear RGB color of the gamut with the given hue, chroma, and Y.
+fn find_result_by_j<T>(
+  hue_radians: T,
+  chroma: T,
+  y: T,
+  gamut: Gamut,
+  viewing_conditions: &ViewingConditions<T>,
+  scaled_discount: &ScaledDiscount<T>,
+) -> Option<[T; 3]>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
   // Initial estimate of j.
-  let mut j = y.sqrt() * 11.0;
+  let mut j = y.sqrt() * T::from_f64(11.0).unwrap();
   // ===========================================================
   // Operations inlined from Cam16 to avoid repeated calculation
   // ===========================================================
-  let viewing_conditions = ViewingConditions::default();
-  let t_inner_coeff = 1.0 / f64::powf(1.64 - f64::powf(0.29, viewing_conditions.n()), 0.73);
-  let e_hue = 0.25 * (f64::cos(hue_radians + 2.0) + 3.8);
-  let p1 = e_hue * (50000.0 / 13.0) * viewing_conditions.nc() * viewing_conditions.ncb();
+  let t_inner_coeff = T::one()
+    / T::powf(
+      T::from_f64(1.64).unwrap() - T::powf(T::from_f64(0.29).unwrap(), viewing_conditions.n()),
+      T::from_f64(0.73).unwrap(),
+    );
+  let e_hue = T::from_f64(0.25).unwrap()
+    * (T::cos(hue_radians + T::from_f64(2.0).unwrap()) + T::from_f64(3.8).unwrap());
+  let p1 = e_hue
+    * T::from_f64(50000.0 / 13.0).unwrap()
+    * viewing_conditions.nc()
+    * viewing_conditions.ncb();
   let h_sin = hue_radians.sin();
   let h_cos = hue_radians.cos();
+  let matrix = scaled_discount.to_linrgb;
+  let [k_r, k_g, k_b]: [T; 3] = gamut.y_from_linrgb();
 
   for iteration_round in 0..5 {
     // ===========================================================
     // Operations inlined from Cam16 to avoid repeated calculation
     // ===========================================================
-    let j_normalized = j / 100.0;
-    let alpha = if chroma == 0.0 || j == 0.0 {
-      0.0
+    let j_normalized = j / T::from_f64(100.0).unwrap();
+    let alpha = if chroma == T::zero() || j == T::zero() {
+      T::zero()
     } else {
       chroma / j_normalized.sqrt()
     };
-    let t = f64::powf(alpha * t_inner_coeff, 1.0 / 0.9);
+    let t = T::powf(alpha * t_inner_coeff, T::from_f64(1.0 / 0.9).unwrap());
     let ac = viewing_conditions.aw()
-      * f64::powf(
+      * T::powf(
         j_normalized,
-        1.0 / viewing_conditions.c() / viewing_conditions.z(),
+        T::one() / viewing_conditions.c() / viewing_conditions.z(),
       );
     let p2 = ac / viewing_conditions.nbb();
-    let gamma = 23.0 * (p2 + 0.305) * t / (23.0 * p1 + 11.0 * t * h_cos + 108.0 * t * h_sin);
+    let gamma = T::from_f64(23.0).unwrap() * (p2 + T::from_f64(0.305).unwrap()) * t
+      / (T::from_f64(23.0).unwrap() * p1
+        + T::from_f64(11.0).unwrap() * t * h_cos
+        + T::from_f64(108.0).unwrap() * t * h_sin);
     let a = gamma * h_cos;
     let b = gamma * h_sin;
-    let r_a = (460.0 * p2 + 451.0 * a + 288.0 * b) / 1403.0;
-    let g_a = (460.0 * p2 - 891.0 * a - 261.0 * b) / 1403.0;
-    let b_a = (460.0 * p2 - 220.0 * a - 6300.0 * b) / 1403.0;
+    let r_a = (T::from_f64(460.0).unwrap() * p2
+      + T::from_f64(451.0).unwrap() * a
+      + T::from_f64(288.0).unwrap() * b)
+      / T::from_f64(1403.0).unwrap();
+    let g_a = (T::from_f64(460.0).unwrap() * p2
+      - T::from_f64(891.0).unwrap() * a
+      - T::from_f64(261.0).unwrap() * b)
+      / T::from_f64(1403.0).unwrap();
+    let b_a = (T::from_f64(460.0).unwrap() * p2
+      - T::from_f64(220.0).unwrap() * a
+      - T::from_f64(6300.0).unwrap() * b)
+      / T::from_f64(1403.0).unwrap();
     let r_c_scaled = inverse_chromatic_adaptation(r_a);
     let g_c_scaled = inverse_chromatic_adaptation(g_a);
     let b_c_scaled = inverse_chromatic_adaptation(b_a);
-    let linrgb = utils::math::matrix_multiply(
-      &[r_c_scaled, g_c_scaled, b_c_scaled],
-      &LINRGB_FROM_SCALED_DISCOUNT,
-    );
+    let linrgb = utils::math::matrix_multiply(&[r_c_scaled, g_c_scaled, b_c_scaled], &matrix);
     // ===========================================================
     // Operations inlined from Cam16 to avoid repeated calculation
     // ===========================================================
-    if linrgb[0] < 0.0 || linrgb[1] < 0.0 || linrgb[2] < 0.0 {
-      return 0;
+    if linrgb[0] < T::zero() || linrgb[1] < T::zero() || linrgb[2] < T::zero() {
+      return None;
     }
-    let k_r = Y_FROM_LINRGB[0];
-    let k_g = Y_FROM_LINRGB[1];
-    let k_b = Y_FROM_LINRGB[2];
     let fnj = k_r * linrgb[0] + k_g * linrgb[1] + k_b * linrgb[2];
-    if fnj <= 0.0 {
-      return 0;
+    if fnj <= T::zero() {
+      return None;
     }
-    if iteration_round == 4 || (fnj - y).abs() < 0.002 {
-      if linrgb[0] > 100.01 || linrgb[1] > 100.01 || linrgb[2] > 100.01 {
-        return 0;
+    if iteration_round == 4 || (fnj - y).abs() < T::from_f64(0.002).unwrap() {
+      let limit = T::from_f64(100.01).unwrap();
+      if linrgb[0] > limit || linrgb[1] > limit || linrgb[2] > limit {
+        return None;
       }
-      return utils::color::argb_from_linrgb::<f64>(linrgb);
+      return Some(linrgb);
     }
     // Iterates with Newton method,
     // Using 2 * fn(j) / j as the approximation of fn'(j)
-    j = j - (fnj - y) * j / (2.0 * fnj);
+    j = j - (fnj - y) * j / (T::from_f64(2.0).unwrap() * fnj);
   }
-  0
+  None
 }
 
 /// Finds an sRGB color with the given hue, chroma, and L*, if possible.
-pub fn solve_to_int(hue_degrees: f64, chroma: f64, lstar: f64) -> u32 {
-  if chroma < 0.0001 || !(0.0001..=99.9999).contains(&lstar) {
+///
+/// If the color is outside of the sRGB gamut, the color with the same hue and L*, and the most
+/// chroma available, is returned.
+pub fn solve_to_int<T>(hue_degrees: T, chroma: T, lstar: T) -> u32
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  solve_to_int_in(
+    hue_degrees,
+    chroma,
+    lstar,
+    &ViewingConditions::default(),
+    &ScaledDiscount::new(Gamut::Srgb),
+  )
+}
+
+/// Like [`solve_to_int`], with the hue and chroma measured in the given viewing conditions instead
+/// of the default ones.
+pub fn solve_to_int_in_viewing_conditions<T>(
+  hue_degrees: T,
+  chroma: T,
+  lstar: T,
+  viewing_conditions: &ViewingConditions<T>,
+) -> u32
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  solve_to_int_in(
+    hue_degrees,
+    chroma,
+    lstar,
+    viewing_conditions,
+    &ScaledDiscount::in_viewing_conditions(Gamut::Srgb, viewing_conditions),
+  )
+}
+
+fn solve_to_int_in<T>(
+  hue_degrees: T,
+  chroma: T,
+  lstar: T,
+  viewing_conditions: &ViewingConditions<T>,
+  scaled_discount: &ScaledDiscount<T>,
+) -> u32
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  if chroma < T::from_f64(0.0001).unwrap() || !is_chromatic_lstar(lstar) {
     return utils::color::argb_from_lstar(lstar);
   }
+  utils::color::argb_from_linrgb(solve_to_linrgb_in(
+    hue_degrees,
+    chroma,
+    lstar,
+    Gamut::Srgb,
+    viewing_conditions,
+    scaled_discount,
+  ))
+}
+
+/// Like [`solve_to_int`], for many colors given as `[hue, chroma, L*]`.
+///
+/// The results are the same as solving each color on its own, but values shared by every color
+/// are only computed once. With the `parallel` feature enabled, colors are solved in parallel.
+pub fn solve_to_int_batch(colors: &[[f64; 3]]) -> Vec<u32> {
+  let viewing_conditions = ViewingConditions::default();
+  let scaled_discount = ScaledDiscount::new(Gamut::Srgb);
+  let solve = |&[hue_degrees, chroma, lstar]: &[f64; 3]| {
+    solve_to_int_in(
+      hue_degrees,
+      chroma,
+      lstar,
+      &viewing_conditions,
+      &scaled_discount,
+    )
+  };
+  #[cfg(feature = "parallel")]
+  let argbs = colors.par_iter().map(solve).collect();
+  #[cfg(not(feature = "parallel"))]
+  let argbs = colors.iter().map(solve).collect();
+  argbs
+}
+
+/// Finds a linear RGB color of the gamut with the given hue, chroma, and L*, if possible.
+///
+/// Components range from 0 to 100. If the color is outside of the gamut, the color with the same
+/// hue and L*, and the most chroma available in the gamut, is returned.
+pub fn solve_to_linrgb<T>(hue_degrees: T, chroma: T, lstar: T, gamut: Gamut) -> [T; 3]
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  solve_to_linrgb_in(
+    hue_degrees,
+    chroma,
+    lstar,
+    gamut,
+    &ViewingConditions::default(),
+    &ScaledDiscount::new(gamut),
+  )
+}
+
+/// Like [`solve_to_linrgb`], with the hue and chroma measured in the given viewing conditions
+/// instead of the default ones.
+pub fn solve_to_linrgb_in_viewing_conditions<T>(
+  hue_degrees: T,
+  chroma: T,
+  lstar: T,
+  gamut: Gamut,
+  viewing_conditions: &ViewingConditions<T>,
+) -> [T; 3]
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  solve_to_linrgb_in(
+    hue_degrees,
+    chroma,
+    lstar,
+    gamut,
+    viewing_conditions,
+    &ScaledDiscount::in_viewing_conditions(gamut, viewing_conditions),
+  )
+}
+
+fn solve_to_linrgb_in<T>(
+  hue_degrees: T,
+  chroma: T,
+  lstar: T,
+  gamut: Gamut,
+  viewing_conditions: &ViewingConditions<T>,
+  scaled_discount: &ScaledDiscount<T>,
+) -> [T; 3]
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  let y = utils::color::y_from_lstar(lstar);
+  if chroma < T::from_f64(0.0001).unwrap() || !is_chromatic_lstar(lstar) {
+    // Every gamut shares the same white point, so grays have equal components.
+    return [y.clamp(T::zero(), T::from_f64(100.0).unwrap()); 3];
+  }
   let hue_degrees = utils::math::sanitize_degrees(hue_degrees);
-  let hue_radians = hue_degrees / 180.0 * f64::consts::PI;
+  let hue_radians = hue_degrees / T::from_f64(180.0).unwrap() * T::PI();
+  find_result_by_j(
+    hue_radians,
+    chroma,
+    y,
+    gamut,
+    viewing_conditions,
+    scaled_discount,
+  )
+  .unwrap_or_else(|| bisect_to_limit(y, hue_radians, gamut, scaled_discount))
+}
+
+/// Whether an L* is far enough from black and white to have chroma.
+fn is_chromatic_lstar<T>(lstar: T) -> bool
+where
+  T: Float + FromPrimitive,
+{
+  T::from_f64(0.0001).unwrap() <= lstar && lstar <= T::from_f64(99.9999).unwrap()
+}
+
+/// Finds an RGB color of the gamut with the given hue, chroma, and L*, if possible.
+///
+/// Components range from 0 to 1, encoded with the transfer function of the gamut, and are not
+/// rounded. See [`solve_to_linrgb`].
+pub fn solve_to_rgb<T>(hue_degrees: T, chroma: T, lstar: T, gamut: Gamut) -> [T; 3]
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  gamut.rgb_from_linrgb(solve_to_linrgb(hue_degrees, chroma, lstar, gamut))
+}
+
+/// Like [`solve_to_rgb`], for many colors given as `[hue, chroma, L*]`.
+///
+/// See [`solve_to_int_batch`].
+pub fn solve_to_rgb_batch(colors: &[[f64; 3]], gamut: Gamut) -> Vec<[f64; 3]> {
+  let viewing_conditions = ViewingConditions::default();
+  let scaled_discount = ScaledDiscount::new(gamut);
+  let solve = |&[hue_degrees, chroma, lstar]: &[f64; 3]| {
+    gamut.rgb_from_linrgb(solve_to_linrgb_in(
+      hue_degrees,
+      chroma,
+      lstar,
+      gamut,
+      &viewing_conditions,
+      &scaled_discount,
+    ))
+  };
+  #[cfg(feature = "parallel")]
+  let rgbs = colors.par_iter().map(solve).collect();
+  #[cfg(not(feature = "parallel"))]
+  let rgbs = colors.iter().map(solve).collect();
+  rgbs
+}
+
+/// Finds the most chroma available in the gamut for the given hue and L*.
+///
+/// For sRGB, this is the chroma of the 8-bit color [`solve_to_int`] returns for a chroma outside
+/// the gamut. For caching, and for the tone with the most chroma, see
+/// [`GamutBoundary`](crate::hct::GamutBoundary).
+pub fn max_chroma<T>(hue_degrees: T, lstar: T, gamut: Gamut) -> T
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  max_chroma_in(
+    hue_degrees,
+    lstar,
+    gamut,
+    &ViewingConditions::default(),
+    &ScaledDiscount::new(gamut),
+  )
+}
+
+/// Like [`max_chroma`], with the hue and chroma measured in the given viewing conditions instead
+/// of the default ones.
+pub fn max_chroma_in_viewing_conditions<T>(
+  hue_degrees: T,
+  lstar: T,
+  gamut: Gamut,
+  viewing_conditions: &ViewingConditions<T>,
+) -> T
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  max_chroma_in(
+    hue_degrees,
+    lstar,
+    gamut,
+    viewing_conditions,
+    &ScaledDiscount::in_viewing_conditions(gamut, viewing_conditions),
+  )
+}
+
+fn max_chroma_in<T>(
+  hue_degrees: T,
+  lstar: T,
+  gamut: Gamut,
+  viewing_conditions: &ViewingConditions<T>,
+  scaled_discount: &ScaledDiscount<T>,
+) -> T
+where
+  T: Float + FloatConst + FromPrimitive,
+{
   let y = utils::color::y_from_lstar(lstar);
-  let exact_answer = find_result_by_j(hue_radians, chroma, y);
-  if exact_answer != 0 {
-    return exact_answer;
+  let linrgb = if is_chromatic_lstar(lstar) {
+    let hue_radians =
+      utils::math::sanitize_degrees(hue_degrees) / T::from_f64(180.0).unwrap() * T::PI();
+    bisect_to_limit(y, hue_radians, gamut, scaled_discount)
+  } else {
+    [y.clamp(T::zero(), T::from_f64(100.0).unwrap()); 3]
+  };
+  match gamut {
+    Gamut::Srgb => Cam16::from_int_in_viewing_conditions(
+      utils::color::argb_from_linrgb(linrgb),
+      viewing_conditions,
+    )
+    .chroma(),
+    _ => {
+      let [x, y, z] = gamut.xyz_from_linrgb(linrgb);
+      Cam16::from_xyz_in_viewing_conditions(x, y, z, viewing_conditions).chroma()
+    }
   }
-  let linrgb = bisect_to_limit(y, hue_radians);
-  utils::color::argb_from_linrgb::<f64>(linrgb)
 }
 
 /// Finds an sRGB color with the given hue, chroma, and L*, if possible.
 #[allow(dead_code)]
-pub fn solve_to_cam(hue_degrees: f64, chroma: f64, lstar: f64) -> Cam16 {
-  Cam16::from_int(solve_to_int(hue_degrees, chroma, lstar))
+pub fn solve_to_cam<T>(hue_degrees: T, chroma: T, lstar: T) -> Cam16<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  Cam16::from(solve_to_int(hue_degrees, chroma, lstar))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn scaled_discount_matches_default_viewing_conditions() {
+    let viewing_conditions: ViewingConditions = ViewingConditions::default();
+    for gamut in [Gamut::Srgb, Gamut::DisplayP3, Gamut::Rec2020] {
+      let precomputed = ScaledDiscount::<f64>::new(gamut);
+      let computed = ScaledDiscount::in_viewing_conditions(gamut, &viewing_conditions);
+      for (matrix, expected) in [
+        (computed.from_linrgb, precomputed.from_linrgb),
+        (computed.to_linrgb, precomputed.to_linrgb),
+      ] {
+        for (row, expected_row) in matrix.iter().zip(expected) {
+          for (value, expected_value) in row.iter().zip(expected_row) {
+            assert!((value - expected_value).abs() <= 1e-6 * f64::max(expected_value.abs(), 1.0));
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn batch_matches_single() {
+    let colors: Vec<[f64; 3]> = (0..=360)
+      .step_by(15)
+      .flat_map(|hue| {
+        [
+          [hue as f64, 48.0, 40.0],
+          [hue as f64, 150.0, 90.0],
+          [hue as f64, 0.0, 50.0],
+        ]
+      })
+      .collect();
+    let argbs = solve_to_int_batch(&colors);
+    let rgbs = solve_to_rgb_batch(&colors, Gamut::DisplayP3);
+    for ((&[hue, chroma, lstar], argb), rgb) in colors.iter().zip(argbs).zip(rgbs) {
+      assert_eq!(argb, solve_to_int(hue, chroma, lstar));
+      assert_eq!(rgb, solve_to_rgb(hue, chroma, lstar, Gamut::DisplayP3));
+    }
+  }
 }
@@ -1,168 +1,572 @@
 mod cam16;
-mod hct_solver;
+mod gamut;
+mod gamut_boundary;
+#[cfg(feature = "std")]
+mod hct_lut;
+pub mod hct_solver;
 mod viewing_conditions;
 
 pub(crate) use cam16::XYZ_TO_CAM16RGB;
 
 pub use cam16::Cam16;
-use ordered_float::NotNan;
-pub use viewing_conditions::ViewingConditions;
+pub use gamut::Gamut;
+pub use gamut_boundary::GamutBoundary;
+#[cfg(feature = "std")]
+pub use hct_lut::HctLut;
+pub use viewing_conditions::{ViewingConditions, ViewingConditionsBuilder};
 
-use std::fmt::Display;
+use alloc::boxed::Box;
+use core::{
+  cmp::Ordering,
+  fmt::Display,
+  hash::{Hash, Hasher},
+};
 
-use num_traits::{Float, FromPrimitive, Zero};
+use num_traits::{Float, FloatConst, FromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::utils;
+use crate::{color::Argb, utils};
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// A color in the HCT color space: hue and chroma from CAM16, and tone from L*.
+///
+/// Components are `f64` by default, and can be `f32` instead, e.g. on targets without a
+/// double-precision FPU. Constructors that don't take a component, like [`Hct::from_int`], are
+/// only available for `f64`. For `f32`, convert the ARGB integer with [`From<u32>`] or
+/// [`From<Argb>`] instead.
+///
+/// Colors are totally ordered even if a component is NaN, as constructors reject but deserialized
+/// colors may have: NaN components are equal to each other, and ordered after every number.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-pub struct Hct {
-  hue: NotNan<f64>,
-  chroma: NotNan<f64>,
-  tone: NotNan<f64>,
+pub struct Hct<T = f64> {
+  hue: T,
+  chroma: T,
+  tone: T,
   argb: u32,
+  #[cfg_attr(feature = "serde", serde(default))]
+  gamut: Gamut,
+  /// Kept out of line, so that sRGB colors in the default viewing conditions, which don't need it,
+  /// stay small.
+  #[cfg_attr(feature = "serde", serde(default))]
+  space: Option<Box<HctSpace<T>>>,
+}
+
+/// What a color needs besides its ARGB integer when it isn't in sRGB or in the default viewing
+/// conditions.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+struct HctSpace<T> {
+  /// The RGB components in the gamut, if the gamut isn't sRGB. sRGB colors are represented exactly
+  /// by `argb`.
+  #[cfg_attr(feature = "serde", serde(default))]
+  rgb: Option<[T; 3]>,
+  /// The viewing conditions the hue and chroma are measured in, if they aren't the default ones.
+  #[cfg_attr(feature = "serde", serde(default))]
+  viewing_conditions: Option<ViewingConditions<T>>,
+}
+
+impl<T> HctSpace<T> {
+  fn new(
+    rgb: Option<[T; 3]>,
+    viewing_conditions: Option<ViewingConditions<T>>,
+  ) -> Option<Box<Self>> {
+    (rgb.is_some() || viewing_conditions.is_some()).then(|| {
+      Box::new(Self {
+        rgb,
+        viewing_conditions,
+      })
+    })
+  }
 }
 
 impl Hct {
+  /// Create a color from an ARGB integer, or any color that converts into [`Argb`].
+  pub fn from_int(argb: impl Into<Argb>) -> Self {
+    Self::new(argb.into().to_int())
+  }
+
+  pub fn is_blue<T>(hue: T) -> bool
+  where
+    T: Float + FromPrimitive,
+  {
+    hue >= T::from_f64(250.0).unwrap() && hue < T::from_f64(270.0).unwrap()
+  }
+
+  pub fn is_yellow<T>(hue: T) -> bool
+  where
+    T: Float + FromPrimitive,
+  {
+    hue >= T::from_f64(105.0).unwrap() && hue < T::from_f64(125.0).unwrap()
+  }
+
+  pub fn is_cyan<T>(hue: T) -> bool
+  where
+    T: Float + FromPrimitive,
+  {
+    hue >= T::from_f64(170.0).unwrap() && hue < T::from_f64(207.0).unwrap()
+  }
+}
+
+impl<T> Hct<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
   fn new(argb: u32) -> Self {
-    let cam = Cam16::from_int(argb);
+    let cam = Cam16::<T>::from(argb);
     Self {
       argb,
-      hue: cam.hue().try_into().unwrap(),
-      chroma: cam.chroma().try_into().unwrap(),
-      tone: NotNan::new(utils::color::lstar_from_argb(argb)).unwrap(),
+      hue: cam.hue(),
+      chroma: cam.chroma(),
+      tone: utils::color::lstar_from_argb(argb),
+      gamut: Gamut::Srgb,
+      space: None,
     }
   }
-  pub fn from(hue: f64, chroma: f64, tone: f64) -> Self {
-    let argb = hct_solver::solve_to_int(hue, chroma, tone);
-    Self::new(argb)
+
+  fn from_int_in(argb: u32, viewing_conditions: Option<&ViewingConditions<T>>) -> Self {
+    let Some(viewing_conditions) = viewing_conditions else {
+      return Self::new(argb);
+    };
+    let cam = Cam16::from_int_in_viewing_conditions(argb, viewing_conditions);
+    Self {
+      argb,
+      hue: cam.hue(),
+      chroma: cam.chroma(),
+      tone: utils::color::lstar_from_argb(argb),
+      gamut: Gamut::Srgb,
+      space: HctSpace::new(None, Some(viewing_conditions.clone())),
+    }
   }
 
-  pub fn from_int(argb: u32) -> Self {
-    Self::new(argb)
+  fn from_linrgb_in(
+    linrgb: [T; 3],
+    gamut: Gamut,
+    viewing_conditions: Option<&ViewingConditions<T>>,
+  ) -> Self {
+    if gamut == Gamut::Srgb {
+      return Self::from_int_in(utils::color::argb_from_linrgb(linrgb), viewing_conditions);
+    }
+    let [x, y, z] = gamut.xyz_from_linrgb(linrgb);
+    let cam = Cam16::from_xyz_in_viewing_conditions(
+      x,
+      y,
+      z,
+      viewing_conditions.unwrap_or(&ViewingConditions::default()),
+    );
+    let tone = utils::color::lstar_from_y(y);
+    Self {
+      argb: match viewing_conditions {
+        Some(viewing_conditions) => hct_solver::solve_to_int_in_viewing_conditions(
+          cam.hue(),
+          cam.chroma(),
+          tone,
+          viewing_conditions,
+        ),
+        None => hct_solver::solve_to_int(cam.hue(), cam.chroma(), tone),
+      },
+      hue: cam.hue(),
+      chroma: cam.chroma(),
+      tone,
+      gamut,
+      space: HctSpace::new(
+        Some(gamut.rgb_from_linrgb(linrgb)),
+        viewing_conditions.cloned(),
+      ),
+    }
+  }
+
+  /// Solves a color in a gamut, with the hue and chroma measured in the viewing conditions, or in
+  /// the default ones if there are none.
+  pub(crate) fn from_in(
+    hue: T,
+    chroma: T,
+    tone: T,
+    gamut: Gamut,
+    viewing_conditions: Option<&ViewingConditions<T>>,
+  ) -> Self {
+    match (gamut, viewing_conditions) {
+      (Gamut::Srgb, None) => Self::new(hct_solver::solve_to_int(hue, chroma, tone)),
+      (Gamut::Srgb, Some(vc)) => Self::from_int_in(
+        hct_solver::solve_to_int_in_viewing_conditions(hue, chroma, tone, vc),
+        viewing_conditions,
+      ),
+      (_, None) => Self::from_linrgb_in(
+        hct_solver::solve_to_linrgb(hue, chroma, tone, gamut),
+        gamut,
+        None,
+      ),
+      (_, Some(vc)) => Self::from_linrgb_in(
+        hct_solver::solve_to_linrgb_in_viewing_conditions(hue, chroma, tone, gamut, vc),
+        gamut,
+        viewing_conditions,
+      ),
+    }
+  }
+
+  /// The default viewing conditions are stored as none, so colors measured in them are the same
+  /// however they were created.
+  pub(crate) fn non_default(
+    viewing_conditions: &ViewingConditions<T>,
+  ) -> Option<&ViewingConditions<T>> {
+    (*viewing_conditions != ViewingConditions::default()).then_some(viewing_conditions)
+  }
+
+  /// Create a color from its hue, chroma and tone.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a component is NaN.
+  pub fn from(hue: T, chroma: T, tone: T) -> Self {
+    Self::from_in(hue, chroma, tone, Gamut::Srgb, None)
+  }
+
+  /// Create a color in a gamut from its hue, chroma and tone.
+  ///
+  /// If the chroma isn't available in the gamut, the color with the most chroma available for the
+  /// hue and tone is created instead. Wide gamuts have more chroma available than sRGB, which
+  /// [`Hct::to_rgb`] keeps.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a component is NaN.
+  pub fn from_in_gamut(hue: T, chroma: T, tone: T, gamut: Gamut) -> Self {
+    Self::from_in(hue, chroma, tone, gamut, None)
   }
 
-  pub fn hue(&self) -> f64 {
-    *self.hue
+  /// Create a color from its RGB components in a gamut, ranging from 0 to 1.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a component is NaN.
+  pub fn from_rgb_in_gamut(rgb: [T; 3], gamut: Gamut) -> Self {
+    Self::from_linrgb_in(gamut.linrgb_from_rgb(rgb), gamut, None)
   }
 
-  pub fn set_hue(&mut self, new_hue: f64) {
-    self.set_internal_state(hct_solver::solve_to_int(
+  /// Create a color from its hue, chroma and tone, with the hue and chroma measured in the given
+  /// viewing conditions instead of the default ones.
+  ///
+  /// The color appears with the given hue and chroma to an observer in the viewing conditions,
+  /// e.g. [`ViewingConditions::dim_room`]. Setters and [`Hct::in_gamut`] keep the viewing
+  /// conditions. Unlike [`Hct::in_viewing_conditions`], the color isn't adjusted to match how it
+  /// appears in the default viewing conditions.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a component is NaN.
+  pub fn from_in_viewing_conditions(
+    hue: T,
+    chroma: T,
+    tone: T,
+    viewing_conditions: &ViewingConditions<T>,
+  ) -> Self {
+    Self::from_in(
+      hue,
+      chroma,
+      tone,
+      Gamut::Srgb,
+      Self::non_default(viewing_conditions),
+    )
+  }
+
+  /// Create a color from an ARGB integer, with the hue and chroma measured in the given viewing
+  /// conditions instead of the default ones.
+  pub fn from_int_in_viewing_conditions(
+    argb: impl Into<Argb>,
+    viewing_conditions: &ViewingConditions<T>,
+  ) -> Self {
+    Self::from_int_in(argb.into().to_int(), Self::non_default(viewing_conditions))
+  }
+
+  pub fn hue(&self) -> T {
+    self.hue
+  }
+
+  /// # Panics
+  ///
+  /// Panics if the hue is NaN.
+  pub fn set_hue(&mut self, new_hue: T) {
+    *self = Self::from_in(
       new_hue,
       self.chroma(),
       self.tone(),
-    ));
+      self.gamut,
+      self.viewing_conditions(),
+    );
   }
 
-  pub fn chroma(&self) -> f64 {
-    *self.chroma
+  pub fn chroma(&self) -> T {
+    self.chroma
   }
 
-  pub fn set_chroma(&mut self, new_chroma: f64) {
-    self.set_internal_state(hct_solver::solve_to_int(
+  /// # Panics
+  ///
+  /// Panics if the chroma is NaN.
+  pub fn set_chroma(&mut self, new_chroma: T) {
+    *self = Self::from_in(
       self.hue(),
       new_chroma,
       self.tone(),
-    ));
+      self.gamut,
+      self.viewing_conditions(),
+    );
   }
 
-  pub fn tone(&self) -> f64 {
-    *self.tone
+  pub fn tone(&self) -> T {
+    self.tone
   }
 
-  pub fn set_tone(&mut self, new_tone: f64) {
-    self.set_internal_state(hct_solver::solve_to_int(
+  /// # Panics
+  ///
+  /// Panics if the tone is NaN.
+  pub fn set_tone(&mut self, new_tone: T) {
+    *self = Self::from_in(
       self.hue(),
       self.chroma(),
       new_tone,
-    ));
+      self.gamut,
+      self.viewing_conditions(),
+    );
   }
 
+  /// The color as an sRGB ARGB integer.
+  ///
+  /// For colors in a wide gamut, this is the sRGB color with the same hue and tone, and the most
+  /// chroma available in sRGB.
   pub fn to_int(&self) -> u32 {
     self.argb
   }
 
-  pub fn in_viewing_conditions(&self, vc: &ViewingConditions) -> Hct {
+  /// The gamut the color was solved in.
+  pub fn gamut(&self) -> Gamut {
+    self.gamut
+  }
+
+  /// The RGB components of the color in its gamut, ranging from 0 to 1.
+  pub fn to_rgb(&self) -> [T; 3] {
+    match self.rgb() {
+      Some(&rgb) => rgb,
+      None => [
+        utils::color::red_from_argb(self.argb),
+        utils::color::green_from_argb(self.argb),
+        utils::color::blue_from_argb(self.argb),
+      ]
+      .map(|component| T::from_u8(component).unwrap() / T::from_f64(255.0).unwrap()),
+    }
+  }
+
+  /// The most chroma available in the color's gamut for its hue and tone.
+  pub fn max_chroma(&self) -> T {
+    match self.viewing_conditions() {
+      Some(vc) => {
+        hct_solver::max_chroma_in_viewing_conditions(self.hue(), self.tone(), self.gamut, vc)
+      }
+      None => hct_solver::max_chroma(self.hue(), self.tone(), self.gamut),
+    }
+  }
+
+  /// The color with the same hue, chroma and tone, solved in another gamut.
+  pub fn in_gamut(&self, gamut: Gamut) -> Self {
+    Self::from_in(
+      self.hue(),
+      self.chroma(),
+      self.tone(),
+      gamut,
+      self.viewing_conditions(),
+    )
+  }
+
+  fn cam16(&self) -> Cam16<T> {
+    let default_vc = ViewingConditions::default();
+    let vc = self.viewing_conditions().unwrap_or(&default_vc);
+    match self.gamut {
+      Gamut::Srgb => Cam16::from_int_in_viewing_conditions(self.to_int(), vc),
+      gamut => {
+        let [x, y, z] = gamut.xyz_from_linrgb(gamut.linrgb_from_rgb(self.to_rgb()));
+        Cam16::from_xyz_in_viewing_conditions(x, y, z, vc)
+      }
+    }
+  }
+
+  /// The color that, viewed in the given viewing conditions, appears the same as this color does
+  /// in the viewing conditions it is measured in.
+  pub fn in_viewing_conditions(&self, vc: &ViewingConditions<T>) -> Self {
+    let default_vc = ViewingConditions::default();
+    let own_vc = self.viewing_conditions().unwrap_or(&default_vc);
+
     // 1. Use CAM16 to find XYZ coordinates of color in specified VC.
-    let cam16 = Cam16::from_int(self.to_int());
+    let cam16 = self.cam16();
     let viewed_in_vc = cam16.xyz_in_viewing_conditions(vc);
 
-    // 2. Create CAM16 of those XYZ coordinates in default VC.
+    // 2. Create CAM16 of those XYZ coordinates in the color's own VC.
     let recast_in_vc = Cam16::from_xyz_in_viewing_conditions(
       viewed_in_vc[0],
       viewed_in_vc[1],
       viewed_in_vc[2],
-      &ViewingConditions::default(),
+      own_vc,
     );
 
     // 3. Create HCT from:
-    // - CAM16 using default VC with XYZ coordinates in specified VC.
+    // - CAM16 using the color's own VC with XYZ coordinates in specified VC.
     // - L* converted from Y in XYZ coordinates in specified VC.
-    Hct::from(
+    Self::from_in(
       recast_in_vc.hue(),
       recast_in_vc.chroma(),
       utils::color::lstar_from_y(viewed_in_vc[1]),
+      self.gamut,
+      self.viewing_conditions(),
     )
   }
+}
 
-  fn set_internal_state(&mut self, argb: u32) {
-    self.argb = argb;
-    let cam = Cam16::from_int(argb);
-    self.hue = cam.hue().try_into().unwrap();
-    self.chroma = cam.chroma().try_into().unwrap();
-    self.tone = NotNan::new(utils::color::lstar_from_argb(argb)).unwrap();
+impl<T> From<u32> for Hct<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  fn from(argb: u32) -> Self {
+    Self::new(argb)
   }
+}
 
-  pub fn is_blue<T>(hue: T) -> bool
-  where
-    T: Float + FromPrimitive,
-  {
-    hue >= T::from_f64(250.0).unwrap() && hue < T::from_f64(270.0).unwrap()
+impl<T: PartialOrd> PartialEq for Hct<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.total_cmp(other) == Ordering::Equal
   }
+}
 
-  pub fn is_yellow<T>(hue: T) -> bool
-  where
-    T: Float + FromPrimitive,
-  {
-    hue >= T::from_f64(105.0).unwrap() && hue < T::from_f64(125.0).unwrap()
+impl<T: PartialOrd> Eq for Hct<T> {}
+
+impl<T: PartialOrd> PartialOrd for Hct<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
   }
+}
 
-  pub fn is_cyan<T>(hue: T) -> bool
-  where
-    T: Float + FromPrimitive,
-  {
-    hue >= T::from_f64(170.0).unwrap() && hue < T::from_f64(207.0).unwrap()
+impl<T: PartialOrd> Ord for Hct<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.total_cmp(other)
   }
 }
 
-impl From<u32> for Hct {
-  fn from(argb: u32) -> Self {
-    Hct::from_int(argb)
+impl<T> Hct<T> {
+  /// The viewing conditions the hue and chroma are measured in, or `None` for the default ones.
+  pub fn viewing_conditions(&self) -> Option<&ViewingConditions<T>> {
+    self
+      .space
+      .as_ref()
+      .and_then(|space| space.viewing_conditions.as_ref())
+  }
+
+  /// The RGB components in the gamut, if the gamut isn't sRGB.
+  fn rgb(&self) -> Option<&[T; 3]> {
+    self.space.as_ref().and_then(|space| space.rgb.as_ref())
+  }
+}
+
+impl<T: PartialOrd> Hct<T> {
+  /// Compares the components in order, with NaN after every number, so that colors are totally
+  /// ordered even if a component is NaN.
+  fn total_cmp(&self, other: &Self) -> Ordering {
+    total_cmp_components(
+      [&self.hue, &self.chroma, &self.tone],
+      [&other.hue, &other.chroma, &other.tone],
+    )
+    .then(self.argb.cmp(&other.argb))
+    .then(self.gamut.cmp(&other.gamut))
+    .then_with(|| total_cmp_optional_components(self.rgb(), other.rgb()))
+    .then_with(|| {
+      total_cmp_optional_components(
+        self.viewing_conditions().map(ViewingConditions::parameters),
+        other
+          .viewing_conditions()
+          .map(ViewingConditions::parameters),
+      )
+    })
+  }
+}
+
+/// Orders components like `partial_cmp`, with NaN equal to itself and after every number.
+pub(crate) fn total_cmp_components<'a, T: PartialOrd + 'a>(
+  a: impl IntoIterator<Item = &'a T>,
+  b: impl IntoIterator<Item = &'a T>,
+) -> Ordering {
+  let is_nan = |component: &T| component.partial_cmp(component).is_none();
+  let mut b = b.into_iter();
+  for a in a {
+    let Some(b) = b.next() else {
+      return Ordering::Greater;
+    };
+    let ordering = a
+      .partial_cmp(b)
+      .unwrap_or_else(|| is_nan(a).cmp(&is_nan(b)));
+    if ordering.is_ne() {
+      return ordering;
+    }
+  }
+  if b.next().is_some() {
+    Ordering::Less
+  } else {
+    Ordering::Equal
+  }
+}
+
+pub(crate) fn total_cmp_optional_components<
+  'a,
+  T: PartialOrd + 'a,
+  I: IntoIterator<Item = &'a T>,
+>(
+  a: Option<I>,
+  b: Option<I>,
+) -> Ordering {
+  match (a, b) {
+    (Some(a), Some(b)) => total_cmp_components(a, b),
+    (a, b) => a.is_some().cmp(&b.is_some()),
   }
 }
 
-impl Display for Hct {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<T> Hash for Hct<T> {
+  /// The hue, chroma and tone are determined by the RGB color, so only it is hashed.
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.argb.hash(state);
+    self.gamut.hash(state);
+  }
+}
+
+impl<T> Display for Hct<T>
+where
+  T: Float,
+{
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    // Like an `as` cast, NaN components are shown as 0.
+    let rounded = |component: T| {
+      component
+        .round()
+        .to_f64()
+        .map_or(0, |component| component as i32)
+    };
     write!(
       f,
       "HCT({}, {}, {})",
-      self.hue().round() as i32,
-      self.chroma().round() as i32,
-      self.tone().round() as i32,
+      rounded(self.hue),
+      rounded(self.chroma),
+      rounded(self.tone),
     )
   }
 }
 
 // Explicit implementation
-impl Default for Hct {
+impl<T> Default for Hct<T>
+where
+  T: Float,
+{
   fn default() -> Self {
     Self {
-      hue: NotNan::zero(),
-      chroma: NotNan::zero(),
-      tone: NotNan::zero(),
+      hue: T::zero(),
+      chroma: T::zero(),
+      tone: T::zero(),
       argb: 0xff000000,
+      gamut: Gamut::Srgb,
+      space: None,
     }
   }
 }
@@ -187,4 +591,139 @@ mod tests {
       }
     }
   }
+
+  #[test]
+  fn wide_gamuts_have_more_chroma() {
+    let srgb = Hct::from(140.0, 200.0, 80.0);
+    let display_p3 = Hct::from_in_gamut(140.0, 200.0, 80.0, Gamut::DisplayP3);
+    let rec_2020 = Hct::from_in_gamut(140.0, 200.0, 80.0, Gamut::Rec2020);
+    assert!(display_p3.chroma() > srgb.chroma() + 10.0);
+    assert!(rec_2020.chroma() > display_p3.chroma());
+    for hct in [&display_p3, &rec_2020] {
+      assert!((hct.hue() - 140.0).abs() < 1.0);
+      assert!((hct.tone() - 80.0).abs() < 0.5);
+      assert_eq!(hct.to_int(), srgb.to_int());
+    }
+  }
+
+  #[test]
+  fn preserves_colors_in_wide_gamuts() {
+    for gamut in [Gamut::DisplayP3, Gamut::Rec2020] {
+      let hct = Hct::from_in_gamut(30.0, 60.0, 50.0, gamut);
+      assert_eq!(hct.gamut(), gamut);
+      assert!((hct.chroma() - 60.0).abs() < 0.5);
+      let reconstructed = Hct::from_rgb_in_gamut(hct.to_rgb(), gamut);
+      assert!((reconstructed.hue() - hct.hue()).abs() < 1e-6);
+      assert!((reconstructed.chroma() - hct.chroma()).abs() < 1e-6);
+      assert!((reconstructed.tone() - hct.tone()).abs() < 1e-6);
+    }
+  }
+
+  #[test]
+  fn srgb_gamut_matches_srgb() {
+    let hct = Hct::from_in_gamut(270.0, 40.0, 60.0, Gamut::Srgb);
+    assert_eq!(hct, Hct::from(270.0, 40.0, 60.0));
+    let [red, green, blue] = hct.to_rgb();
+    assert_eq!(
+      utils::color::argb_from_rgb(
+        (red * 255.0).round() as u8,
+        (green * 255.0).round() as u8,
+        (blue * 255.0).round() as u8
+      ),
+      hct.to_int()
+    );
+  }
+
+  #[test]
+  fn solves_in_viewing_conditions() {
+    for vc in [
+      ViewingConditions::dim_room(),
+      ViewingConditions::bright_outdoor(),
+      ViewingConditions::d50_print_booth(),
+    ] {
+      for hue in (15..360).step_by(60) {
+        let hue = hue as f64;
+        let hct = Hct::from_in_viewing_conditions(hue, 30.0, 60.0, &vc);
+        assert_eq!(hct.viewing_conditions(), Some(&vc));
+        assert!((hct.hue() - hue).abs() < 1.0);
+        assert!((hct.chroma() - 30.0).abs() < 1.0);
+        assert!((hct.tone() - 60.0).abs() < 0.5);
+        let from_int = Hct::from_int_in_viewing_conditions(hct.to_int(), &vc);
+        assert_eq!(from_int, hct);
+        // Measured in the default viewing conditions, the color looks different.
+        let default = Hct::from_int(hct.to_int());
+        assert!((default.hue() - hue).abs() > 0.1 || (default.chroma() - 30.0).abs() > 0.1);
+      }
+    }
+  }
+
+  #[test]
+  fn keeps_viewing_conditions() {
+    let vc = ViewingConditions::dim_room();
+    let mut hct = Hct::from_in_viewing_conditions(200.0, 40.0, 50.0, &vc);
+    hct.set_tone(70.0);
+    assert_eq!(hct.viewing_conditions(), Some(&vc));
+    assert!((hct.hue() - 200.0).abs() < 1.0);
+    let display_p3 = hct.in_gamut(Gamut::DisplayP3);
+    assert_eq!(display_p3.viewing_conditions(), Some(&vc));
+    assert!((display_p3.hue() - 200.0).abs() < 1.0);
+    assert!(
+      (hct.max_chroma() - Hct::from_in_viewing_conditions(200.0, 200.0, 70.0, &vc).chroma()).abs()
+        < 1.0
+    );
+    // The default viewing conditions are the same as none.
+    let default = Hct::from_in_viewing_conditions(200.0, 40.0, 50.0, &ViewingConditions::default());
+    assert_eq!(default, Hct::from(200.0, 40.0, 50.0));
+    assert_eq!(default.viewing_conditions(), None);
+  }
+
+  #[test]
+  fn srgb_colors_stay_small() {
+    // The hue, chroma and tone, the ARGB integer and gamut, and a pointer to the rest.
+    assert!(size_of::<Hct>() <= 4 * size_of::<f64>() + size_of::<usize>());
+    assert!(Hct::from_int(0xff4285f4).space.is_none());
+    assert!(
+      Hct::from_in_gamut(30.0, 60.0, 50.0, Gamut::DisplayP3)
+        .space
+        .is_some()
+    );
+  }
+
+  #[test]
+  fn f32_matches_f64() {
+    for hue in (0..360).step_by(45) {
+      for tone in (10..=90).step_by(20) {
+        let (hue, tone) = (hue as f64, tone as f64);
+        let hct = Hct::from(hue, 48.0, tone);
+        let hct_f32 = Hct::<f32>::from(hue as f32, 48.0, tone as f32);
+        let (argb, argb_f32) = (hct.to_int(), hct_f32.to_int());
+        for shift in [0, 8, 16] {
+          let component = (argb >> shift & 0xff) as i32;
+          let component_f32 = (argb_f32 >> shift & 0xff) as i32;
+          assert!((component - component_f32).abs() <= 1);
+        }
+        let from_argb: Hct<f32> = argb.into();
+        assert!((from_argb.hue() as f64 - hct.hue()).abs() < 1e-2);
+        assert!((from_argb.tone() as f64 - hct.tone()).abs() < 1e-3);
+      }
+    }
+  }
+
+  #[test]
+  fn orders_nan_components() {
+    // Constructors panic on NaN, but deserialized colors aren't validated.
+    let nan = Hct {
+      space: HctSpace::new(Some([f64::NAN, 0.5, 0.5]), None),
+      ..Hct::from_rgb_in_gamut([0.25, 0.5, 0.5], Gamut::DisplayP3)
+    };
+    assert_eq!(nan, nan.clone());
+    let mut colors = [nan.clone(), Hct::from_int(0xff4285f4), nan.clone()];
+    colors.sort();
+    assert_eq!(
+      colors[0].cmp(&colors[1]),
+      colors[0].partial_cmp(&colors[1]).unwrap()
+    );
+    assert_eq!(colors.iter().filter(|&hct| *hct == nan).count(), 2);
+    assert!(!alloc::format!("{nan}").is_empty());
+  }
 }
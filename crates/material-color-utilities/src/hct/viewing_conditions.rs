@@ -1,9 +1,8 @@
-use std::f64;
-
+use num_traits::{Float, FloatConst, FromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{hct::XYZ_TO_CAM16RGB, utils};
+use crate::{chromatic_adaptation, hct::XYZ_TO_CAM16RGB, utils};
 
 /// In traditional color spaces, a color can be identified solely by the observer's measurement of
 /// the color. Color appearance models such as CAM16 also use information about the environment where
@@ -14,39 +13,63 @@ use crate::{hct::XYZ_TO_CAM16RGB, utils};
 ///
 /// This class caches intermediate values of the CAM16 conversion process that depend only on
 /// viewing conditions, enabling speed ups.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Besides the sRGB-like [`ViewingConditions::default`], there are presets for common environments
+/// such as [`ViewingConditions::dim_room`], and [`ViewingConditions::builder`] for anything else.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-pub struct ViewingConditions {
-  aw: f64,
-  nbb: f64,
-  ncb: f64,
-  c: f64,
-  nc: f64,
-  n: f64,
-  rgb_d: [f64; 3],
-  fl: f64,
-  fl_root: f64,
-  z: f64,
+pub struct ViewingConditions<T = f64> {
+  aw: T,
+  nbb: T,
+  ncb: T,
+  c: T,
+  nc: T,
+  n: T,
+  rgb_d: [T; 3],
+  fl: T,
+  fl_root: T,
+  z: T,
 }
 
 impl ViewingConditions {
+  /// Start from sRGB-like viewing conditions, and change only the parameters that differ.
+  ///
+  /// For other float types, and for adjusting presets, use [`ViewingConditionsBuilder`].
+  pub fn builder() -> ViewingConditionsBuilder {
+    ViewingConditionsBuilder::default()
+  }
+
+  /// A display viewed in a dimly lit room, see [`ViewingConditionsBuilder::dim_room`].
+  pub fn dim_room() -> Self {
+    ViewingConditionsBuilder::dim_room().build()
+  }
+
+  /// A display viewed in a dark room, see [`ViewingConditionsBuilder::dark_room`].
+  pub fn dark_room() -> Self {
+    ViewingConditionsBuilder::dark_room().build()
+  }
+
+  /// A display viewed outdoors in daylight, see [`ViewingConditionsBuilder::bright_outdoor`].
+  pub fn bright_outdoor() -> Self {
+    ViewingConditionsBuilder::bright_outdoor().build()
+  }
+
+  /// Prints viewed in a D50 viewing booth, see [`ViewingConditionsBuilder::d50_print_booth`].
+  pub fn d50_print_booth() -> Self {
+    ViewingConditionsBuilder::d50_print_booth().build()
+  }
+}
+
+impl<T> ViewingConditions<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
   /// Parameters are intermediate values of the CAM16 conversion process. Their names are shorthand
   /// for technical color science terminology, this class would not benefit from documenting them
   /// individually. A brief overview is available in the CAM16 specification, and a complete overview
   /// requires a color science textbook, such as Fairchild's Color Appearance Models.
   #[allow(clippy::too_many_arguments)]
-  fn new(
-    aw: f64,
-    nbb: f64,
-    ncb: f64,
-    c: f64,
-    nc: f64,
-    n: f64,
-    rgb_d: [f64; 3],
-    fl: f64,
-    fl_root: f64,
-    z: f64,
-  ) -> Self {
+  fn new(aw: T, nbb: T, ncb: T, c: T, nc: T, n: T, rgb_d: [T; 3], fl: T, fl_root: T, z: T) -> Self {
     Self {
       aw,
       nbb,
@@ -63,118 +86,380 @@ impl ViewingConditions {
 
   /// Create ViewingConditions from a simple, physically relevant, set of parameters.
   pub fn make(
-    white_point: [f64; 3],
-    adapting_luminance: f64,
-    mut background_lstar: f64,
-    surround: f64,
+    white_point: [T; 3],
+    adapting_luminance: T,
+    mut background_lstar: T,
+    surround: T,
     discounting_illuminant: bool,
   ) -> Self {
     // A background of pure black is non-physical and leads to infinities that represent the idea
     // that any color viewed in pure black can't be seen.
-    background_lstar = f64::max(0.1, background_lstar);
+    background_lstar = T::max(T::from_f64(0.1).unwrap(), background_lstar);
     // Transform white point XYZ to 'cone'/'rgb' responses
-    let matrix = XYZ_TO_CAM16RGB;
+    let matrix = utils::color::matrix_from_f64::<T>(XYZ_TO_CAM16RGB);
     let xyz = white_point;
     let r_w = (xyz[0] * matrix[0][0]) + (xyz[1] * matrix[0][1]) + (xyz[2] * matrix[0][2]);
     let g_w = (xyz[0] * matrix[1][0]) + (xyz[1] * matrix[1][1]) + (xyz[2] * matrix[1][2]);
     let b_w = (xyz[0] * matrix[2][0]) + (xyz[1] * matrix[2][1]) + (xyz[2] * matrix[2][2]);
-    let f = 0.8 + (surround / 10.0);
-    let c = if f >= 0.9 {
-      utils::math::lerp(0.59, 0.69, (f - 0.9) * 10.0)
+    let f = T::from_f64(0.8).unwrap() + (surround / T::from_f64(10.0).unwrap());
+    let c = if f >= T::from_f64(0.9).unwrap() {
+      utils::math::lerp(
+        T::from_f64(0.59).unwrap(),
+        T::from_f64(0.69).unwrap(),
+        (f - T::from_f64(0.9).unwrap()) * T::from_f64(10.0).unwrap(),
+      )
     } else {
-      utils::math::lerp(0.525, 0.59, (f - 0.8) * 10.0)
+      utils::math::lerp(
+        T::from_f64(0.525).unwrap(),
+        T::from_f64(0.59).unwrap(),
+        (f - T::from_f64(0.8).unwrap()) * T::from_f64(10.0).unwrap(),
+      )
     };
     let d = if discounting_illuminant {
-      1.0
+      T::one()
     } else {
-      f * (1.0 - ((1.0 / 3.6) * ((-adapting_luminance - 42.0) / 92.0).exp()))
+      f * (T::one()
+        - ((T::from_f64(1.0 / 3.6).unwrap())
+          * ((-adapting_luminance - T::from_f64(42.0).unwrap()) / T::from_f64(92.0).unwrap())
+            .exp()))
     }
-    .clamp(0.0, 1.0);
+    .clamp(T::zero(), T::one());
     let nc = f;
     let rgb_d = [
-      d * (100.0 / r_w) + 1.0 - d,
-      d * (100.0 / g_w) + 1.0 - d,
-      d * (100.0 / b_w) + 1.0 - d,
+      d * (T::from_f64(100.0).unwrap() / r_w) + T::one() - d,
+      d * (T::from_f64(100.0).unwrap() / g_w) + T::one() - d,
+      d * (T::from_f64(100.0).unwrap() / b_w) + T::one() - d,
     ];
-    let k = 1.0 / (5.0 * adapting_luminance + 1.0);
+    let k = T::one() / (T::from_f64(5.0).unwrap() * adapting_luminance + T::one());
     let k4 = k * k * k * k;
-    let k4_f = 1.0 - k4;
-    let fl = (k4 * adapting_luminance) + (0.1 * k4_f * k4_f * f64::cbrt(5.0 * adapting_luminance));
+    let k4_f = T::one() - k4;
+    let fl = (k4 * adapting_luminance)
+      + (T::from_f64(0.1).unwrap()
+        * k4_f
+        * k4_f
+        * T::cbrt(T::from_f64(5.0).unwrap() * adapting_luminance));
     let n = utils::color::y_from_lstar(background_lstar) / white_point[1];
-    let z = 1.48 + f64::sqrt(n);
-    let nbb = 0.725 / n.powf(0.2);
+    let z = T::from_f64(1.48).unwrap() + T::sqrt(n);
+    let nbb = T::from_f64(0.725).unwrap() / n.powf(T::from_f64(0.2).unwrap());
     let ncb = nbb;
     let rgb_a_factors = [
-      (fl * rgb_d[0] * r_w / 100.0).powf(0.42),
-      (fl * rgb_d[1] * g_w / 100.0).powf(0.42),
-      (fl * rgb_d[2] * b_w / 100.0).powf(0.42),
+      (fl * rgb_d[0] * r_w / T::from_f64(100.0).unwrap()).powf(T::from_f64(0.42).unwrap()),
+      (fl * rgb_d[1] * g_w / T::from_f64(100.0).unwrap()).powf(T::from_f64(0.42).unwrap()),
+      (fl * rgb_d[2] * b_w / T::from_f64(100.0).unwrap()).powf(T::from_f64(0.42).unwrap()),
     ];
 
     let rgb_a = [
-      (400.0 * rgb_a_factors[0]) / (rgb_a_factors[0] + 27.13),
-      (400.0 * rgb_a_factors[1]) / (rgb_a_factors[1] + 27.13),
-      (400.0 * rgb_a_factors[2]) / (rgb_a_factors[2] + 27.13),
+      (T::from_f64(400.0).unwrap() * rgb_a_factors[0])
+        / (rgb_a_factors[0] + T::from_f64(27.13).unwrap()),
+      (T::from_f64(400.0).unwrap() * rgb_a_factors[1])
+        / (rgb_a_factors[1] + T::from_f64(27.13).unwrap()),
+      (T::from_f64(400.0).unwrap() * rgb_a_factors[2])
+        / (rgb_a_factors[2] + T::from_f64(27.13).unwrap()),
     ];
 
-    let aw = ((2.0 * rgb_a[0]) + rgb_a[1] + (0.05 * rgb_a[2])) * nbb;
-    Self::new(aw, nbb, ncb, c, nc, n, rgb_d, fl, fl.powf(0.25), z)
+    let aw =
+      ((T::from_f64(2.0).unwrap() * rgb_a[0]) + rgb_a[1] + (T::from_f64(0.05).unwrap() * rgb_a[2]))
+        * nbb;
+    Self::new(
+      aw,
+      nbb,
+      ncb,
+      c,
+      nc,
+      n,
+      rgb_d,
+      fl,
+      fl.powf(T::from_f64(0.25).unwrap()),
+      z,
+    )
   }
 
   /// Create sRGB-like viewing conditions with a custom background lstar.
-  pub fn default_with_background_lstar(lstar: f64) -> Self {
+  pub fn default_with_background_lstar(lstar: T) -> Self {
     Self::make(
       utils::color::white_point_d65(),
-      200.0 / f64::consts::PI * utils::color::y_from_lstar(50.0) / 100.0,
+      T::from_f64(200.0).unwrap() / T::PI()
+        * utils::color::y_from_lstar(T::from_f64(50.0).unwrap())
+        / T::from_f64(100.0).unwrap(),
       lstar,
-      2.0,
+      T::from_f64(2.0).unwrap(),
       false,
     )
   }
 
-  pub fn aw(&self) -> f64 {
+  pub fn aw(&self) -> T {
     self.aw
   }
 
-  pub fn nbb(&self) -> f64 {
+  pub fn nbb(&self) -> T {
     self.nbb
   }
 
-  pub fn ncb(&self) -> f64 {
+  pub fn ncb(&self) -> T {
     self.ncb
   }
 
-  pub fn c(&self) -> f64 {
+  pub fn c(&self) -> T {
     self.c
   }
 
-  pub fn nc(&self) -> f64 {
+  pub fn nc(&self) -> T {
     self.nc
   }
 
-  pub fn n(&self) -> f64 {
+  pub fn n(&self) -> T {
     self.n
   }
 
-  pub fn rgb_d(&self) -> &[f64; 3] {
+  pub fn rgb_d(&self) -> &[T; 3] {
     &self.rgb_d
   }
 
-  pub fn fl(&self) -> f64 {
+  pub fn fl(&self) -> T {
     self.fl
   }
 
-  pub fn fl_root(&self) -> f64 {
+  pub fn fl_root(&self) -> T {
     self.fl_root
   }
 
-  pub fn z(&self) -> f64 {
+  pub fn z(&self) -> T {
     self.z
   }
 }
 
-impl Default for ViewingConditions {
+impl<T> ViewingConditions<T> {
+  /// Every parameter, in declaration order.
+  pub(crate) fn parameters(&self) -> [&T; 12] {
+    let [r_d, g_d, b_d] = &self.rgb_d;
+    [
+      &self.aw,
+      &self.nbb,
+      &self.ncb,
+      &self.c,
+      &self.nc,
+      &self.n,
+      r_d,
+      g_d,
+      b_d,
+      &self.fl,
+      &self.fl_root,
+      &self.z,
+    ]
+  }
+}
+
+impl<T> Default for ViewingConditions<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
   /// sRGB-like viewing conditions.
   fn default() -> Self {
-    Self::default_with_background_lstar(50.0)
+    Self::default_with_background_lstar(T::from_f64(50.0).unwrap())
+  }
+}
+
+/// Builds [`ViewingConditions`] from physically relevant parameters, any of which can be left at
+/// their sRGB-like defaults.
+///
+/// The presets describe common environments, and can be adjusted further, e.g. to set the
+/// background L* of a dark theme.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewingConditionsBuilder<T = f64> {
+  white_point: Option<[T; 3]>,
+  adapting_luminance: Option<T>,
+  background_lstar: Option<T>,
+  surround: Option<T>,
+  discounting_illuminant: Option<bool>,
+}
+
+impl<T> Default for ViewingConditionsBuilder<T> {
+  fn default() -> Self {
+    Self {
+      white_point: None,
+      adapting_luminance: None,
+      background_lstar: None,
+      surround: None,
+      discounting_illuminant: None,
+    }
+  }
+}
+
+impl ViewingConditionsBuilder {
+  /// Surround of a display viewed in an office or outdoors, brighter than or as bright as the
+  /// display.
+  pub const AVERAGE_SURROUND: f64 = 2.0;
+  /// Surround of a display viewed in a dimly lit room, darker than the display.
+  pub const DIM_SURROUND: f64 = 1.0;
+  /// Surround of a display viewed in a dark room, e.g. a projector in a cinema.
+  pub const DARK_SURROUND: f64 = 0.0;
+}
+
+impl<T> ViewingConditionsBuilder<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  /// A display viewed in a living room in the evening, lit at 64 lux, with a dim surround.
+  pub fn dim_room() -> Self {
+    Self::default()
+      .ambient_illuminance(T::from_f64(64.0).unwrap())
+      .surround(T::from_f64(ViewingConditionsBuilder::DIM_SURROUND).unwrap())
+  }
+
+  /// A display viewed in a room lit at only 4 lux, with a dark surround.
+  pub fn dark_room() -> Self {
+    Self::default()
+      .ambient_illuminance(T::from_f64(4.0).unwrap())
+      .surround(T::from_f64(ViewingConditionsBuilder::DARK_SURROUND).unwrap())
+  }
+
+  /// A display viewed outdoors in daylight at 10,000 lux, with an average surround.
+  pub fn bright_outdoor() -> Self {
+    Self::default()
+      .ambient_illuminance(T::from_f64(10_000.0).unwrap())
+      .surround(T::from_f64(ViewingConditionsBuilder::AVERAGE_SURROUND).unwrap())
+  }
+
+  /// Prints viewed under the D50 illuminant at 2,000 lux, as in the critical comparison
+  /// conditions of ISO 3664. The observer discounts the illuminant, as with any reflective
+  /// surface.
+  pub fn d50_print_booth() -> Self {
+    Self::default()
+      .white_point(utils::color::white_point_d50())
+      .ambient_illuminance(T::from_f64(2_000.0).unwrap())
+      .surround(T::from_f64(ViewingConditionsBuilder::AVERAGE_SURROUND).unwrap())
+      .discounting_illuminant(true)
+  }
+
+  /// The XYZ coordinates of the white point the observer is adapted to. Defaults to D65.
+  pub fn white_point(mut self, white_point: [T; 3]) -> Self {
+    self.white_point = Some(white_point);
+    self
+  }
+
+  /// Set the white point to that of a black body at a color temperature in Kelvin, e.g. for a
+  /// display with a "night light" setting. See [`chromatic_adaptation::white_point_from_cct`].
+  pub fn color_temperature(self, kelvin: T) -> Self {
+    self.white_point(chromatic_adaptation::white_point_from_cct(kelvin))
+  }
+
+  /// The luminance of the adapting field, in cd/m². Defaults to the luminance of a mid-gray
+  /// surface lit at 200 lux.
+  pub fn adapting_luminance(mut self, adapting_luminance: T) -> Self {
+    self.adapting_luminance = Some(adapting_luminance);
+    self
+  }
+
+  /// Set the adapting luminance from the illuminance of the environment, in lux, assuming the
+  /// adapting field is a mid-gray surface.
+  pub fn ambient_illuminance(self, lux: T) -> Self {
+    self.adapting_luminance(
+      lux / T::PI() * utils::color::y_from_lstar(T::from_f64(50.0).unwrap())
+        / T::from_f64(100.0).unwrap(),
+    )
+  }
+
+  /// The L* of the background of the colors. Defaults to 50.
+  pub fn background_lstar(mut self, background_lstar: T) -> Self {
+    self.background_lstar = Some(background_lstar);
+    self
+  }
+
+  /// How bright the surround is, from 0 for a dark surround to 2 for an average one. Defaults to
+  /// [`ViewingConditionsBuilder::AVERAGE_SURROUND`].
+  pub fn surround(mut self, surround: T) -> Self {
+    self.surround = Some(surround);
+    self
+  }
+
+  /// Whether the observer fully adapts to the white point, as with colors of reflective surfaces.
+  /// Defaults to `false`, for self-luminous displays.
+  pub fn discounting_illuminant(mut self, discounting_illuminant: bool) -> Self {
+    self.discounting_illuminant = Some(discounting_illuminant);
+    self
+  }
+
+  pub fn build(self) -> ViewingConditions<T> {
+    let adapting_luminance = self.adapting_luminance.unwrap_or_else(|| {
+      T::from_f64(200.0).unwrap() / T::PI() * utils::color::y_from_lstar(T::from_f64(50.0).unwrap())
+        / T::from_f64(100.0).unwrap()
+    });
+    ViewingConditions::make(
+      self
+        .white_point
+        .unwrap_or_else(utils::color::white_point_d65),
+      adapting_luminance,
+      self
+        .background_lstar
+        .unwrap_or_else(|| T::from_f64(50.0).unwrap()),
+      self
+        .surround
+        .unwrap_or_else(|| T::from_f64(ViewingConditionsBuilder::AVERAGE_SURROUND).unwrap()),
+      self.discounting_illuminant.unwrap_or(false),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hct::Cam16;
+
+  #[test]
+  fn builder_defaults_to_default() {
+    assert_eq!(
+      ViewingConditions::builder().build(),
+      ViewingConditions::default()
+    );
+    assert_eq!(
+      ViewingConditions::builder().background_lstar(20.0).build(),
+      ViewingConditions::default_with_background_lstar(20.0)
+    );
+    assert_eq!(
+      ViewingConditions::<f64>::builder()
+        .ambient_illuminance(200.0)
+        .build(),
+      ViewingConditions::default()
+    );
+  }
+
+  #[test]
+  fn presets_adapt_to_their_environment() {
+    let default: ViewingConditions = ViewingConditions::default();
+    let dim_room = ViewingConditions::dim_room();
+    let dark_room = ViewingConditions::dark_room();
+    let bright_outdoor = ViewingConditions::bright_outdoor();
+    assert!(dark_room.fl() < dim_room.fl());
+    assert!(dim_room.fl() < default.fl());
+    assert!(default.fl() < bright_outdoor.fl());
+    assert!(dark_room.c() < dim_room.c());
+    assert!(dim_room.c() < default.c());
+    assert_eq!(
+      dim_room,
+      ViewingConditions::builder()
+        .ambient_illuminance(64.0)
+        .surround(ViewingConditionsBuilder::DIM_SURROUND)
+        .build()
+    );
+    // Fully adapted to D50, so its white is neutral.
+    let d50_print_booth = ViewingConditions::d50_print_booth();
+    let white = Cam16::from_xyz_in_viewing_conditions(96.422, 100.0, 82.521, &d50_print_booth);
+    assert!(white.chroma() < 1.0);
+  }
+
+  #[test]
+  fn adapts_to_color_temperature() {
+    let night_light = ViewingConditionsBuilder::<f64>::default()
+      .color_temperature(3400.0)
+      .discounting_illuminant(true)
+      .build();
+    let [x, y, z] = chromatic_adaptation::white_point_from_cct(3400.0);
+    let white = Cam16::from_xyz_in_viewing_conditions(x, y, z, &night_light);
+    assert!(white.chroma() < 1.0);
+    // sRGB white looks blue to an observer adapted to a warm white.
+    let [x, y, z] = utils::color::white_point_d65();
+    let srgb_white = Cam16::from_xyz_in_viewing_conditions(x, y, z, &night_light);
+    assert!(srgb_white.chroma() > 10.0);
+    assert!((200.0..300.0).contains(&srgb_white.hue()));
   }
 }
@@ -7,14 +7,36 @@
 //! See the shared repository
 //! [deminearchiver/material-color-utilities](https://github.com/deminearchiver/material-color-utilities)
 //! for additional information.
+//!
+//! ## `no_std`
+//!
+//! With the default `std` feature disabled, the crate only needs `alloc`. The [`apca`],
+//! [`chromatic_adaptation`], [`color`], [`color_vision`], [`hct`], [`contrast`], [`difference`] and
+//! [`palettes`] modules, and the color math in [`utils`], are available, and are generic over `f32`
+//! and `f64`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+pub mod apca;
+#[cfg(feature = "std")]
 pub mod blend;
+pub mod chromatic_adaptation;
+pub mod color;
+pub mod color_vision;
 pub mod contrast;
+pub mod difference;
+#[cfg(feature = "std")]
 pub mod dislike_analyzer;
+#[cfg(feature = "std")]
 pub mod dynamiccolor;
 pub mod hct;
 pub mod palettes;
-// pub mod quantize;
+#[cfg(feature = "std")]
+pub mod quantize;
+#[cfg(feature = "std")]
 pub mod score;
+#[cfg(feature = "std")]
 pub mod temperature_cache;
 pub mod utils;
@@ -1,82 +1,227 @@
-use ordered_float::NotNan;
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+use core::{cell::RefCell, hash::Hash};
+
+use num_traits::{Float, FloatConst, FromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::{cell::RefCell, collections::HashMap, hash::Hash};
 
-use crate::hct::Hct;
+#[cfg(feature = "std")]
+use crate::hct::HctLut;
+use crate::{
+  color::Argb,
+  hct::{Gamut, GamutBoundary, Hct, ViewingConditions, total_cmp_components},
+};
 
 /// A convenience class for retrieving colors that are constant in hue and chroma, but vary in tone.
 ///
 /// TonalPalette is intended for use in a single thread due to its stateful caching.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-pub struct TonalPalette {
+pub struct TonalPalette<T = f64> {
+  #[cfg_attr(feature = "serde", serde(skip_serializing, default))]
+  cache: RefCell<BTreeMap<u8, u32>>,
   #[cfg_attr(feature = "serde", serde(skip_serializing, default))]
-  cache: RefCell<HashMap<u8, u32>>,
-  hue: NotNan<f64>,
-  chroma: NotNan<f64>,
-  key_color: Hct,
+  rgb_cache: RefCell<BTreeMap<u8, [T; 3]>>,
+  hue: T,
+  chroma: T,
+  /// Also holds the gamut and viewing conditions of the palette.
+  key_color: Hct<T>,
+  #[cfg(feature = "std")]
+  #[cfg_attr(feature = "serde", serde(skip))]
+  lut: Option<Arc<HctLut>>,
 }
 
 impl TonalPalette {
-  fn new(hue: f64, chroma: f64, key_color: Hct) -> Self {
+  /// Create tones using the HCT hue and chroma from a color.
+  pub fn from_int(argb: impl Into<Argb>) -> Self {
+    Self::from_hct(Hct::from_int(argb))
+  }
+
+  /// Look up tones in a shared [`HctLut`] instead of solving them, trading accuracy for speed.
+  ///
+  /// Only [`TonalPalette::tone`] uses the table. The hue and chroma tones are solved for are off
+  /// by at most [`HctLut::error_bound`].
+  #[cfg(feature = "std")]
+  pub fn with_lut(mut self, lut: Arc<HctLut>) -> Self {
+    self.cache.borrow_mut().clear();
+    self.lut = Some(lut);
+    self
+  }
+
+  #[cfg(feature = "std")]
+  pub fn lut(&self) -> Option<&Arc<HctLut>> {
+    self.lut.as_ref()
+  }
+}
+
+impl<T> TonalPalette<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
+  fn new(hue: T, chroma: T, key_color: Hct<T>) -> Self {
     Self {
       cache: Default::default(),
-      hue: hue.try_into().unwrap(),
-      chroma: chroma.try_into().unwrap(),
+      rgb_cache: Default::default(),
+      hue,
+      chroma,
       key_color,
+      #[cfg(feature = "std")]
+      lut: None,
     }
   }
 
-  /// Create tones using the HCT hue and chroma from a color.
-  pub fn from_int(argb: u32) -> Self {
-    Self::from_hct(Hct::from_int(argb))
-  }
-
-  /// Create tones using a HCT color.
-  pub fn from_hct(hct: Hct) -> Self {
+  /// Create tones using a HCT color, in the gamut and viewing conditions of the color.
+  pub fn from_hct(hct: Hct<T>) -> Self {
     Self::new(hct.hue(), hct.hue(), hct)
   }
 
   /// Create tones from a defined HCT hue and chroma.
-  pub fn from_hue_and_chroma(hue: f64, chroma: f64) -> Self {
-    let key_color = KeyColor::new(hue, chroma).create();
+  pub fn from_hue_and_chroma(hue: T, chroma: T) -> Self {
+    Self::from_hue_and_chroma_in_gamut(hue, chroma, Gamut::Srgb)
+  }
+
+  /// Create tones from a defined HCT hue and chroma, solved in a gamut.
+  ///
+  /// Wide gamuts have more chroma available, which [`TonalPalette::tone_rgb`] and
+  /// [`TonalPalette::hct`] keep. [`TonalPalette::tone`] still returns sRGB colors.
+  pub fn from_hue_and_chroma_in_gamut(hue: T, chroma: T, gamut: Gamut) -> Self {
+    Self::from_hue_and_chroma_in(hue, chroma, gamut, None)
+  }
+
+  /// Create tones from a defined HCT hue and chroma, measured in the given viewing conditions
+  /// instead of the default ones.
+  ///
+  /// Tones appear with the palette's hue and chroma to an observer in the viewing conditions, see
+  /// [`Hct::from_in_viewing_conditions`].
+  pub fn from_hue_and_chroma_in_viewing_conditions(
+    hue: T,
+    chroma: T,
+    viewing_conditions: &ViewingConditions<T>,
+  ) -> Self {
+    Self::from_hue_and_chroma_in(
+      hue,
+      chroma,
+      Gamut::Srgb,
+      Hct::non_default(viewing_conditions),
+    )
+  }
+
+  /// Measure the palette's hue and chroma in the given viewing conditions instead, keeping its
+  /// gamut. The key color is searched for again.
+  pub fn with_viewing_conditions(self, viewing_conditions: &ViewingConditions<T>) -> Self {
+    Self::from_hue_and_chroma_in(
+      self.hue(),
+      self.chroma(),
+      self.gamut(),
+      Hct::non_default(viewing_conditions),
+    )
+  }
+
+  fn from_hue_and_chroma_in(
+    hue: T,
+    chroma: T,
+    gamut: Gamut,
+    viewing_conditions: Option<&ViewingConditions<T>>,
+  ) -> Self {
+    let key_color = KeyColor::new(hue, chroma, gamut, viewing_conditions).create();
     Self::new(hue, chroma, key_color)
   }
 
   /// The hue of the Tonal Palette, in HCT. Ranges from 0 to 360.
-  pub fn hue(&self) -> f64 {
-    *self.hue
+  pub fn hue(&self) -> T {
+    self.hue
   }
 
   /// The chroma of the Tonal Palette, in HCT. Ranges from 0 to ~130 (for sRGB gamut).
-  pub fn chroma(&self) -> f64 {
-    *self.chroma
+  pub fn chroma(&self) -> T {
+    self.chroma
+  }
+
+  /// The gamut tones are solved in.
+  pub fn gamut(&self) -> Gamut {
+    self.key_color.gamut()
+  }
+
+  /// The viewing conditions the hue and chroma are measured in, or `None` for the default ones.
+  pub fn viewing_conditions(&self) -> Option<&ViewingConditions<T>> {
+    self.key_color.viewing_conditions()
   }
 
   /// The key color is the first tone, starting from T50, that matches the palette's chroma.
-  pub fn key_color(&self) -> &Hct {
+  pub fn key_color(&self) -> &Hct<T> {
     &self.key_color
   }
 
   /// Create an ARGB color with HCT hue and chroma of this Tones instance, and the provided HCT tone.
   pub fn tone(&self, tone: u8) -> u32 {
     if let Some(color) = self.cache.borrow().get(&tone) {
-      *color
+      return *color;
+    }
+    let color = if tone == 99 && Hct::is_yellow(self.hue()) {
+      Self::average_argb(self.tone(98), self.tone(100))
     } else {
-      let color = if tone == 99 && Hct::is_yellow(self.hue()) {
-        Self::average_argb(self.tone(98), self.tone(100))
-      } else {
-        Hct::from(self.hue(), self.chroma(), tone as f64).to_int()
-      };
-      self.cache.borrow_mut().insert(tone, color);
-      color
+      self.solve_tone(tone)
+    };
+    self.cache.borrow_mut().insert(tone, color);
+    color
+  }
+
+  #[cfg(feature = "std")]
+  fn solve_tone(&self, tone: u8) -> u32 {
+    match (&self.lut, self.viewing_conditions()) {
+      (Some(lut), None) => lut.solve_to_int(
+        self.hue().to_f64().unwrap(),
+        self.chroma().to_f64().unwrap(),
+        tone as f64,
+      ),
+      _ => self.solve_srgb_tone(tone),
+    }
+  }
+
+  #[cfg(not(feature = "std"))]
+  fn solve_tone(&self, tone: u8) -> u32 {
+    self.solve_srgb_tone(tone)
+  }
+
+  fn solve_srgb_tone(&self, tone: u8) -> u32 {
+    Hct::from_in(
+      self.hue(),
+      self.chroma(),
+      T::from_u8(tone).unwrap(),
+      Gamut::Srgb,
+      self.viewing_conditions(),
+    )
+    .to_int()
+  }
+
+  /// Create an RGB color in the palette's gamut with HCT hue and chroma of this Tones instance,
+  /// and the provided HCT tone. Components range from 0 to 1.
+  pub fn tone_rgb(&self, tone: u8) -> [T; 3] {
+    if let Some(rgb) = self.rgb_cache.borrow().get(&tone) {
+      return *rgb;
     }
+    let rgb = if tone == 99 && Hct::is_yellow(self.hue()) {
+      let [rgb1, rgb2] = [self.tone_rgb(98), self.tone_rgb(100)];
+      [0, 1, 2].map(|i| (rgb1[i] + rgb2[i]) / T::from_f64(2.0).unwrap())
+    } else {
+      self.hct(T::from_u8(tone).unwrap()).to_rgb()
+    };
+    self.rgb_cache.borrow_mut().insert(tone, rgb);
+    rgb
   }
 
-  /// Given a tone, use hue and chroma of palette to create a color, and return it as HCT.
-  pub fn hct(&self, tone: f64) -> Hct {
-    Hct::from(self.hue(), self.chroma(), tone)
+  /// Given a tone, use hue and chroma of palette to create a color in the palette's gamut, and
+  /// return it as HCT.
+  pub fn hct(&self, tone: T) -> Hct<T> {
+    Hct::from_in(
+      self.hue(),
+      self.chroma(),
+      tone,
+      self.gamut(),
+      self.viewing_conditions(),
+    )
   }
 
   fn average_argb(argb1: u32, argb2: u32) -> u32 {
@@ -86,52 +231,76 @@ impl TonalPalette {
     let red2 = (argb2 >> 16) & 0xff;
     let green2 = (argb2 >> 8) & 0xff;
     let blue2 = argb2 & 0xff;
-    let red = ((red1 + red2) as f64 / 2.0).round() as u32;
-    let green = ((green1 + green2) as f64 / 2.0).round() as u32;
-    let blue = ((blue1 + blue2) as f64 / 2.0).round() as u32;
+    // Rounds halves up.
+    let red = (red1 + red2).div_ceil(2);
+    let green = (green1 + green2).div_ceil(2);
+    let blue = (blue1 + blue2).div_ceil(2);
     255 << 24 | (red & 255) << 16 | (green & 255) << 8 | (blue & 255)
   }
 }
 
-impl Eq for TonalPalette {}
+/// Palettes are compared like [`Hct`], with NaN components equal to each other. The caches of tones
+/// aren't compared.
+impl<T: PartialOrd> PartialEq for TonalPalette<T> {
+  fn eq(&self, other: &Self) -> bool {
+    #[cfg(feature = "std")]
+    if self.lut != other.lut {
+      return false;
+    }
+    total_cmp_components([&self.hue, &self.chroma], [&other.hue, &other.chroma]).is_eq()
+      && self.key_color == other.key_color
+  }
+}
+
+impl<T: PartialOrd> Eq for TonalPalette<T> {}
 
-impl Hash for TonalPalette {
-  fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-    self.hue.hash(state);
-    self.chroma.hash(state);
+impl<T> Hash for TonalPalette<T> {
+  /// The hue, chroma and gamut are hashed through the key color.
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
     self.key_color.hash(state);
   }
 }
 
 /// Key color is a color that represents the hue and chroma of a tonal palette.
-struct KeyColor {
-  hue: f64,
-  requested_chroma: f64,
-  /// Cache that maps tone to max chroma to avoid duplicated HCT calculation.
-  chroma_cache: HashMap<u8, f64>,
+struct KeyColor<T> {
+  hue: T,
+  requested_chroma: T,
+  /// Caches max chroma to avoid duplicated HCT calculation. Also holds the gamut and viewing
+  /// conditions of the key color.
+  boundary: GamutBoundary<T>,
 }
 
-impl KeyColor {
-  const MAX_CHROMA_VALUE: f64 = 200.0;
-
+impl<T> KeyColor<T>
+where
+  T: Float + FloatConst + FromPrimitive,
+{
   /// Key color is a color that represents the hue and chroma of a tonal palette
-  pub fn new(hue: f64, requested_chroma: f64) -> Self {
+  pub fn new(
+    hue: T,
+    requested_chroma: T,
+    gamut: Gamut,
+    viewing_conditions: Option<&ViewingConditions<T>>,
+  ) -> Self {
+    let boundary = match viewing_conditions {
+      Some(vc) => GamutBoundary::from(gamut).with_viewing_conditions(vc.clone()),
+      None => GamutBoundary::from(gamut),
+    };
     Self {
       hue,
       requested_chroma,
-      chroma_cache: HashMap::default(),
+      boundary,
     }
   }
 
   /// Creates a key color from a [hue] and a [chroma]. The key color is the first tone, starting
   /// from T50, matching the given hue and chroma.
-  pub fn create(&mut self) -> Hct {
+  pub fn create(&mut self) -> Hct<T> {
     // Pivot around T50 because T50 has the most chroma available, on
     // average. Thus it is most likely to have a direct answer.
     let pivot_tone = 50;
     let tone_step_size = 1;
     // Epsilon to accept values slightly higher than the requested chroma.
-    let epsilon = 0.01;
+    let epsilon = T::from_f64(0.01).unwrap();
 
     // Binary search to find the tone that can provide a chroma that is closest
     // to the requested chroma.
@@ -149,7 +318,7 @@ impl KeyColor {
           upper_tone = mid_tone;
         } else {
           if lower_tone == mid_tone {
-            return Hct::from(self.hue, self.requested_chroma, lower_tone as f64);
+            return self.hct(lower_tone);
           }
           lower_tone = mid_tone;
         }
@@ -165,15 +334,24 @@ impl KeyColor {
       }
     }
 
-    Hct::from(self.hue, self.requested_chroma, lower_tone as f64)
+    self.hct(lower_tone)
+  }
+
+  fn hct(&self, tone: u8) -> Hct<T> {
+    Hct::from_in(
+      self.hue,
+      self.requested_chroma,
+      T::from_u8(tone).unwrap(),
+      self.boundary.gamut(),
+      self.boundary.viewing_conditions(),
+    )
   }
 
   /// Find the maximum chroma for a given tone
-  fn max_chroma(&mut self, tone: u8) -> f64 {
-    *self
-      .chroma_cache
-      .entry(tone)
-      .or_insert_with_key(|tone| Hct::from(self.hue, Self::MAX_CHROMA_VALUE, *tone as f64).chroma())
+  fn max_chroma(&mut self, tone: u8) -> T {
+    self
+      .boundary
+      .max_chroma(self.hue, T::from_u8(tone).unwrap())
   }
 }
 
@@ -246,4 +424,73 @@ mod tests {
     let tone_difference = (result.tone() - 50.0).abs();
     assert!(tone_difference < 0.5);
   }
+
+  #[test]
+  fn wide_gamut_palette() {
+    let srgb = TonalPalette::from_hue_and_chroma(140.0, 120.0);
+    let display_p3 = TonalPalette::from_hue_and_chroma_in_gamut(140.0, 120.0, Gamut::DisplayP3);
+    assert_eq!(display_p3.gamut(), Gamut::DisplayP3);
+    assert!(display_p3.key_color().chroma() > srgb.key_color().chroma() + 10.0);
+    assert_eq!(display_p3.tone(80), srgb.tone(80));
+    assert!(display_p3.hct(80.0).chroma() > srgb.hct(80.0).chroma());
+    assert_eq!(display_p3.tone_rgb(80), display_p3.hct(80.0).to_rgb());
+    assert!(
+      display_p3
+        .tone_rgb(100)
+        .iter()
+        .all(|&component| (component - 1.0).abs() < 1e-9)
+    );
+  }
+
+  #[test]
+  fn palette_in_viewing_conditions() {
+    let dim_room = ViewingConditions::dim_room();
+    let palette = TonalPalette::from_hue_and_chroma_in_viewing_conditions(270.0, 36.0, &dim_room);
+    assert_eq!(palette.viewing_conditions(), Some(&dim_room));
+    assert_eq!(palette.key_color().viewing_conditions(), Some(&dim_room));
+    let default = TonalPalette::from_hue_and_chroma(270.0, 36.0);
+    assert_ne!(palette.tone(40), default.tone(40));
+    for tone in [10, 40, 60] {
+      let hct = Hct::from_int_in_viewing_conditions(palette.tone(tone), &dim_room);
+      assert!((hct.hue() - 270.0).abs() < 2.0);
+      assert!((hct.chroma() - 36.0).abs() < 2.0);
+      assert!((hct.tone() - tone as f64).abs() < 0.5);
+    }
+    let again = default.with_viewing_conditions(&dim_room);
+    assert_eq!(again.tone(40), palette.tone(40));
+    // The default viewing conditions are the same as none.
+    let palette = TonalPalette::from_hue_and_chroma_in_viewing_conditions(
+      270.0,
+      36.0,
+      &ViewingConditions::default(),
+    );
+    assert_eq!(palette, TonalPalette::from_hue_and_chroma(270.0, 36.0));
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn tones_from_lut() {
+    let lut = Arc::new(HctLut::default());
+    let palette = TonalPalette::from_hue_and_chroma(270.0, 36.0);
+    let palette_with_lut = palette.clone().with_lut(lut.clone());
+    for tone in [0, 10, 40, 90, 99, 100] {
+      assert_eq!(palette_with_lut.tone(tone), palette.tone(tone));
+    }
+    assert!(!lut.is_empty());
+  }
+
+  #[test]
+  fn f32_palette() {
+    let palette = TonalPalette::from_hue_and_chroma(270.0, 36.0);
+    let palette_f32 = TonalPalette::<f32>::from_hue_and_chroma(270.0, 36.0);
+    assert!((palette_f32.key_color().tone() as f64 - palette.key_color().tone()).abs() < 0.5);
+    for tone in [0, 10, 40, 90, 100] {
+      let (argb, argb_f32) = (palette.tone(tone), palette_f32.tone(tone));
+      for shift in [0, 8, 16] {
+        let component = (argb >> shift & 0xff) as i32;
+        let component_f32 = (argb_f32 >> shift & 0xff) as i32;
+        assert!((component - component_f32).abs() <= 1);
+      }
+    }
+  }
 }
@@ -4,12 +4,18 @@ mod point_provider;
 mod quantizer;
 mod quantizer_celebi;
 mod quantizer_map;
+mod quantizer_median_cut;
+mod quantizer_octree;
 mod quantizer_wsmeans;
 mod quantizer_wu;
+mod weight_mask;
 
 pub use point_provider::*;
 pub use quantizer::*;
 pub use quantizer_celebi::*;
 pub use quantizer_map::*;
+pub use quantizer_median_cut::*;
+pub use quantizer_octree::*;
 pub use quantizer_wsmeans::*;
 pub use quantizer_wu::*;
+pub use weight_mask::*;
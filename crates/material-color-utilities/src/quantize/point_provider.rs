@@ -1,14 +1,25 @@
-use crate::utils;
+use crate::{
+  difference::{self, DeltaE},
+  hct::Cam16,
+  utils,
+};
 
 /// An interface to allow use of different color spaces by
 /// quantizers.
+///
+/// Quantizers only use [`PointProvider::distance`] to compare candidate clusters. Most providers
+/// here implement it with [`squared_euclidean`](difference::squared_euclidean), and
+/// [`PointProviderDeltaE`] with the square of a [`DeltaE`] formula. Quantizers don't report cluster
+/// distances; to measure how far apart the resulting colors are, pass them to [`DeltaE::between`].
 pub trait PointProvider {
   type Point;
 
   /// The four components in the color space of an sRGB color.
+  #[allow(clippy::wrong_self_convention)]
   fn from_int(&self, argb: u32) -> Self::Point;
 
   /// The ARGB (i.e. hex code) representation of this color.
+  #[allow(clippy::wrong_self_convention)]
   fn to_int(&self, point: Self::Point) -> u32;
 
   /// Squared distance between two colors. Distance is defined by scientific color spaces and
@@ -42,9 +53,195 @@ impl PointProvider for PointProviderLab {
   /// This relatively minor optimization is helpful because this method is
   /// called at least once for each pixel in an image.
   fn distance(&self, one: &Self::Point, two: &Self::Point) -> f64 {
-    let d_l = one[0] - two[0];
-    let d_a = one[1] - two[1];
-    let d_b = one[2] - two[2];
-    d_l * d_l + d_a * d_a + d_b * d_b
+    difference::squared_euclidean(one, two)
+  }
+}
+
+/// Provides conversions needed for K-Means quantization in CAM16-UCS, the
+/// uniform color space of the CAM16 color appearance model HCT is based on.
+///
+/// Points are the J*, a* and b* coordinates of the color in the default
+/// viewing conditions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PointProviderCam16;
+
+impl PointProvider for PointProviderCam16 {
+  type Point = [f64; 3];
+
+  /// Convert a color represented in ARGB to a 3-element array of CAM16-UCS
+  /// coordinates of the color.
+  fn from_int(&self, argb: u32) -> Self::Point {
+    let cam = Cam16::from_int(argb);
+    [cam.jstar(), cam.astar(), cam.bstar()]
+  }
+
+  /// Convert a 3-element array of CAM16-UCS coordinates to a color
+  /// represented in ARGB.
+  fn to_int(&self, point: Self::Point) -> u32 {
+    Cam16::from_ucs(point[0], point[1], point[2]).to_int()
+  }
+
+  /// Squared Euclidean distance in CAM16-UCS. Unlike [`Cam16::distance`],
+  /// the result is not remapped, which keeps the relative ordering while
+  /// being cheaper to compute.
+  fn distance(&self, one: &Self::Point, two: &Self::Point) -> f64 {
+    difference::squared_euclidean(one, two)
+  }
+}
+
+/// Provides conversions needed for K-Means quantization in Oklab.
+///
+/// Points are Oklab coordinates scaled by 100, so that lightness ranges from 0
+/// to 100 like L* does, and distances are on a scale comparable to the other
+/// point providers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PointProviderOklab;
+
+impl PointProviderOklab {
+  const SCALE: f64 = 100.0;
+}
+
+impl PointProvider for PointProviderOklab {
+  type Point = [f64; 3];
+
+  /// Convert a color represented in ARGB to a 3-element array of scaled Oklab
+  /// coordinates of the color.
+  fn from_int(&self, argb: u32) -> Self::Point {
+    utils::color::oklab_from_argb::<f64>(argb).map(|component| component * Self::SCALE)
+  }
+
+  /// Convert a 3-element array of scaled Oklab coordinates to a color
+  /// represented in ARGB.
+  fn to_int(&self, point: Self::Point) -> u32 {
+    let [l, a, b] = point.map(|component| component / Self::SCALE);
+    utils::color::argb_from_oklab(l, a, b)
+  }
+
+  /// Squared Euclidean distance in Oklab.
+  fn distance(&self, one: &Self::Point, two: &Self::Point) -> f64 {
+    difference::squared_euclidean(one, two)
+  }
+}
+
+/// Provides conversions needed for K-Means quantization, comparing colors with a [`DeltaE`]
+/// formula.
+///
+/// Points are in the color space of the formula: L*a*b* for CIE76, CIE94 and CIEDE2000, and the
+/// same spaces as [`PointProviderCam16`] and [`PointProviderOklab`] otherwise. Distances are the
+/// squared delta E, so CIE76, CAM16-UCS and Oklab cluster the same as the provider of their space.
+///
+/// CIE94 and CIEDE2000 aren't Euclidean distances, so clusters are still averaged in L*a*b*, and
+/// the triangle inequality [`QuantizerWsmeans`](crate::quantize::QuantizerWsmeans) skips
+/// comparisons with only holds approximately. They are also much slower to compute.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PointProviderDeltaE {
+  delta_e: DeltaE,
+}
+
+impl PointProviderDeltaE {
+  pub const fn new(delta_e: DeltaE) -> Self {
+    Self { delta_e }
+  }
+
+  pub fn delta_e(&self) -> DeltaE {
+    self.delta_e
+  }
+}
+
+impl PointProvider for PointProviderDeltaE {
+  type Point = [f64; 3];
+
+  /// Convert a color represented in ARGB to a 3-element array of coordinates in the color space of
+  /// the formula.
+  fn from_int(&self, argb: u32) -> Self::Point {
+    match self.delta_e {
+      DeltaE::Cie76 | DeltaE::Cie94 | DeltaE::Ciede2000 => PointProviderLab.from_int(argb),
+      DeltaE::Cam16Ucs => PointProviderCam16.from_int(argb),
+      DeltaE::Oklab => PointProviderOklab.from_int(argb),
+    }
+  }
+
+  /// Convert a 3-element array of coordinates in the color space of the formula to a color
+  /// represented in ARGB.
+  fn to_int(&self, point: Self::Point) -> u32 {
+    match self.delta_e {
+      DeltaE::Cie76 | DeltaE::Cie94 | DeltaE::Ciede2000 => PointProviderLab.to_int(point),
+      DeltaE::Cam16Ucs => PointProviderCam16.to_int(point),
+      DeltaE::Oklab => PointProviderOklab.to_int(point),
+    }
+  }
+
+  /// The squared delta E, which orders pairs of colors the same as the formula.
+  fn distance(&self, one: &Self::Point, two: &Self::Point) -> f64 {
+    match self.delta_e {
+      DeltaE::Cie76 | DeltaE::Cam16Ucs | DeltaE::Oklab => difference::squared_euclidean(one, two),
+      DeltaE::Cie94 => difference::cie94(one, two).powi(2),
+      DeltaE::Ciede2000 => difference::ciede2000(one, two).powi(2),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const COLORS: [u32; 6] = [
+    0xff000000, 0xffffffff, 0xffff0000, 0xff00ff00, 0xff0000ff, 0xff4285f4,
+  ];
+
+  fn assert_round_trips(point_provider: impl PointProvider) {
+    for argb in COLORS {
+      let point = point_provider.from_int(argb);
+      assert_eq!(point_provider.to_int(point), argb);
+    }
+  }
+
+  #[test]
+  fn lab_round_trips() {
+    assert_round_trips(PointProviderLab);
+  }
+
+  #[test]
+  fn cam16_round_trips() {
+    assert_round_trips(PointProviderCam16);
+  }
+
+  #[test]
+  fn oklab_round_trips() {
+    assert_round_trips(PointProviderOklab);
+  }
+
+  #[test]
+  fn oklab_white_and_black() {
+    let white = PointProviderOklab.from_int(0xffffffff);
+    assert!((white[0] - 100.0).abs() < 0.01);
+    assert!(white[1].abs() < 0.01 && white[2].abs() < 0.01);
+    let black = PointProviderOklab.from_int(0xff000000);
+    assert!(black[0].abs() < 0.01);
+  }
+
+  #[test]
+  fn delta_e_matches_difference() {
+    for delta_e in [
+      DeltaE::Cie76,
+      DeltaE::Cie94,
+      DeltaE::Ciede2000,
+      DeltaE::Cam16Ucs,
+      DeltaE::Oklab,
+    ] {
+      let point_provider = PointProviderDeltaE::new(delta_e);
+      assert_round_trips(point_provider);
+      for one in COLORS {
+        for two in COLORS {
+          let distance =
+            point_provider.distance(&point_provider.from_int(one), &point_provider.from_int(two));
+          let expected = delta_e.between(one, two);
+          assert!(
+            (distance.sqrt() - expected).abs() < 1e-9,
+            "{delta_e:?} {one:x} {two:x}"
+          );
+        }
+      }
+    }
   }
 }
@@ -1,11 +1,145 @@
 use std::collections::HashMap;
 
-pub trait Quantizer {
-  fn quantize(self, pixels: &[u32], max_colors: usize) -> QuantizerResult;
+use crate::quantize::QuantizerMap;
+
+pub trait Quantizer: Sized {
+  fn quantize(self, pixels: &[u32], max_colors: usize) -> QuantizerResult {
+    self.quantize_histogram(&QuantizerMap::histogram(pixels), max_colors)
+  }
+
+  /// Quantize an image given as a histogram, with keys of ARGB colors, and values of the number
+  /// of times that color appears in the image.
+  ///
+  /// Counts don't have to be pixel counts, e.g. they may be weighted by where in the image the
+  /// pixels are, see [`QuantizerMap::weighted_histogram`].
+  fn quantize_histogram(
+    self,
+    color_to_count: &HashMap<u32, usize>,
+    max_colors: usize,
+  ) -> QuantizerResult;
+
+  /// Quantize an image where each pixel counts according to its weight, e.g. to favor the subject
+  /// of a photo over its background.
+  ///
+  /// See [`QuantizerMap::weighted_histogram`] for how weights are counted.
+  fn quantize_weighted(
+    self,
+    pixels: &[u32],
+    weights: &[f32],
+    max_colors: usize,
+  ) -> QuantizerResult {
+    self.quantize_histogram(
+      &QuantizerMap::weighted_histogram(pixels, weights),
+      max_colors,
+    )
+  }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct QuantizerResult {
+  /// The colors of the clusters, with the number of input pixels in each cluster, or the sum of
+  /// their counts when quantizing a histogram.
   pub color_to_count: HashMap<u32, usize>,
-  // pub input_pixel_to_cluster_pixel: HashMap<u32, u32>,
+  /// Every distinct input pixel, with the color of the cluster it was assigned to.
+  ///
+  /// Empty for the result of [`crate::quantize::QuantizerWuStream::finish`], which doesn't keep
+  /// the pixels, see [`crate::quantize::QuantizerWuClusters`].
+  pub input_pixel_to_cluster_pixel: HashMap<u32, u32>,
+}
+
+impl QuantizerResult {
+  /// The color of the cluster an input pixel was assigned to, or `None` if the pixel wasn't part
+  /// of the quantized input.
+  pub fn cluster_pixel(&self, pixel: u32) -> Option<u32> {
+    self.input_pixel_to_cluster_pixel.get(&pixel).copied()
+  }
+
+  /// The colors of the clusters, sorted by the number of input pixels in each cluster, from most
+  /// to least dominant.
+  pub fn clusters_by_population(&self) -> Vec<u32> {
+    let mut clusters: Vec<(u32, usize)> = self
+      .color_to_count
+      .iter()
+      .map(|(&color, &count)| (color, count))
+      .collect();
+    clusters.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    clusters.into_iter().map(|(color, _)| color).collect()
+  }
+
+  /// Replace every pixel of an image with the color of its cluster.
+  ///
+  /// Pixels that weren't part of the quantized input, such as transparent pixels that were
+  /// filtered out beforehand, are left unchanged.
+  pub fn posterize(&self, pixels: &[u32]) -> Vec<u32> {
+    pixels
+      .iter()
+      .map(|&pixel| self.cluster_pixel(pixel).unwrap_or(pixel))
+      .collect()
+  }
+
+  /// A mask of an image, with `true` for each pixel assigned to the cluster with the given color.
+  pub fn cluster_mask(&self, pixels: &[u32], cluster: u32) -> Vec<bool> {
+    pixels
+      .iter()
+      .map(|&pixel| self.cluster_pixel(pixel) == Some(cluster))
+      .collect()
+  }
+
+  /// Replace the pixels of the clusters in `cluster_to_color` with their new color, e.g. to tint
+  /// the dominant clusters of an image with the roles of a scheme.
+  ///
+  /// Pixels of other clusters, and pixels that weren't part of the quantized input, are left
+  /// unchanged.
+  pub fn recolor(&self, pixels: &[u32], cluster_to_color: &HashMap<u32, u32>) -> Vec<u32> {
+    pixels
+      .iter()
+      .map(|&pixel| {
+        self
+          .cluster_pixel(pixel)
+          .and_then(|cluster| cluster_to_color.get(&cluster).copied())
+          .unwrap_or(pixel)
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RED: u32 = 0xffff0000;
+  const DARK_RED: u32 = 0xfffe0000;
+  const BLUE: u32 = 0xff0000ff;
+  const GREEN: u32 = 0xff00ff00;
+
+  fn result() -> QuantizerResult {
+    QuantizerResult {
+      color_to_count: HashMap::from([(RED, 3), (BLUE, 1)]),
+      input_pixel_to_cluster_pixel: HashMap::from([(RED, RED), (DARK_RED, RED), (BLUE, BLUE)]),
+    }
+  }
+
+  #[test]
+  fn clusters_by_population() {
+    assert_eq!(result().clusters_by_population(), [RED, BLUE]);
+  }
+
+  #[test]
+  fn posterize() {
+    let pixels = [DARK_RED, BLUE, 0x00000000];
+    assert_eq!(result().posterize(&pixels), [RED, BLUE, 0x00000000]);
+  }
+
+  #[test]
+  fn cluster_mask() {
+    let pixels = [RED, DARK_RED, BLUE];
+    assert_eq!(result().cluster_mask(&pixels, RED), [true, true, false]);
+  }
+
+  #[test]
+  fn recolor() {
+    let pixels = [RED, DARK_RED, BLUE];
+    let recolored = result().recolor(&pixels, &HashMap::from([(RED, GREEN)]));
+    assert_eq!(recolored, [GREEN, GREEN, BLUE]);
+  }
 }
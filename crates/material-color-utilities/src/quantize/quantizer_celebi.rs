@@ -1,4 +1,8 @@
-use crate::quantize::{Quantizer, QuantizerResult, QuantizerWsmeans, QuantizerWu};
+use std::collections::HashMap;
+
+use crate::quantize::{
+  PointProvider, PointProviderLab, Quantizer, QuantizerResult, QuantizerWsmeans, QuantizerWu,
+};
 
 /// An image quantizer that improves on the quality of a standard K-Means
 /// algorithm by setting the K-Means initial state to the output of a Wu
@@ -8,16 +12,105 @@ use crate::quantize::{Quantizer, QuantizerResult, QuantizerWsmeans, QuantizerWu}
 ///
 /// This algorithm was designed by M. Emre Celebi, and was found in their 2011
 /// paper, Improving the Performance of K-Means for Color Quantization.
-/// https://arxiv.org/abs/1101.0395
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct QuantizerCelebi;
+/// <https://arxiv.org/abs/1101.0395>
+///
+/// K-Means clustering happens in the color space of the [`PointProvider`],
+/// which is L*a*b* by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuantizerCelebi<P = PointProviderLab> {
+  point_provider: P,
+}
+
+impl QuantizerCelebi {
+  pub const fn new() -> Self {
+    Self::with_point_provider(PointProviderLab)
+  }
+}
+
+impl Default for QuantizerCelebi {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<P> QuantizerCelebi<P> {
+  /// Create a quantizer clustering colors in the color space of `point_provider`.
+  pub const fn with_point_provider(point_provider: P) -> Self {
+    Self { point_provider }
+  }
+}
 
-impl Quantizer for QuantizerCelebi {
-  fn quantize(self, pixels: &[u32], max_colors: usize) -> QuantizerResult {
+impl<P> Quantizer for QuantizerCelebi<P>
+where
+  P: PointProvider<Point = [f64; 3]> + Sync,
+{
+  fn quantize_histogram(
+    self,
+    color_to_count: &HashMap<u32, usize>,
+    max_colors: usize,
+  ) -> QuantizerResult {
     let wu = QuantizerWu::default();
-    let wu_result = wu.quantize(pixels, max_colors);
-    let wu_clusters: Vec<_> = wu_result.color_to_count.into_keys().collect();
-    let wsmeans = QuantizerWsmeans::default();
-    wsmeans.quantize_with_starting_clusters(pixels, max_colors, &wu_clusters)
+    let wu_result = wu.quantize_histogram(color_to_count, max_colors);
+    let mut wu_clusters: Vec<_> = wu_result.color_to_count.into_keys().collect();
+    // Keeps the starting clusters in a stable order, so results are consistent between runs.
+    wu_clusters.sort_unstable();
+    let wsmeans = QuantizerWsmeans::with_point_provider(self.point_provider);
+    wsmeans.quantize_histogram_with_starting_clusters(color_to_count, max_colors, &wu_clusters)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::quantize::{PointProviderCam16, PointProviderOklab};
+
+  #[test]
+  fn quantizes_gradient() {
+    let pixels: Vec<u32> = (0..=255)
+      .map(|i| crate::utils::color::argb_from_rgb(i, 255 - i, 128))
+      .collect();
+    let result = QuantizerCelebi::default().quantize(&pixels, 8);
+    assert!(!result.color_to_count.is_empty());
+    assert!(result.color_to_count.len() <= 8);
+    assert_eq!(result.color_to_count.values().sum::<usize>(), pixels.len());
+    assert_eq!(result.input_pixel_to_cluster_pixel.len(), pixels.len());
+    assert!(
+      result
+        .posterize(&pixels)
+        .iter()
+        .all(|color| result.color_to_count.contains_key(color))
+    );
+  }
+
+  #[test]
+  fn is_deterministic() {
+    let pixels: Vec<u32> = (0..1024u32)
+      .map(|i| 0xff000000 | (i.wrapping_mul(2654435761) & 0x00ffffff))
+      .collect();
+    let first = QuantizerCelebi::default().quantize(&pixels, 16);
+    let second = QuantizerCelebi::default().quantize(&pixels, 16);
+    assert_eq!(first, second);
+  }
+
+  #[test]
+  fn quantizes_in_other_spaces() {
+    let pixels: Vec<u32> = (0..=255)
+      .map(|i| crate::utils::color::argb_from_rgb(0, i, 255 - i))
+      .collect();
+    let cam16 = QuantizerCelebi::with_point_provider(PointProviderCam16).quantize(&pixels, 8);
+    let oklab = QuantizerCelebi::with_point_provider(PointProviderOklab).quantize(&pixels, 8);
+    for result in [cam16, oklab] {
+      assert!(result.color_to_count.len() <= 8);
+      assert_eq!(result.color_to_count.values().sum::<usize>(), pixels.len());
+    }
+  }
+
+  #[test]
+  fn weights_populations() {
+    let pixels = [0xffff0000, 0xffff0000, 0xffff0000, 0xff0000ff];
+    let weights = [0.25, 0.25, 0.25, 1.0];
+    let result = QuantizerCelebi::default().quantize_weighted(&pixels, &weights, 128);
+    assert_eq!(result.color_to_count[&0xffff0000], 192);
+    assert_eq!(result.color_to_count[&0xff0000ff], 256);
   }
 }
@@ -1,20 +1,247 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 use crate::quantize::{Quantizer, QuantizerResult};
 
 /// Quantizes an image into a map, with keys of ARGB colors, and values of the
 /// number of times that color appears in the image.
+///
+/// Every color is its own cluster, so each input pixel maps to itself.
+///
+/// To quantize pixels as they are decoded, instead of all at once, see
+/// [`QuantizerMapStream`].
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct QuantizerMap;
 
+impl QuantizerMap {
+  /// The count of a pixel with a weight of 1 in a weighted histogram.
+  pub const WEIGHT_RESOLUTION: f32 = 256.0;
+  /// The largest weight a pixel can have in a weighted histogram. Larger weights count as this.
+  pub const MAX_WEIGHT: f32 = 1.0;
+
+  /// The number of pixels counted by each task when the `parallel` feature is enabled.
+  #[cfg(feature = "parallel")]
+  const CHUNK_SIZE: usize = 1 << 16;
+
+  fn count_pixels(color_to_count: &mut HashMap<u32, usize>, pixels: &[u32]) {
+    for &pixel in pixels.iter() {
+      *color_to_count.entry(pixel).or_insert(0) += 1;
+    }
+  }
+
+  fn merge(a: HashMap<u32, usize>, b: HashMap<u32, usize>) -> HashMap<u32, usize> {
+    let (mut larger, smaller) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    for (pixel, count) in smaller {
+      let total = larger.entry(pixel).or_insert(0);
+      *total = total.saturating_add(count);
+    }
+    larger
+  }
+
+  fn count_weighted_pixels(
+    color_to_count: &mut HashMap<u32, usize>,
+    pixels: &[u32],
+    weights: &[f32],
+  ) {
+    for (&pixel, &weight) in pixels.iter().zip(weights) {
+      let count = Self::count_from_weight(weight);
+      if count > 0 {
+        let total = color_to_count.entry(pixel).or_insert(0);
+        *total = total.saturating_add(count);
+      }
+    }
+  }
+
+  pub(crate) fn count_from_weight(weight: f32) -> usize {
+    // NaN and negative weights saturate to 0.
+    (weight.min(Self::MAX_WEIGHT) * Self::WEIGHT_RESOLUTION).round() as usize
+  }
+
+  /// The number of times each color appears in the pixels, given as ARGB integers.
+  pub fn histogram(pixels: &[u32]) -> HashMap<u32, usize> {
+    // Counts are integers, so the histogram is the same regardless of how pixels are split
+    // between tasks.
+    #[cfg(feature = "parallel")]
+    let color_to_count = pixels
+      .par_chunks(Self::CHUNK_SIZE)
+      .fold(HashMap::new, |mut color_to_count, chunk| {
+        Self::count_pixels(&mut color_to_count, chunk);
+        color_to_count
+      })
+      .reduce(HashMap::new, Self::merge);
+    #[cfg(not(feature = "parallel"))]
+    let color_to_count = {
+      let mut color_to_count = HashMap::new();
+      Self::count_pixels(&mut color_to_count, pixels);
+      color_to_count
+    };
+    color_to_count
+  }
+
+  /// The weighted number of times each color appears in the pixels, given as ARGB integers.
+  ///
+  /// Weights range from 0 to [`QuantizerMap::MAX_WEIGHT`], and larger weights, including infinite
+  /// ones, count as the maximum. Each pixel counts [`QuantizerMap::WEIGHT_RESOLUTION`] times its
+  /// weight, rounded to the nearest integer, so pixels with a weight of 1 all count the same.
+  /// Pixels whose weight rounds to 0, including negative and NaN weights, are left out of the
+  /// histogram.
+  ///
+  /// # Panics
+  ///
+  /// Panics if there isn't exactly one weight per pixel.
+  pub fn weighted_histogram(pixels: &[u32], weights: &[f32]) -> HashMap<u32, usize> {
+    assert_eq!(pixels.len(), weights.len(), "expected one weight per pixel");
+    #[cfg(feature = "parallel")]
+    let color_to_count = pixels
+      .par_chunks(Self::CHUNK_SIZE)
+      .zip(weights.par_chunks(Self::CHUNK_SIZE))
+      .fold(HashMap::new, |mut color_to_count, (pixels, weights)| {
+        Self::count_weighted_pixels(&mut color_to_count, pixels, weights);
+        color_to_count
+      })
+      .reduce(HashMap::new, Self::merge);
+    #[cfg(not(feature = "parallel"))]
+    let color_to_count = {
+      let mut color_to_count = HashMap::new();
+      Self::count_weighted_pixels(&mut color_to_count, pixels, weights);
+      color_to_count
+    };
+    color_to_count
+  }
+
+  fn result_from_histogram(color_to_count: HashMap<u32, usize>) -> QuantizerResult {
+    let input_pixel_to_cluster_pixel = color_to_count.keys().map(|&pixel| (pixel, pixel)).collect();
+    QuantizerResult {
+      color_to_count,
+      input_pixel_to_cluster_pixel,
+    }
+  }
+}
+
 impl Quantizer for QuantizerMap {
   fn quantize(self, pixels: &[u32], _max_colors: usize) -> QuantizerResult {
-    let mut result = QuantizerResult::default();
-    for &pixel in pixels.iter() {
-      result
-        .color_to_count
-        .entry(pixel)
-        .and_modify(|count| *count += 1)
-        .or_insert(1);
+    Self::result_from_histogram(Self::histogram(pixels))
+  }
+
+  fn quantize_histogram(
+    self,
+    color_to_count: &HashMap<u32, usize>,
+    _max_colors: usize,
+  ) -> QuantizerResult {
+    Self::result_from_histogram(color_to_count.clone())
+  }
+}
+
+/// Incrementally quantizes an image into a map, like [`QuantizerMap`], from pixels fed in chunks
+/// or from an iterator.
+///
+/// Only the histogram of the pixels is kept, so memory use is bounded by the number of distinct
+/// colors rather than by the size of the image.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct QuantizerMapStream {
+  color_to_count: HashMap<u32, usize>,
+}
+
+impl QuantizerMapStream {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a chunk of pixels, given as ARGB integers, e.g. a row or a tile of an image.
+  pub fn add_pixels(&mut self, pixels: &[u32]) {
+    let color_to_count = std::mem::take(&mut self.color_to_count);
+    self.color_to_count = QuantizerMap::merge(color_to_count, QuantizerMap::histogram(pixels));
+  }
+
+  /// Add a chunk of pixels, given as ARGB integers, each counting according to its weight.
+  ///
+  /// See [`QuantizerMap::weighted_histogram`] for how weights are counted.
+  pub fn add_weighted_pixels(&mut self, pixels: &[u32], weights: &[f32]) {
+    let color_to_count = std::mem::take(&mut self.color_to_count);
+    self.color_to_count = QuantizerMap::merge(
+      color_to_count,
+      QuantizerMap::weighted_histogram(pixels, weights),
+    );
+  }
+
+  /// Add a single pixel, given as an ARGB integer.
+  pub fn add_pixel(&mut self, pixel: u32) {
+    *self.color_to_count.entry(pixel).or_insert(0) += 1;
+  }
+
+  /// The number of times each color appeared in the pixels added so far.
+  pub fn color_to_count(&self) -> &HashMap<u32, usize> {
+    &self.color_to_count
+  }
+
+  pub fn finish(self) -> QuantizerResult {
+    QuantizerMap::result_from_histogram(self.color_to_count)
+  }
+}
+
+impl Extend<u32> for QuantizerMapStream {
+  fn extend<I: IntoIterator<Item = u32>>(&mut self, pixels: I) {
+    for pixel in pixels {
+      self.add_pixel(pixel);
     }
-    result
+  }
+}
+
+impl FromIterator<u32> for QuantizerMapStream {
+  fn from_iter<I: IntoIterator<Item = u32>>(pixels: I) -> Self {
+    let mut stream = Self::new();
+    stream.extend(pixels);
+    stream
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn counts_pixels() {
+    let pixels: Vec<u32> = (0..300_000u32).map(|i| 0xff000000 | (i % 7)).collect();
+    let result = QuantizerMap.quantize(&pixels, 0);
+    assert_eq!(result.color_to_count.len(), 7);
+    assert_eq!(result.color_to_count[&0xff000000], 42_858);
+    assert_eq!(result.color_to_count[&0xff000006], 42_857);
+    assert_eq!(result.input_pixel_to_cluster_pixel[&0xff000006], 0xff000006);
+  }
+
+  #[test]
+  fn stream_matches_quantize() {
+    let pixels: Vec<u32> = (0..10_000u32).map(|i| 0xff000000 | (i % 13)).collect();
+    let mut stream = QuantizerMapStream::new();
+    for chunk in pixels.chunks(999) {
+      stream.add_pixels(chunk);
+    }
+    let expected = QuantizerMap.quantize(&pixels, 0);
+    assert_eq!(stream.finish(), expected);
+    let collected: QuantizerMapStream = pixels.iter().copied().collect();
+    assert_eq!(collected.finish(), expected);
+  }
+
+  #[test]
+  fn weighted_histogram() {
+    let pixels = [0xffff0000, 0xffff0000, 0xff00ff00, 0xff0000ff];
+    let weights = [1.0, 0.5, 0.25, 0.0];
+    let color_to_count = QuantizerMap::weighted_histogram(&pixels, &weights);
+    assert_eq!(color_to_count.len(), 2);
+    assert_eq!(color_to_count[&0xffff0000], 384);
+    assert_eq!(color_to_count[&0xff00ff00], 64);
+  }
+
+  #[test]
+  fn weighted_histogram_clamps_large_weights() {
+    let pixels = [0xffff0000, 0xffff0000, 0xff00ff00, 0xff0000ff];
+    let weights = [f32::INFINITY, 1.0, f32::MAX, 1e30];
+    let color_to_count = QuantizerMap::weighted_histogram(&pixels, &weights);
+    assert_eq!(color_to_count.len(), 3);
+    assert_eq!(color_to_count[&0xffff0000], 512);
+    assert_eq!(color_to_count[&0xff00ff00], 256);
+    assert_eq!(color_to_count[&0xff0000ff], 256);
   }
 }
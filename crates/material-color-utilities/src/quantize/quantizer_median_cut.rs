@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use crate::{
+  quantize::{Quantizer, QuantizerResult},
+  utils,
+};
+
+/// An image quantizer that divides the image's pixels into clusters by
+/// recursively splitting boxes in RGB space at the median of their widest
+/// channel.
+///
+/// The algorithm was described by Paul Heckbert in Color Image Quantization
+/// for Frame Buffer Display, published in 1982.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuantizerMedianCut;
+
+impl QuantizerMedianCut {
+  fn channels(color: u32) -> [u8; 3] {
+    [
+      utils::color::red_from_argb(color),
+      utils::color::green_from_argb(color),
+      utils::color::blue_from_argb(color),
+    ]
+  }
+
+  /// Splits the box in two at the weighted median of its widest channel, returning the upper half.
+  fn split(entries: &mut [(u32, usize)], vbox: &mut ColorBox) -> ColorBox {
+    let slice = &mut entries[vbox.start..vbox.end];
+    let mut min = [u8::MAX; 3];
+    let mut max = [u8::MIN; 3];
+    for &(color, _) in slice.iter() {
+      for (channel, value) in Self::channels(color).into_iter().enumerate() {
+        min[channel] = min[channel].min(value);
+        max[channel] = max[channel].max(value);
+      }
+    }
+    let channel = (0..3).max_by_key(|&i| max[i] - min[i]).unwrap();
+    slice.sort_unstable_by_key(|&(color, _)| (Self::channels(color)[channel], color));
+
+    let half = vbox.population.div_ceil(2);
+    let mut lower_population = 0;
+    let mut split = 0;
+    while split < slice.len() - 1 {
+      lower_population += slice[split].1;
+      split += 1;
+      if lower_population >= half {
+        break;
+      }
+    }
+
+    let upper = ColorBox {
+      start: vbox.start + split,
+      end: vbox.end,
+      population: vbox.population - lower_population,
+    };
+    vbox.end = upper.start;
+    vbox.population = lower_population;
+    upper
+  }
+}
+
+impl Quantizer for QuantizerMedianCut {
+  fn quantize_histogram(
+    self,
+    color_to_count: &HashMap<u32, usize>,
+    max_colors: usize,
+  ) -> QuantizerResult {
+    let mut result = QuantizerResult::default();
+    if max_colors == 0 {
+      return result;
+    }
+    let mut entries: Vec<(u32, usize)> = color_to_count
+      .iter()
+      .filter(|&(_, &count)| count > 0)
+      .map(|(&color, &count)| (color, count))
+      .collect();
+    if entries.is_empty() {
+      return result;
+    }
+    // Keeps the entries in a stable order, so results are consistent between runs.
+    entries.sort_unstable();
+
+    let mut boxes = vec![ColorBox {
+      start: 0,
+      end: entries.len(),
+      population: entries.iter().map(|&(_, count)| count).sum(),
+    }];
+    while boxes.len() < max_colors {
+      let Some(next) = boxes
+        .iter()
+        .enumerate()
+        .filter(|(_, vbox)| vbox.end - vbox.start > 1)
+        .max_by_key(|&(i, vbox)| (vbox.population, std::cmp::Reverse(i)))
+        .map(|(i, _)| i)
+      else {
+        break;
+      };
+      let upper = Self::split(&mut entries, &mut boxes[next]);
+      boxes.push(upper);
+    }
+
+    for vbox in boxes {
+      let slice = &entries[vbox.start..vbox.end];
+      let mut sums = [0usize; 3];
+      for &(color, count) in slice {
+        for (sum, value) in sums.iter_mut().zip(Self::channels(color)) {
+          *sum += value as usize * count;
+        }
+      }
+      let [r, g, b] = sums.map(|sum| (sum / vbox.population) as u8);
+      let cluster = utils::color::argb_from_rgb(r, g, b);
+      *result.color_to_count.entry(cluster).or_insert(0) += vbox.population;
+      for &(color, _) in slice {
+        result.input_pixel_to_cluster_pixel.insert(color, cluster);
+      }
+    }
+    result
+  }
+}
+
+/// A range of histogram entries, with the number of pixels they account for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct ColorBox {
+  start: usize,
+  end: usize,
+  population: usize,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RED: u32 = 0xffff0000;
+  const GREEN: u32 = 0xff00ff00;
+  const BLUE: u32 = 0xff0000ff;
+  const MAX_COLORS: usize = 256;
+
+  #[test]
+  fn empty_input() {
+    let result = QuantizerMedianCut.quantize(&[], MAX_COLORS);
+    assert!(result.color_to_count.is_empty());
+  }
+
+  #[test]
+  fn two_red_three_green() {
+    let result = QuantizerMedianCut.quantize(&[RED, RED, GREEN, GREEN, GREEN], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 2);
+    assert_eq!(result.color_to_count[&RED], 2);
+    assert_eq!(result.color_to_count[&GREEN], 3);
+    assert_eq!(result.input_pixel_to_cluster_pixel[&GREEN], GREEN);
+  }
+
+  #[test]
+  fn one_red_one_green_one_blue() {
+    let result = QuantizerMedianCut.quantize(&[RED, GREEN, BLUE], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 3);
+    assert_eq!(result.color_to_count[&RED], 1);
+    assert_eq!(result.color_to_count[&GREEN], 1);
+    assert_eq!(result.color_to_count[&BLUE], 1);
+  }
+
+  #[test]
+  fn respects_max_colors() {
+    let pixels: Vec<u32> = (0..4096)
+      .map(|i| utils::color::argb_from_rgb((i * 7) as u8, (i * 13) as u8, (i * 31) as u8))
+      .collect();
+    let result = QuantizerMedianCut.quantize(&pixels, 16);
+    assert!(result.color_to_count.len() <= 16);
+    assert_eq!(result.color_to_count.values().sum::<usize>(), pixels.len());
+    assert!(
+      pixels
+        .iter()
+        .all(|pixel| result.input_pixel_to_cluster_pixel.contains_key(pixel))
+    );
+  }
+
+  #[test]
+  fn ignores_zero_counts() {
+    let color_to_count = HashMap::from([(BLUE, 5), (RED, 0)]);
+    let result = QuantizerMedianCut.quantize_histogram(&color_to_count, 4);
+    assert_eq!(result.color_to_count, HashMap::from([(BLUE, 5)]));
+    assert_eq!(
+      result.input_pixel_to_cluster_pixel,
+      HashMap::from([(BLUE, BLUE)])
+    );
+
+    let color_to_count = HashMap::from([(BLUE, 0)]);
+    let result = QuantizerMedianCut.quantize_histogram(&color_to_count, 4);
+    assert!(result.color_to_count.is_empty());
+  }
+}
@@ -0,0 +1,240 @@
+use std::{
+  cmp::Reverse,
+  collections::{BinaryHeap, HashMap},
+};
+
+use crate::{
+  quantize::{Quantizer, QuantizerResult},
+  utils,
+};
+
+const MAX_DEPTH: usize = 8;
+
+/// An image quantizer that sorts the image's colors into an octree, where
+/// each level splits RGB space in eight by the next bit of each channel, and
+/// then merges the least populated leaves until few enough remain.
+///
+/// It needs a single pass over the image's colors, and works well on large
+/// images, at the cost of cluster quality.
+///
+/// The algorithm was described by Michael Gervautz and Werner Purgathofer in
+/// A Simple Method for Color Quantization: Octree Quantization, published in
+/// 1988.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuantizerOctree;
+
+impl QuantizerOctree {
+  fn child_index(color: u32, level: usize) -> usize {
+    let shift = MAX_DEPTH - 1 - level;
+    let r = (utils::color::red_from_argb(color) >> shift) & 1;
+    let g = (utils::color::green_from_argb(color) >> shift) & 1;
+    let b = (utils::color::blue_from_argb(color) >> shift) & 1;
+    ((r << 2) | (g << 1) | b) as usize
+  }
+}
+
+impl Quantizer for QuantizerOctree {
+  fn quantize_histogram(
+    self,
+    color_to_count: &HashMap<u32, usize>,
+    max_colors: usize,
+  ) -> QuantizerResult {
+    let mut result = QuantizerResult::default();
+    if max_colors == 0 {
+      return result;
+    }
+    let mut entries: Vec<(u32, usize)> = color_to_count
+      .iter()
+      .filter(|&(_, &count)| count > 0)
+      .map(|(&color, &count)| (color, count))
+      .collect();
+    if entries.is_empty() {
+      return result;
+    }
+    // Keeps the entries in a stable order, so results are consistent between runs.
+    entries.sort_unstable();
+
+    let mut octree = Octree::default();
+    for &(color, count) in entries.iter() {
+      octree.insert(color, count);
+    }
+    octree.reduce(max_colors);
+
+    for (color, count) in entries {
+      let leaf = &octree.nodes[octree.leaf(color)];
+      let [r, g, b] = leaf.sums.map(|sum| (sum / leaf.population) as u8);
+      let cluster = utils::color::argb_from_rgb(r, g, b);
+      result.input_pixel_to_cluster_pixel.insert(color, cluster);
+      *result.color_to_count.entry(cluster).or_insert(0) += count;
+    }
+    result
+  }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Octree {
+  nodes: Vec<OctreeNode>,
+  /// The nodes that have children, by level.
+  reducible: [Vec<usize>; MAX_DEPTH],
+  leaf_count: usize,
+}
+
+impl Octree {
+  fn insert(&mut self, color: u32, count: usize) {
+    if self.nodes.is_empty() {
+      self.nodes.push(OctreeNode::default());
+    }
+    let channels = [
+      utils::color::red_from_argb(color),
+      utils::color::green_from_argb(color),
+      utils::color::blue_from_argb(color),
+    ];
+    let mut index = 0;
+    for level in 0..=MAX_DEPTH {
+      let node = &mut self.nodes[index];
+      node.population += count;
+      for (sum, value) in node.sums.iter_mut().zip(channels) {
+        *sum += value as usize * count;
+      }
+      if level == MAX_DEPTH {
+        if !node.is_leaf {
+          node.is_leaf = true;
+          self.leaf_count += 1;
+        }
+        break;
+      }
+
+      let child_index = QuantizerOctree::child_index(color, level);
+      index = match node.children[child_index] {
+        Some(child) => child,
+        None => {
+          if node.children.iter().all(Option::is_none) {
+            self.reducible[level].push(index);
+          }
+          let child = self.nodes.len();
+          self.nodes[index].children[child_index] = Some(child);
+          self.nodes.push(OctreeNode::default());
+          child
+        }
+      };
+    }
+  }
+
+  /// Merges the least populated nodes of the deepest level into leaves, until at most
+  /// `max_colors` leaves remain.
+  fn reduce(&mut self, max_colors: usize) {
+    // Populations are final once every color is inserted, and merging a node's children leaves
+    // the populations of the other nodes unchanged, so each level can be ordered once up front.
+    let mut reducible = core::mem::take(&mut self.reducible).map(|level| {
+      level
+        .into_iter()
+        .map(|index| Reverse((self.nodes[index].population, index)))
+        .collect::<BinaryHeap<_>>()
+    });
+    while self.leaf_count > max_colors {
+      let Some(Reverse((_, index))) = reducible.iter_mut().rev().find_map(BinaryHeap::pop) else {
+        break;
+      };
+
+      let node = &mut self.nodes[index];
+      let child_count = node.children.iter().flatten().count();
+      node.children = [None; 8];
+      node.is_leaf = true;
+      self.leaf_count = self.leaf_count + 1 - child_count;
+    }
+  }
+
+  /// The index of the leaf node a color belongs to.
+  fn leaf(&self, color: u32) -> usize {
+    let mut index = 0;
+    for level in 0..MAX_DEPTH {
+      let node = &self.nodes[index];
+      if node.is_leaf {
+        break;
+      }
+      index = node.children[QuantizerOctree::child_index(color, level)].unwrap();
+    }
+    index
+  }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct OctreeNode {
+  children: [Option<usize>; 8],
+  is_leaf: bool,
+  /// The number of pixels in the subtree.
+  population: usize,
+  /// The sums of the red, green and blue channels of the pixels in the subtree.
+  sums: [usize; 3],
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RED: u32 = 0xffff0000;
+  const GREEN: u32 = 0xff00ff00;
+  const BLUE: u32 = 0xff0000ff;
+  const MAX_COLORS: usize = 256;
+
+  #[test]
+  fn empty_input() {
+    let result = QuantizerOctree.quantize(&[], MAX_COLORS);
+    assert!(result.color_to_count.is_empty());
+  }
+
+  #[test]
+  fn two_red_three_green() {
+    let result = QuantizerOctree.quantize(&[RED, RED, GREEN, GREEN, GREEN], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 2);
+    assert_eq!(result.color_to_count[&RED], 2);
+    assert_eq!(result.color_to_count[&GREEN], 3);
+    assert_eq!(result.input_pixel_to_cluster_pixel[&RED], RED);
+  }
+
+  #[test]
+  fn one_red_one_green_one_blue() {
+    let result = QuantizerOctree.quantize(&[RED, GREEN, BLUE], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 3);
+    assert_eq!(result.color_to_count[&RED], 1);
+    assert_eq!(result.color_to_count[&GREEN], 1);
+    assert_eq!(result.color_to_count[&BLUE], 1);
+  }
+
+  #[test]
+  fn merges_into_one_color() {
+    let result = QuantizerOctree.quantize(&[RED, GREEN, BLUE], 1);
+    assert_eq!(result.color_to_count.len(), 1);
+    assert_eq!(result.color_to_count[&0xff555555], 3);
+  }
+
+  #[test]
+  fn respects_max_colors() {
+    let pixels: Vec<u32> = (0..4096)
+      .map(|i| utils::color::argb_from_rgb((i * 7) as u8, (i * 13) as u8, (i * 31) as u8))
+      .collect();
+    let result = QuantizerOctree.quantize(&pixels, 16);
+    assert!(result.color_to_count.len() <= 16);
+    assert_eq!(result.color_to_count.values().sum::<usize>(), pixels.len());
+    assert!(
+      pixels
+        .iter()
+        .all(|pixel| result.input_pixel_to_cluster_pixel.contains_key(pixel))
+    );
+  }
+
+  #[test]
+  fn ignores_zero_counts() {
+    let color_to_count = HashMap::from([(BLUE, 5), (RED, 0)]);
+    let result = QuantizerOctree.quantize_histogram(&color_to_count, 4);
+    assert_eq!(result.color_to_count, HashMap::from([(BLUE, 5)]));
+    assert_eq!(
+      result.input_pixel_to_cluster_pixel,
+      HashMap::from([(BLUE, BLUE)])
+    );
+
+    let color_to_count = HashMap::from([(BLUE, 0)]);
+    let result = QuantizerOctree.quantize_histogram(&color_to_count, 4);
+    assert!(result.color_to_count.is_empty());
+  }
+}
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
-use rand::{SeedableRng, rngs::StdRng};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::index};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-use crate::quantize::{PointProvider, PointProviderLab, Quantizer, QuantizerResult};
+use crate::quantize::{PointProvider, PointProviderLab, Quantizer, QuantizerMap, QuantizerResult};
 
 /// An image quantizer that improves on the speed of a standard K-Means algorithm
 /// by implementing several optimizations, including deduping identical pixels
@@ -13,85 +15,398 @@ use crate::quantize::{PointProvider, PointProviderLab, Quantizer, QuantizerResul
 ///
 /// This algorithm was designed by M. Emre Celebi, and was found in their 2011
 /// paper, Improving the Performance of K-Means for Color Quantization.
-/// https://arxiv.org/abs/1101.0395
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct QuantizerWsmeans {}
+/// <https://arxiv.org/abs/1101.0395>
+///
+/// Clustering happens in the color space of the [`PointProvider`], which is
+/// L*a*b* by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuantizerWsmeans<P = PointProviderLab> {
+  point_provider: P,
+}
 
 impl QuantizerWsmeans {
+  pub const fn new() -> Self {
+    Self::with_point_provider(PointProviderLab)
+  }
+}
+
+impl Default for QuantizerWsmeans {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<P> QuantizerWsmeans<P> {
+  /// Create a quantizer clustering colors in the color space of `point_provider`.
+  pub const fn with_point_provider(point_provider: P) -> Self {
+    Self { point_provider }
+  }
+}
+
+impl<P> QuantizerWsmeans<P>
+where
+  P: PointProvider<Point = [f64; 3]> + Sync,
+{
   const MAX_ITERATIONS: usize = 10;
   const MIN_MOVEMENT_DISTANCE: f64 = 3.0;
 
   /// Reduce the number of colors needed to represented the input, minimizing the difference between
   /// the original image and the recolored image.
+  ///
+  /// When `starting_clusters` is empty, the initial clusters are picked at random from the input
+  /// colors, using a seeded random number generator.
   pub fn quantize_with_starting_clusters(
     self,
-    inputPixels: &[u32],      // TODO: rename to `pixels`
-    maxColors: usize,         // TODO: rename to `max_colors`
-    startingClusters: &[u32], // TODO: rename to `starting_clusters`
+    pixels: &[u32],
+    max_colors: usize,
+    starting_clusters: &[u32],
+  ) -> QuantizerResult {
+    self.quantize_histogram_with_starting_clusters(
+      &QuantizerMap::histogram(pixels),
+      max_colors,
+      starting_clusters,
+    )
+  }
+
+  /// Like [`QuantizerWsmeans::quantize_with_starting_clusters`], but for an image given as a
+  /// histogram, see [`Quantizer::quantize_histogram`].
+  pub fn quantize_histogram_with_starting_clusters(
+    self,
+    color_to_count: &HashMap<u32, usize>,
+    max_colors: usize,
+    starting_clusters: &[u32],
   ) -> QuantizerResult {
     // Uses a seeded random number generator to ensure consistent results.
     let mut rng = StdRng::seed_from_u64(0x42688);
 
-    let pointProvider = PointProviderLab;
-
-    let mut pixelToCount: HashMap<u32, usize> = HashMap::new();
-    let mut points: Vec<[f64; 3]> = Vec::with_capacity(inputPixels.len());
-    let mut pixels: Vec<u32> = Vec::with_capacity(inputPixels.len());
-    let mut pointCount = 0;
+    let point_provider = &self.point_provider;
 
-    for &input_pixel in inputPixels {
-      pixelToCount
-        .entry(input_pixel)
-        .and_modify(|pixel_count| *pixel_count += 1)
-        .or_insert_with(|| {
-          points[pointCount] = pointProvider.from_int(input_pixel);
-          pixels[pointCount] = input_pixel;
+    // Sorted, so that the random initial state doesn't depend on the order of the histogram.
+    let mut unique_pixels: Vec<u32> = color_to_count
+      .iter()
+      .filter(|&(_, &count)| count > 0)
+      .map(|(&pixel, _)| pixel)
+      .collect();
+    unique_pixels.sort_unstable();
+    let points: Vec<[f64; 3]> = unique_pixels
+      .iter()
+      .map(|&pixel| point_provider.from_int(pixel))
+      .collect();
+    let counts: Vec<usize> = unique_pixels
+      .iter()
+      .map(|pixel| color_to_count[pixel])
+      .collect();
+    let point_count = points.len();
 
-          pointCount += 1;
-          1
-        });
+    let mut cluster_count = usize::min(max_colors, point_count);
+    if !starting_clusters.is_empty() {
+      cluster_count = usize::min(cluster_count, starting_clusters.len());
     }
-
-    let mut counts = vec![0; pointCount];
-    for i in 0..pointCount {
-      let pixel = pixels[i];
-      let count = pixelToCount[&pixel];
-      counts[i] = count;
+    if cluster_count == 0 {
+      return QuantizerResult::default();
     }
 
-    let mut clusterCount = usize::min(maxColors, pointCount);
-    if !startingClusters.is_empty() {
-      clusterCount = usize::min(clusterCount, startingClusters.len());
-    }
+    let mut clusters: Vec<[f64; 3]> = if starting_clusters.is_empty() {
+      index::sample(&mut rng, point_count, cluster_count)
+        .into_iter()
+        .map(|i| points[i])
+        .collect()
+    } else {
+      starting_clusters
+        .iter()
+        .take(cluster_count)
+        .map(|&starting_cluster| point_provider.from_int(starting_cluster))
+        .collect()
+    };
 
-    let mut clusters: Vec<[f64; 3]> = startingClusters
-      .iter()
-      .map(|starting_cluster| pointProvider.from_int(*starting_cluster))
+    let mut cluster_indices: Vec<usize> = (0..point_count)
+      .map(|_| rng.random_range(0..cluster_count))
       .collect();
 
-    todo!()
+    let mut distance_to_index_matrix =
+      vec![vec![DistanceAndIndex::default(); cluster_count]; cluster_count];
+
+    let mut pixel_count_sums = vec![0; cluster_count];
+    for iteration in 0..Self::MAX_ITERATIONS {
+      for i in 0..cluster_count {
+        distance_to_index_matrix[i][i] = DistanceAndIndex::new(0.0, i);
+        for j in i + 1..cluster_count {
+          let distance = point_provider.distance(&clusters[i], &clusters[j]);
+          distance_to_index_matrix[j][i] = DistanceAndIndex::new(distance, i);
+          distance_to_index_matrix[i][j] = DistanceAndIndex::new(distance, j);
+        }
+      }
+      // Sorting scrambles the positions cells are written to, so rows are only sorted once the
+      // whole matrix is filled in.
+      for row in distance_to_index_matrix.iter_mut() {
+        row.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+      }
+
+      // Moves a point to its nearest cluster, returning whether it moved.
+      let assign_point = |point: &[f64; 3], cluster_index: &mut usize| {
+        let previous_cluster_index = *cluster_index;
+        let previous_cluster = &clusters[previous_cluster_index];
+        let previous_distance = point_provider.distance(point, previous_cluster);
+
+        let mut minimum_distance = previous_distance;
+        let mut new_cluster_index = None;
+        for neighbor in distance_to_index_matrix[previous_cluster_index].iter() {
+          // By the triangle inequality, a cluster at least twice as far from the previous
+          // cluster as the point itself cannot be closer to the point. Rows are sorted, so
+          // every remaining cluster can be skipped too.
+          if neighbor.distance >= 4.0 * previous_distance {
+            break;
+          }
+          let distance = point_provider.distance(point, &clusters[neighbor.index]);
+          if distance < minimum_distance {
+            minimum_distance = distance;
+            new_cluster_index = Some(neighbor.index);
+          }
+        }
+        if let Some(new_cluster_index) = new_cluster_index {
+          let distance_change = (minimum_distance.sqrt() - previous_distance.sqrt()).abs();
+          if distance_change > Self::MIN_MOVEMENT_DISTANCE {
+            *cluster_index = new_cluster_index;
+            return true;
+          }
+        }
+        false
+      };
+
+      let points_moved = assign_points(&points, &mut cluster_indices, assign_point);
+
+      if points_moved == 0 && iteration != 0 {
+        break;
+      }
+
+      let mut component_sums = vec![[0.0; 3]; cluster_count];
+      pixel_count_sums.fill(0);
+      for i in 0..point_count {
+        let cluster_index = cluster_indices[i];
+        let point = &points[i];
+        let count = counts[i];
+        pixel_count_sums[cluster_index] += count;
+        component_sums[cluster_index][0] += point[0] * count as f64;
+        component_sums[cluster_index][1] += point[1] * count as f64;
+        component_sums[cluster_index][2] += point[2] * count as f64;
+      }
+
+      for (i, cluster) in clusters.iter_mut().enumerate() {
+        let count = pixel_count_sums[i];
+        if count == 0 {
+          *cluster = [0.0; 3];
+          continue;
+        }
+        let count = count as f64;
+        *cluster = [
+          component_sums[i][0] / count,
+          component_sums[i][1] / count,
+          component_sums[i][2] / count,
+        ];
+      }
+    }
+
+    let mut result = QuantizerResult::default();
+    let mut cluster_argbs = vec![0; cluster_count];
+    for (i, &cluster) in clusters.iter().enumerate() {
+      let count = pixel_count_sums[i];
+      if count == 0 {
+        continue;
+      }
+
+      let possible_new_cluster = point_provider.to_int(cluster);
+      cluster_argbs[i] = possible_new_cluster;
+      *result
+        .color_to_count
+        .entry(possible_new_cluster)
+        .or_insert(0) += count;
+    }
+
+    for (&pixel, &cluster_index) in unique_pixels.iter().zip(cluster_indices.iter()) {
+      result
+        .input_pixel_to_cluster_pixel
+        .insert(pixel, cluster_argbs[cluster_index]);
+    }
+    result
   }
 }
 
-impl Quantizer for QuantizerWsmeans {
+impl<P> Quantizer for QuantizerWsmeans<P>
+where
+  P: PointProvider<Point = [f64; 3]> + Sync,
+{
   /// Reduce the number of colors needed to represented the input, minimizing the difference between
   /// the original image and the recolored image.
-  fn quantize(self, pixels: &[u32], max_colors: usize) -> QuantizerResult {
-    self.quantize_with_starting_clusters(pixels, max_colors, &[])
+  fn quantize_histogram(
+    self,
+    color_to_count: &HashMap<u32, usize>,
+    max_colors: usize,
+  ) -> QuantizerResult {
+    self.quantize_histogram_with_starting_clusters(color_to_count, max_colors, &[])
   }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+/// Moves each point to its nearest cluster with `assign_point`, returning how many points moved.
+///
+/// Each point is assigned independently of the others, so assigning them in parallel gives the
+/// same clusters.
+#[cfg(feature = "parallel")]
+fn assign_points(
+  points: &[[f64; 3]],
+  cluster_indices: &mut [usize],
+  assign_point: impl Fn(&[f64; 3], &mut usize) -> bool + Sync,
+) -> usize {
+  points
+    .par_iter()
+    .zip(cluster_indices.par_iter_mut())
+    .map(|(point, cluster_index)| assign_point(point, cluster_index))
+    .filter(|&moved| moved)
+    .count()
+}
+
+/// Moves each point to its nearest cluster with `assign_point`, returning how many points moved.
+#[cfg(any(not(feature = "parallel"), test))]
+fn assign_points_sequentially(
+  points: &[[f64; 3]],
+  cluster_indices: &mut [usize],
+  assign_point: impl Fn(&[f64; 3], &mut usize) -> bool,
+) -> usize {
+  points
+    .iter()
+    .zip(cluster_indices.iter_mut())
+    .map(|(point, cluster_index)| assign_point(point, cluster_index))
+    .filter(|&moved| moved)
+    .count()
+}
+
+#[cfg(not(feature = "parallel"))]
+use assign_points_sequentially as assign_points;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 struct DistanceAndIndex {
   distance: f64,
-  index: isize,
+  index: usize,
 }
 
-impl Default for DistanceAndIndex {
-  fn default() -> Self {
-    Self {
-      distance: -1.0,
-      index: -1,
+impl DistanceAndIndex {
+  fn new(distance: f64, index: usize) -> Self {
+    Self { distance, index }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    difference::DeltaE,
+    quantize::{PointProviderCam16, PointProviderDeltaE, PointProviderOklab},
+  };
+
+  const RED: u32 = 0xffff0000;
+  const GREEN: u32 = 0xff00ff00;
+  const BLUE: u32 = 0xff0000ff;
+
+  #[test]
+  fn empty_input() {
+    let result = QuantizerWsmeans::default().quantize(&[], 128);
+    assert!(result.color_to_count.is_empty());
+  }
+
+  #[test]
+  fn one_red() {
+    let result = QuantizerWsmeans::default().quantize(&[RED], 128);
+    assert_eq!(result.color_to_count.len(), 1);
+    assert_eq!(result.color_to_count[&RED], 1);
+    assert_eq!(result.input_pixel_to_cluster_pixel[&RED], RED);
+  }
+
+  #[test]
+  fn ignores_zero_counts() {
+    let color_to_count = HashMap::from([(RED, 5), (BLUE, 0)]);
+    let result = QuantizerWsmeans::default().quantize_histogram(&color_to_count, 4);
+    assert_eq!(result.color_to_count, HashMap::from([(RED, 5)]));
+    assert_eq!(
+      result.input_pixel_to_cluster_pixel,
+      HashMap::from([(RED, RED)])
+    );
+
+    let color_to_count = HashMap::from([(BLUE, 0)]);
+    let result = QuantizerWsmeans::default().quantize_histogram(&color_to_count, 4);
+    assert!(result.color_to_count.is_empty());
+  }
+
+  #[test]
+  fn separates_distinct_colors() {
+    let pixels = [RED, RED, GREEN, GREEN, GREEN, BLUE];
+    let result = QuantizerWsmeans::default().quantize(&pixels, 128);
+    assert_eq!(result.color_to_count.len(), 3);
+    assert_eq!(result.color_to_count[&RED], 2);
+    assert_eq!(result.color_to_count[&GREEN], 3);
+    assert_eq!(result.color_to_count[&BLUE], 1);
+  }
+
+  #[test]
+  fn refines_starting_clusters() {
+    let pixels = [0xffff0000, 0xfffe0000, 0xff0000ff, 0xff0000fe];
+    let result =
+      QuantizerWsmeans::default().quantize_with_starting_clusters(&pixels, 128, &[RED, BLUE]);
+    assert_eq!(result.color_to_count.len(), 2);
+    assert_eq!(result.color_to_count.values().sum::<usize>(), pixels.len());
+    assert_eq!(result.input_pixel_to_cluster_pixel.len(), pixels.len());
+    assert_eq!(
+      result.input_pixel_to_cluster_pixel[&0xffff0000],
+      result.input_pixel_to_cluster_pixel[&0xfffe0000]
+    );
+    assert!(
+      result
+        .input_pixel_to_cluster_pixel
+        .values()
+        .all(|cluster| result.color_to_count.contains_key(cluster))
+    );
+  }
+
+  #[test]
+  fn separates_distinct_colors_in_other_spaces() {
+    let pixels = [RED, RED, GREEN, GREEN, GREEN, BLUE];
+    let cam16 = QuantizerWsmeans::with_point_provider(PointProviderCam16).quantize(&pixels, 128);
+    let oklab = QuantizerWsmeans::with_point_provider(PointProviderOklab).quantize(&pixels, 128);
+    let ciede2000 =
+      QuantizerWsmeans::with_point_provider(PointProviderDeltaE::new(DeltaE::Ciede2000))
+        .quantize(&pixels, 128);
+    for result in [cam16, oklab, ciede2000] {
+      assert_eq!(result.color_to_count.len(), 3);
+      assert_eq!(result.color_to_count[&RED], 2);
+      assert_eq!(result.color_to_count[&GREEN], 3);
+      assert_eq!(result.color_to_count[&BLUE], 1);
     }
   }
+
+  #[cfg(feature = "parallel")]
+  #[test]
+  fn parallel_matches_sequential() {
+    let points: Vec<[f64; 3]> = (0..4096u32)
+      .map(|i| PointProviderLab.from_int(0xff000000 | i.wrapping_mul(0x9e3779b1) >> 8))
+      .collect();
+    let clusters = [RED, GREEN, BLUE, 0xff808080].map(|argb| PointProviderLab.from_int(argb));
+    let assign_point = |point: &[f64; 3], cluster_index: &mut usize| {
+      let nearest = (0..clusters.len())
+        .min_by(|&a, &b| {
+          let distance_a = PointProviderLab.distance(point, &clusters[a]);
+          let distance_b = PointProviderLab.distance(point, &clusters[b]);
+          distance_a.total_cmp(&distance_b)
+        })
+        .unwrap();
+      let moved = nearest != *cluster_index;
+      *cluster_index = nearest;
+      moved
+    };
+    let initial_indices: Vec<usize> = (0..points.len()).map(|i| i % clusters.len()).collect();
+    let mut sequential = initial_indices.clone();
+    let mut parallel = initial_indices;
+    assert_eq!(
+      assign_points_sequentially(&points, &mut sequential, assign_point),
+      assign_points(&points, &mut parallel, assign_point)
+    );
+    assert_eq!(sequential, parallel);
+  }
 }
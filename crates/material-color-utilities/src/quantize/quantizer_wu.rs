@@ -1,12 +1,20 @@
-use crate::quantize::{PointProviderLab, Quantizer, QuantizerResult};
+use std::collections::HashMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+  quantize::{Quantizer, QuantizerMap, QuantizerResult},
+  utils,
+};
 
 // A histogram of all the input colors is constructed. It has the shape of a
 // cube. The cube would be too large if it contained all 16 million colors:
 // historical best practice is to use 5 bits  of the 8 in each channel,
 // reducing the histogram to a volume of ~32,000.
-// const INDEX_BITS: usize = 5;
-// const INDEX_COUNT: usize = (1 << INDEX_BITS) + 1;
-// const TOTAL_SIZE: usize = INDEX_COUNT * INDEX_COUNT * INDEX_COUNT;
+const INDEX_BITS: usize = 5;
+const INDEX_COUNT: usize = (1 << INDEX_BITS) + 1;
+const TOTAL_SIZE: usize = INDEX_COUNT * INDEX_COUNT * INDEX_COUNT;
 
 /// An image quantizer that divides the image's pixels into clusters by
 /// recursively cutting an RGB cube, based on the weight of pixels in each area
@@ -15,12 +23,719 @@ use crate::quantize::{PointProviderLab, Quantizer, QuantizerResult};
 /// The algorithm was described by Xiaolin Wu in Graphic Gems II, published in
 /// 1991.
 #[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
-pub struct QuantizerWu {}
+pub struct QuantizerWu {
+  weights: Vec<i64>,
+  moments_r: Vec<i64>,
+  moments_g: Vec<i64>,
+  moments_b: Vec<i64>,
+  moments: Vec<f64>,
+  cubes: Vec<Cube>,
+}
+
+impl QuantizerWu {
+  fn index(r: usize, g: usize, b: usize) -> usize {
+    (r << (INDEX_BITS * 2)) + (r << (INDEX_BITS + 1)) + r + (g << INDEX_BITS) + g + b
+  }
+
+  /// The index of the histogram cell a pixel falls into.
+  fn histogram_index(pixel: u32) -> usize {
+    let bits_to_remove = 8 - INDEX_BITS;
+    let i_r = (utils::color::red_from_argb(pixel) as usize >> bits_to_remove) + 1;
+    let i_g = (utils::color::green_from_argb(pixel) as usize >> bits_to_remove) + 1;
+    let i_b = (utils::color::blue_from_argb(pixel) as usize >> bits_to_remove) + 1;
+    Self::index(i_r, i_g, i_b)
+  }
+
+  fn construct_histogram(color_to_count: &HashMap<u32, usize>) -> Histogram {
+    #[cfg(not(feature = "parallel"))]
+    let histogram =
+      color_to_count
+        .iter()
+        .fold(Histogram::new(), |mut histogram, (&pixel, &count)| {
+          histogram.add(pixel, count);
+          histogram
+        });
+    // The moments are sums of integers, which are exact even in floating point for any image
+    // size that fits in memory, so they don't depend on how pixels are split between tasks.
+    #[cfg(feature = "parallel")]
+    let histogram = color_to_count
+      .par_iter()
+      .fold(Histogram::new, |mut histogram, (&pixel, &count)| {
+        histogram.add(pixel, count);
+        histogram
+      })
+      .reduce(Histogram::new, Histogram::merge);
+    histogram
+  }
+
+  /// Cuts the histogram into at most `color_count` boxes, returning the colors of the boxes and the
+  /// box each cell of the histogram belongs to.
+  fn quantize_cube(
+    mut self,
+    histogram: Histogram,
+    color_count: usize,
+  ) -> (QuantizerResult, QuantizerWuClusters) {
+    if color_count == 0 {
+      return (QuantizerResult::default(), QuantizerWuClusters::new());
+    }
+    self.weights = histogram.weights;
+    self.moments_r = histogram.moments_r;
+    self.moments_g = histogram.moments_g;
+    self.moments_b = histogram.moments_b;
+    self.moments = histogram.moments;
+    self.create_moments();
+    let result_count = self.create_boxes(color_count);
+    self.create_result(result_count)
+  }
+
+  fn create_moments(&mut self) {
+    for r in 1..INDEX_COUNT {
+      let mut area = [0i64; INDEX_COUNT];
+      let mut area_r = [0i64; INDEX_COUNT];
+      let mut area_g = [0i64; INDEX_COUNT];
+      let mut area_b = [0i64; INDEX_COUNT];
+      let mut area2 = [0.0; INDEX_COUNT];
+      for g in 1..INDEX_COUNT {
+        let mut line = 0;
+        let mut line_r = 0;
+        let mut line_g = 0;
+        let mut line_b = 0;
+        let mut line2 = 0.0;
+        for b in 1..INDEX_COUNT {
+          let index = Self::index(r, g, b);
+          line += self.weights[index];
+          line_r += self.moments_r[index];
+          line_g += self.moments_g[index];
+          line_b += self.moments_b[index];
+          line2 += self.moments[index];
+
+          area[b] += line;
+          area_r[b] += line_r;
+          area_g[b] += line_g;
+          area_b[b] += line_b;
+          area2[b] += line2;
+
+          let previous_index = Self::index(r - 1, g, b);
+          self.weights[index] = self.weights[previous_index] + area[b];
+          self.moments_r[index] = self.moments_r[previous_index] + area_r[b];
+          self.moments_g[index] = self.moments_g[previous_index] + area_g[b];
+          self.moments_b[index] = self.moments_b[previous_index] + area_b[b];
+          self.moments[index] = self.moments[previous_index] + area2[b];
+        }
+      }
+    }
+  }
+
+  /// Cuts the histogram cube into at most `max_color_count` boxes, returning the number of boxes
+  /// that were actually generated.
+  fn create_boxes(&mut self, max_color_count: usize) -> usize {
+    self.cubes = vec![Cube::default(); max_color_count];
+    let mut volume_variance = vec![0.0; max_color_count];
+    let first_box = &mut self.cubes[0];
+    first_box.r1 = INDEX_COUNT - 1;
+    first_box.g1 = INDEX_COUNT - 1;
+    first_box.b1 = INDEX_COUNT - 1;
+
+    let mut generated_color_count = max_color_count;
+    let mut next = 0;
+    let mut i = 1;
+    while i < max_color_count {
+      if self.cut(next, i) {
+        volume_variance[next] = if self.cubes[next].vol > 1 {
+          self.variance(&self.cubes[next])
+        } else {
+          0.0
+        };
+        volume_variance[i] = if self.cubes[i].vol > 1 {
+          self.variance(&self.cubes[i])
+        } else {
+          0.0
+        };
+      } else {
+        volume_variance[next] = 0.0;
+        i -= 1;
+      }
+
+      next = 0;
+      let mut temp = volume_variance[0];
+      for (j, &variance) in volume_variance.iter().enumerate().take(i + 1).skip(1) {
+        if variance > temp {
+          temp = variance;
+          next = j;
+        }
+      }
+      if temp <= 0.0 {
+        generated_color_count = i + 1;
+        break;
+      }
+      i += 1;
+    }
+    generated_color_count
+  }
+
+  fn create_result(&self, color_count: usize) -> (QuantizerResult, QuantizerWuClusters) {
+    let mut result = QuantizerResult::default();
+    let mut clusters = QuantizerWuClusters::new();
+    for cube in self.cubes.iter().take(color_count) {
+      let weight = Self::volume(cube, &self.weights);
+      if weight > 0 {
+        let r = (Self::volume(cube, &self.moments_r) / weight) as u8;
+        let g = (Self::volume(cube, &self.moments_g) / weight) as u8;
+        let b = (Self::volume(cube, &self.moments_b) / weight) as u8;
+        let color = utils::color::argb_from_rgb(r, g, b);
+        *result.color_to_count.entry(color).or_insert(0) += weight as usize;
+        for r in cube.r0 + 1..=cube.r1 {
+          for g in cube.g0 + 1..=cube.g1 {
+            for b in cube.b0 + 1..=cube.b1 {
+              clusters.tags[Self::index(r, g, b)] = Some(color);
+            }
+          }
+        }
+      }
+    }
+    (result, clusters)
+  }
+
+  fn variance(&self, cube: &Cube) -> f64 {
+    let dr = Self::volume(cube, &self.moments_r) as f64;
+    let dg = Self::volume(cube, &self.moments_g) as f64;
+    let db = Self::volume(cube, &self.moments_b) as f64;
+    let moments = &self.moments;
+    let xx = moments[Self::index(cube.r1, cube.g1, cube.b1)]
+      - moments[Self::index(cube.r1, cube.g1, cube.b0)]
+      - moments[Self::index(cube.r1, cube.g0, cube.b1)]
+      + moments[Self::index(cube.r1, cube.g0, cube.b0)]
+      - moments[Self::index(cube.r0, cube.g1, cube.b1)]
+      + moments[Self::index(cube.r0, cube.g1, cube.b0)]
+      + moments[Self::index(cube.r0, cube.g0, cube.b1)]
+      - moments[Self::index(cube.r0, cube.g0, cube.b0)];
+    let hypotenuse = dr * dr + dg * dg + db * db;
+    let volume = Self::volume(cube, &self.weights) as f64;
+    xx - hypotenuse / volume
+  }
+
+  /// Attempts to cut the cube at index `one`, storing the upper half in the cube at index `two`.
+  fn cut(&mut self, one: usize, two: usize) -> bool {
+    let one_cube = self.cubes[one];
+    let whole_r = Self::volume(&one_cube, &self.moments_r);
+    let whole_g = Self::volume(&one_cube, &self.moments_g);
+    let whole_b = Self::volume(&one_cube, &self.moments_b);
+    let whole_w = Self::volume(&one_cube, &self.weights);
+    let whole = [whole_r, whole_g, whole_b, whole_w];
+
+    let max_r_result = self.maximize(
+      &one_cube,
+      Direction::Red,
+      one_cube.r0 + 1,
+      one_cube.r1,
+      whole,
+    );
+    let max_g_result = self.maximize(
+      &one_cube,
+      Direction::Green,
+      one_cube.g0 + 1,
+      one_cube.g1,
+      whole,
+    );
+    let max_b_result = self.maximize(
+      &one_cube,
+      Direction::Blue,
+      one_cube.b0 + 1,
+      one_cube.b1,
+      whole,
+    );
+
+    let max_r = max_r_result.maximum;
+    let max_g = max_g_result.maximum;
+    let max_b = max_b_result.maximum;
+    let cut_direction = if max_r >= max_g && max_r >= max_b {
+      if max_r_result.cut_location.is_none() {
+        return false;
+      }
+      Direction::Red
+    } else if max_g >= max_r && max_g >= max_b {
+      Direction::Green
+    } else {
+      Direction::Blue
+    };
+
+    let mut one_cube = one_cube;
+    let mut two_cube = self.cubes[two];
+    two_cube.r1 = one_cube.r1;
+    two_cube.g1 = one_cube.g1;
+    two_cube.b1 = one_cube.b1;
+
+    match cut_direction {
+      Direction::Red => {
+        one_cube.r1 = max_r_result.cut_location.unwrap();
+        two_cube.r0 = one_cube.r1;
+        two_cube.g0 = one_cube.g0;
+        two_cube.b0 = one_cube.b0;
+      }
+      Direction::Green => {
+        one_cube.g1 = max_g_result.cut_location.unwrap();
+        two_cube.r0 = one_cube.r0;
+        two_cube.g0 = one_cube.g1;
+        two_cube.b0 = one_cube.b0;
+      }
+      Direction::Blue => {
+        one_cube.b1 = max_b_result.cut_location.unwrap();
+        two_cube.r0 = one_cube.r0;
+        two_cube.g0 = one_cube.g0;
+        two_cube.b0 = one_cube.b1;
+      }
+    }
+
+    one_cube.vol =
+      (one_cube.r1 - one_cube.r0) * (one_cube.g1 - one_cube.g0) * (one_cube.b1 - one_cube.b0);
+    two_cube.vol =
+      (two_cube.r1 - two_cube.r0) * (two_cube.g1 - two_cube.g0) * (two_cube.b1 - two_cube.b0);
+    self.cubes[one] = one_cube;
+    self.cubes[two] = two_cube;
+    true
+  }
 
-impl QuantizerWu {}
+  fn maximize(
+    &self,
+    cube: &Cube,
+    direction: Direction,
+    first: usize,
+    last: usize,
+    [whole_r, whole_g, whole_b, whole_w]: [i64; 4],
+  ) -> MaximizeResult {
+    let bottom_r = Self::bottom(cube, direction, &self.moments_r);
+    let bottom_g = Self::bottom(cube, direction, &self.moments_g);
+    let bottom_b = Self::bottom(cube, direction, &self.moments_b);
+    let bottom_w = Self::bottom(cube, direction, &self.weights);
+
+    let mut max = 0.0;
+    let mut cut = None;
+
+    for i in first..last {
+      let mut half_r = bottom_r + Self::top(cube, direction, i, &self.moments_r);
+      let mut half_g = bottom_g + Self::top(cube, direction, i, &self.moments_g);
+      let mut half_b = bottom_b + Self::top(cube, direction, i, &self.moments_b);
+      let mut half_w = bottom_w + Self::top(cube, direction, i, &self.weights);
+      if half_w == 0 {
+        continue;
+      }
+
+      let mut temp_numerator = Self::squared_sum(half_r, half_g, half_b);
+      let mut temp_denominator = half_w as f64;
+      let mut temp = temp_numerator / temp_denominator;
+
+      half_r = whole_r - half_r;
+      half_g = whole_g - half_g;
+      half_b = whole_b - half_b;
+      half_w = whole_w - half_w;
+      if half_w == 0 {
+        continue;
+      }
+
+      temp_numerator = Self::squared_sum(half_r, half_g, half_b);
+      temp_denominator = half_w as f64;
+      temp += temp_numerator / temp_denominator;
+
+      if temp > max {
+        max = temp;
+        cut = Some(i);
+      }
+    }
+    MaximizeResult {
+      cut_location: cut,
+      maximum: max,
+    }
+  }
+
+  fn squared_sum(r: i64, g: i64, b: i64) -> f64 {
+    let (r, g, b) = (r as f64, g as f64, b as f64);
+    r * r + g * g + b * b
+  }
+
+  fn volume(cube: &Cube, moment: &[i64]) -> i64 {
+    moment[Self::index(cube.r1, cube.g1, cube.b1)]
+      - moment[Self::index(cube.r1, cube.g1, cube.b0)]
+      - moment[Self::index(cube.r1, cube.g0, cube.b1)]
+      + moment[Self::index(cube.r1, cube.g0, cube.b0)]
+      - moment[Self::index(cube.r0, cube.g1, cube.b1)]
+      + moment[Self::index(cube.r0, cube.g1, cube.b0)]
+      + moment[Self::index(cube.r0, cube.g0, cube.b1)]
+      - moment[Self::index(cube.r0, cube.g0, cube.b0)]
+  }
+
+  fn bottom(cube: &Cube, direction: Direction, moment: &[i64]) -> i64 {
+    match direction {
+      Direction::Red => {
+        -moment[Self::index(cube.r0, cube.g1, cube.b1)]
+          + moment[Self::index(cube.r0, cube.g1, cube.b0)]
+          + moment[Self::index(cube.r0, cube.g0, cube.b1)]
+          - moment[Self::index(cube.r0, cube.g0, cube.b0)]
+      }
+      Direction::Green => {
+        -moment[Self::index(cube.r1, cube.g0, cube.b1)]
+          + moment[Self::index(cube.r1, cube.g0, cube.b0)]
+          + moment[Self::index(cube.r0, cube.g0, cube.b1)]
+          - moment[Self::index(cube.r0, cube.g0, cube.b0)]
+      }
+      Direction::Blue => {
+        -moment[Self::index(cube.r1, cube.g1, cube.b0)]
+          + moment[Self::index(cube.r1, cube.g0, cube.b0)]
+          + moment[Self::index(cube.r0, cube.g1, cube.b0)]
+          - moment[Self::index(cube.r0, cube.g0, cube.b0)]
+      }
+    }
+  }
+
+  fn top(cube: &Cube, direction: Direction, position: usize, moment: &[i64]) -> i64 {
+    match direction {
+      Direction::Red => {
+        moment[Self::index(position, cube.g1, cube.b1)]
+          - moment[Self::index(position, cube.g1, cube.b0)]
+          - moment[Self::index(position, cube.g0, cube.b1)]
+          + moment[Self::index(position, cube.g0, cube.b0)]
+      }
+      Direction::Green => {
+        moment[Self::index(cube.r1, position, cube.b1)]
+          - moment[Self::index(cube.r1, position, cube.b0)]
+          - moment[Self::index(cube.r0, position, cube.b1)]
+          + moment[Self::index(cube.r0, position, cube.b0)]
+      }
+      Direction::Blue => {
+        moment[Self::index(cube.r1, cube.g1, position)]
+          - moment[Self::index(cube.r1, cube.g0, position)]
+          - moment[Self::index(cube.r0, cube.g1, position)]
+          + moment[Self::index(cube.r0, cube.g0, position)]
+      }
+    }
+  }
+}
 
 impl Quantizer for QuantizerWu {
-  fn quantize(mut self, pixels: &[u32], colorCount: usize) -> QuantizerResult {
-    todo!()
+  fn quantize_histogram(
+    self,
+    color_to_count: &HashMap<u32, usize>,
+    color_count: usize,
+  ) -> QuantizerResult {
+    let (mut result, clusters) =
+      self.quantize_cube(Self::construct_histogram(color_to_count), color_count);
+    clusters.add_pixels_to(&mut result, color_to_count.keys().copied());
+    result
+  }
+}
+
+/// The weights and moments of the pixels in each cell of the histogram cube.
+#[derive(Debug, Clone, PartialEq)]
+struct Histogram {
+  weights: Vec<i64>,
+  moments_r: Vec<i64>,
+  moments_g: Vec<i64>,
+  moments_b: Vec<i64>,
+  moments: Vec<f64>,
+}
+
+impl Histogram {
+  fn new() -> Self {
+    Self {
+      weights: vec![0; TOTAL_SIZE],
+      moments_r: vec![0; TOTAL_SIZE],
+      moments_g: vec![0; TOTAL_SIZE],
+      moments_b: vec![0; TOTAL_SIZE],
+      moments: vec![0.0; TOTAL_SIZE],
+    }
+  }
+
+  fn add(&mut self, pixel: u32, count: usize) {
+    let red = utils::color::red_from_argb(pixel) as i64;
+    let green = utils::color::green_from_argb(pixel) as i64;
+    let blue = utils::color::blue_from_argb(pixel) as i64;
+    let index = QuantizerWu::histogram_index(pixel);
+    let count = count as i64;
+    self.weights[index] += count;
+    self.moments_r[index] += red * count;
+    self.moments_g[index] += green * count;
+    self.moments_b[index] += blue * count;
+    self.moments[index] += (count * (red * red + green * green + blue * blue)) as f64;
+  }
+
+  #[cfg(feature = "parallel")]
+  fn merge(mut self, other: Self) -> Self {
+    for i in 0..TOTAL_SIZE {
+      self.weights[i] += other.weights[i];
+      self.moments_r[i] += other.moments_r[i];
+      self.moments_g[i] += other.moments_g[i];
+      self.moments_b[i] += other.moments_b[i];
+      self.moments[i] += other.moments[i];
+    }
+    self
+  }
+}
+
+/// Incrementally quantizes an image with [`QuantizerWu`], from pixels fed in chunks or from an
+/// iterator.
+///
+/// Pixels are added to the weights and moments of the histogram cube as they come in, so memory
+/// use stays constant, whatever the size of the image or its number of distinct colors. The boxes
+/// are only cut once the stream is finished.
+///
+/// The stream doesn't keep the pixels themselves, so the `input_pixel_to_cluster_pixel` of the
+/// result of [`QuantizerWuStream::finish`] is empty. To map pixels to their clusters, e.g. to
+/// [posterize](QuantizerResult::posterize) the image, see
+/// [`QuantizerWuStream::finish_with_clusters`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizerWuStream {
+  histogram: Histogram,
+}
+
+impl QuantizerWuStream {
+  pub fn new() -> Self {
+    Self {
+      histogram: Histogram::new(),
+    }
+  }
+
+  /// Add a chunk of pixels, given as ARGB integers, e.g. a row or a tile of an image.
+  pub fn add_pixels(&mut self, pixels: &[u32]) {
+    self.extend(pixels.iter().copied());
+  }
+
+  /// Add a chunk of pixels, given as ARGB integers, each counting according to its weight.
+  ///
+  /// See [`QuantizerMap::weighted_histogram`] for how weights are counted.
+  ///
+  /// # Panics
+  ///
+  /// Panics if there isn't exactly one weight per pixel.
+  pub fn add_weighted_pixels(&mut self, pixels: &[u32], weights: &[f32]) {
+    assert_eq!(pixels.len(), weights.len(), "expected one weight per pixel");
+    for (&pixel, &weight) in pixels.iter().zip(weights) {
+      let count = QuantizerMap::count_from_weight(weight);
+      if count > 0 {
+        self.histogram.add(pixel, count);
+      }
+    }
+  }
+
+  /// Add a single pixel, given as an ARGB integer.
+  pub fn add_pixel(&mut self, pixel: u32) {
+    self.histogram.add(pixel, 1);
+  }
+
+  /// Cut the histogram into at most `max_colors` clusters.
+  ///
+  /// Unlike other quantizers, the `input_pixel_to_cluster_pixel` of the result is empty, so
+  /// [`QuantizerResult::posterize`], [`QuantizerResult::cluster_mask`] and
+  /// [`QuantizerResult::recolor`] leave every pixel unchanged. Use
+  /// [`QuantizerWuStream::finish_with_clusters`] to fill it in.
+  pub fn finish(self, max_colors: usize) -> QuantizerResult {
+    self.finish_with_clusters(max_colors).0
+  }
+
+  /// Like [`QuantizerWuStream::finish`], but also returns the cluster of each area of the RGB cube,
+  /// to map pixels to their clusters.
+  pub fn finish_with_clusters(self, max_colors: usize) -> (QuantizerResult, QuantizerWuClusters) {
+    QuantizerWu::default().quantize_cube(self.histogram, max_colors)
+  }
+}
+
+/// The clusters the RGB cube was cut into by [`QuantizerWuStream::finish_with_clusters`].
+///
+/// Pixels are assigned to clusters by the histogram cell they fall into, so this takes constant
+/// memory, and can map pixels to clusters as the image is decoded a second time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantizerWuClusters {
+  /// The color of the cluster each histogram cell belongs to.
+  tags: Vec<Option<u32>>,
+}
+
+impl QuantizerWuClusters {
+  fn new() -> Self {
+    Self {
+      tags: vec![None; TOTAL_SIZE],
+    }
+  }
+
+  /// The color of the cluster a pixel falls into, or `None` if no pixel of the stream was close to
+  /// it.
+  pub fn cluster_pixel(&self, pixel: u32) -> Option<u32> {
+    self.tags[QuantizerWu::histogram_index(pixel)]
+  }
+
+  /// Add the pixels, given as ARGB integers, to the `input_pixel_to_cluster_pixel` of a result, so
+  /// it can [posterize](QuantizerResult::posterize) them. Memory then grows with the number of
+  /// distinct pixels added.
+  pub fn add_pixels_to(&self, result: &mut QuantizerResult, pixels: impl IntoIterator<Item = u32>) {
+    for pixel in pixels {
+      if let Some(cluster) = self.cluster_pixel(pixel) {
+        result.input_pixel_to_cluster_pixel.insert(pixel, cluster);
+      }
+    }
+  }
+}
+
+impl Default for QuantizerWuStream {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Extend<u32> for QuantizerWuStream {
+  fn extend<I: IntoIterator<Item = u32>>(&mut self, pixels: I) {
+    for pixel in pixels {
+      self.add_pixel(pixel);
+    }
+  }
+}
+
+impl FromIterator<u32> for QuantizerWuStream {
+  fn from_iter<I: IntoIterator<Item = u32>>(pixels: I) -> Self {
+    let mut stream = Self::new();
+    stream.extend(pixels);
+    stream
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Direction {
+  Red,
+  Green,
+  Blue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct MaximizeResult {
+  // None if cut impossible
+  cut_location: Option<usize>,
+  maximum: f64,
+}
+
+/// Keeps track of the state of each box created as the Wu quantization algorithm progresses
+/// through dividing the image's pixels as plotted in RGB.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Cube {
+  r0: usize,
+  r1: usize,
+  g0: usize,
+  g1: usize,
+  b0: usize,
+  b1: usize,
+  vol: usize,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RED: u32 = 0xffff0000;
+  const GREEN: u32 = 0xff00ff00;
+  const BLUE: u32 = 0xff0000ff;
+  const MAX_COLORS: usize = 256;
+
+  #[test]
+  fn one_random() {
+    let result = QuantizerWu::default().quantize(&[0xff141216], MAX_COLORS);
+    let colors: Vec<_> = result.color_to_count.into_keys().collect();
+    assert_eq!(colors, [0xff141216]);
+  }
+
+  #[test]
+  fn one_red() {
+    let result = QuantizerWu::default().quantize(&[RED], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 1);
+    assert_eq!(result.color_to_count[&RED], 1);
+  }
+
+  #[test]
+  fn two_red() {
+    let result = QuantizerWu::default().quantize(&[RED, RED], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 1);
+    assert_eq!(result.color_to_count[&RED], 2);
+  }
+
+  #[test]
+  fn five_blue() {
+    let result = QuantizerWu::default().quantize(&[BLUE; 5], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 1);
+    assert_eq!(result.color_to_count[&BLUE], 5);
+  }
+
+  #[test]
+  fn two_red_three_green() {
+    let result = QuantizerWu::default().quantize(&[RED, RED, GREEN, GREEN, GREEN], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 2);
+    assert_eq!(result.color_to_count[&RED], 2);
+    assert_eq!(result.color_to_count[&GREEN], 3);
+    assert_eq!(result.input_pixel_to_cluster_pixel[&RED], RED);
+    assert_eq!(result.input_pixel_to_cluster_pixel[&GREEN], GREEN);
+  }
+
+  #[test]
+  fn one_red_one_green_one_blue() {
+    let result = QuantizerWu::default().quantize(&[RED, GREEN, BLUE], MAX_COLORS);
+    assert_eq!(result.color_to_count.len(), 3);
+    assert_eq!(result.color_to_count[&RED], 1);
+    assert_eq!(result.color_to_count[&GREEN], 1);
+    assert_eq!(result.color_to_count[&BLUE], 1);
+  }
+
+  #[test]
+  fn respects_max_colors() {
+    let pixels: Vec<u32> = (0..4096)
+      .map(|i| utils::color::argb_from_rgb((i * 7) as u8, (i * 13) as u8, (i * 31) as u8))
+      .collect();
+    let result = QuantizerWu::default().quantize(&pixels, 16);
+    assert!(result.color_to_count.len() <= 16);
+    assert_eq!(result.color_to_count.values().sum::<usize>(), pixels.len());
+    let unique_pixels: std::collections::HashSet<_> = pixels.iter().collect();
+    assert_eq!(
+      result.input_pixel_to_cluster_pixel.len(),
+      unique_pixels.len()
+    );
+    for (&pixel, cluster) in result.input_pixel_to_cluster_pixel.iter() {
+      let count = pixels.iter().filter(|&&p| p == pixel).count();
+      assert!(result.color_to_count[cluster] >= count);
+    }
+  }
+
+  #[test]
+  fn stream_matches_quantize() {
+    let pixels: Vec<u32> = (0..4096)
+      .map(|i| utils::color::argb_from_rgb((i * 7) as u8, (i * 13) as u8, (i * 31) as u8))
+      .collect();
+    let mut stream = QuantizerWuStream::new();
+    for tile in pixels.chunks(100) {
+      stream.add_pixels(tile);
+    }
+    let (mut result, clusters) = stream.clone().finish_with_clusters(16);
+    assert_eq!(stream.finish(16), result);
+    let expected = QuantizerWu::default().quantize(&pixels, 16);
+    assert_eq!(result.color_to_count, expected.color_to_count);
+    assert!(result.input_pixel_to_cluster_pixel.is_empty());
+    clusters.add_pixels_to(&mut result, pixels.iter().copied());
+    assert_eq!(result, expected);
+    assert_eq!(result.posterize(&pixels), expected.posterize(&pixels));
+    assert_eq!(
+      QuantizerWuStream::from_iter(pixels.iter().copied())
+        .finish(16)
+        .color_to_count,
+      result.color_to_count
+    );
+    assert_eq!(
+      QuantizerWuStream::new().finish(16),
+      QuantizerWu::default().quantize(&[], 16)
+    );
+  }
+
+  #[test]
+  fn weighted_stream_matches_quantize_weighted() {
+    let pixels: Vec<u32> = (0..4096)
+      .map(|i| utils::color::argb_from_rgb((i * 7) as u8, (i * 13) as u8, (i * 31) as u8))
+      .collect();
+    let weights: Vec<f32> = (0..pixels.len()).map(|i| (i % 5) as f32 / 4.0).collect();
+    let mut stream = QuantizerWuStream::new();
+    for (tile, tile_weights) in pixels.chunks(100).zip(weights.chunks(100)) {
+      stream.add_weighted_pixels(tile, tile_weights);
+    }
+    let expected = QuantizerWu::default().quantize_weighted(&pixels, &weights, 16);
+    assert_eq!(stream.finish(16).color_to_count, expected.color_to_count);
   }
 }
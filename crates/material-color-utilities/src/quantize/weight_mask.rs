@@ -0,0 +1,108 @@
+/// Per-pixel weights for an image, used to make some areas of the image count more than others
+/// when quantizing, see [`Quantizer::quantize_weighted`](crate::quantize::Quantizer::quantize_weighted).
+///
+/// Masks are defined over normalized coordinates, so the same mask applies to an image of any
+/// size.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum WeightMask {
+  /// Every pixel has a weight of 1.
+  #[default]
+  Uniform,
+  /// Pixels have a weight of 1 in the center of the image, falling off to 0.25 in the corners.
+  ///
+  /// Favors the subject of a photo, which is usually near the center.
+  CenterWeighted,
+  /// Pixels have a weight of 1, except within 10% of the edges of the image, where the weight
+  /// falls off to 0 at the edge.
+  ///
+  /// Suppresses borders, letterboxing and vignetting.
+  EdgeSuppressed,
+  /// Weights given row by row, resampled to the size of the image.
+  ///
+  /// Like the other masks, weights should range from 0 to 1; larger weights count as 1 when
+  /// quantizing, see [`QuantizerMap::weighted_histogram`](crate::quantize::QuantizerMap::weighted_histogram).
+  Custom {
+    width: usize,
+    height: usize,
+    weights: Vec<f32>,
+  },
+}
+
+impl WeightMask {
+  const CENTER_WEIGHTED_MIN_WEIGHT: f32 = 0.25;
+  const EDGE_SUPPRESSED_BORDER: f32 = 0.1;
+
+  /// The weight at a point of the image, where `x` and `y` range from 0 to 1.
+  pub fn weight(&self, x: f32, y: f32) -> f32 {
+    match self {
+      Self::Uniform => 1.0,
+      Self::CenterWeighted => {
+        let dx = 2.0 * x - 1.0;
+        let dy = 2.0 * y - 1.0;
+        // 0 at the center, 1 in the corners.
+        let distance_squared = ((dx * dx + dy * dy) / 2.0).min(1.0);
+        1.0 - (1.0 - Self::CENTER_WEIGHTED_MIN_WEIGHT) * distance_squared
+      }
+      Self::EdgeSuppressed => {
+        let edge_distance = x.min(1.0 - x).min(y).min(1.0 - y).max(0.0);
+        (edge_distance / Self::EDGE_SUPPRESSED_BORDER).min(1.0)
+      }
+      Self::Custom {
+        width,
+        height,
+        weights,
+      } => {
+        if *width == 0 || *height == 0 {
+          return 0.0;
+        }
+        let column = ((x * *width as f32) as usize).min(width - 1);
+        let row = ((y * *height as f32) as usize).min(height - 1);
+        weights.get(row * width + column).copied().unwrap_or(0.0)
+      }
+    }
+  }
+
+  /// The weights of the pixels of an image, row by row, sampled at the center of each pixel.
+  pub fn weights(&self, width: usize, height: usize) -> Vec<f32> {
+    let mut weights = Vec::with_capacity(width * height);
+    for row in 0..height {
+      let y = (row as f32 + 0.5) / height as f32;
+      for column in 0..width {
+        let x = (column as f32 + 0.5) / width as f32;
+        weights.push(self.weight(x, y));
+      }
+    }
+    weights
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn center_weighted() {
+    let mask = WeightMask::CenterWeighted;
+    assert_eq!(mask.weight(0.5, 0.5), 1.0);
+    assert_eq!(mask.weight(0.0, 0.0), 0.25);
+    assert!(mask.weight(0.5, 0.0) > mask.weight(0.0, 0.0));
+  }
+
+  #[test]
+  fn edge_suppressed() {
+    let mask = WeightMask::EdgeSuppressed;
+    assert_eq!(mask.weight(0.5, 0.5), 1.0);
+    assert_eq!(mask.weight(0.0, 0.5), 0.0);
+    assert!((mask.weight(0.05, 0.5) - 0.5).abs() < 1e-6);
+  }
+
+  #[test]
+  fn custom_is_resampled() {
+    let mask = WeightMask::Custom {
+      width: 2,
+      height: 1,
+      weights: vec![0.0, 1.0],
+    };
+    assert_eq!(mask.weights(4, 2), [0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0]);
+  }
+}
@@ -1,7 +1,13 @@
 use std::collections::HashMap;
 
-use crate::{hct::Hct, utils};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+use crate::{dislike_analyzer::DislikeAnalyzer, hct::Hct, utils};
+
+const DESIRED: usize = 4;
+// Google Blue.
+const FALLBACK_COLOR_ARGB: u32 = 0xff4285f4;
 const TARGET_CHROMA: f64 = 48.0; // A1 Chroma
 const WEIGHT_PROPORTION: f64 = 0.7;
 const WEIGHT_CHROMA_ABOVE: f64 = 0.3;
@@ -16,144 +22,585 @@ const CUTOFF_EXCITED_PROPORTION: f64 = 0.01;
 /// the least suitable is the last. There will always be at least one color returned. If all
 /// the input colors were not suitable for a theme, a default fallback color will be provided,
 /// Google Blue.
+#[deprecated(note = "use `Score` instead, which accepts populations of any size")]
 pub fn score_with(
   colors_to_population: HashMap<u32, u16>,
   desired: Option<usize>,
   fallback_color_argb: Option<u32>,
   filter: Option<bool>,
 ) -> Vec<u32> {
-  let desired = desired.unwrap_or(4);
-  // Fallback color is Google Blue.
-  let fallback_color_argb = fallback_color_argb.unwrap_or(0xff4285f4);
-  let filter = filter.unwrap_or(true);
-
-  // Get the HCT color for each Argb value, while finding the per hue count and
-  // total count.
-  let mut colors_hct: Vec<Hct> = vec![];
-  let mut hue_population: [u16; 360] = [0; 360];
-  let mut population_sum = 0.0;
-  for (key, value) in &colors_to_population {
-    let hct = Hct::from_int(*key);
-    let hue = hct.hue().floor() as u16;
-    colors_hct.push(hct);
-    hue_population[hue as usize] += value;
-    population_sum += *value as f64;
-  }
-
-  // Hues with more usage in neighboring 30 degree slice get a larger number.
-  let mut hue_excited_proportions = [0.0; 360];
-  for (hue, population) in hue_population.iter().enumerate() {
-    let proportion = *population as f64 / population_sum;
-    for i in hue as isize - 14_isize..hue as isize + 16 {
-      let neighbor_hue = utils::math::sanitize_degrees(i);
-      hue_excited_proportions[neighbor_hue as usize] += proportion;
-    }
+  let mut builder = ScoreBuilder::default();
+  if let Some(desired) = desired {
+    builder = builder.desired(desired);
+  }
+  if let Some(fallback_color_argb) = fallback_color_argb {
+    builder = builder.fallback_color_argb(fallback_color_argb);
+  }
+  if let Some(filter) = filter {
+    builder = builder.filter(filter);
+  }
+  let colors_to_population: HashMap<u32, usize> = colors_to_population
+    .into_iter()
+    .map(|(color, population)| (color, population as usize))
+    .collect();
+  builder.build().score(&colors_to_population)
+}
+
+/// Ranks colors based on suitability for being used for a UI theme.
+///
+/// The default options match the ones Android uses for wallpaper theming. Use [`Score::builder`]
+/// to customize them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Score {
+  desired: usize,
+  fallback_color_argb: u32,
+  filter: bool,
+  target_chroma: f64,
+  weight_proportion: f64,
+  weight_chroma_above: f64,
+  weight_chroma_below: f64,
+  cutoff_chroma: f64,
+  cutoff_excited_proportion: f64,
+  dislike_analyzer: Option<DislikeAnalyzer>,
+}
+
+impl Default for Score {
+  fn default() -> Self {
+    ScoreBuilder::default().build()
+  }
+}
+
+impl Score {
+  pub fn builder() -> ScoreBuilder {
+    ScoreBuilder::default()
+  }
+
+  /// The maximum number of colors returned.
+  pub fn desired(&self) -> usize {
+    self.desired
+  }
+
+  /// The color returned when none of the input colors are suitable.
+  pub fn fallback_color_argb(&self) -> u32 {
+    self.fallback_color_argb
+  }
+
+  /// Whether colors with too little chroma or usage are filtered out.
+  pub fn filter(&self) -> bool {
+    self.filter
+  }
+
+  /// The chroma colors are scored against. Colors above it are rewarded, colors below it are
+  /// penalized.
+  pub fn target_chroma(&self) -> f64 {
+    self.target_chroma
+  }
+
+  pub fn weight_proportion(&self) -> f64 {
+    self.weight_proportion
+  }
+
+  pub fn weight_chroma_above(&self) -> f64 {
+    self.weight_chroma_above
+  }
+
+  pub fn weight_chroma_below(&self) -> f64 {
+    self.weight_chroma_below
+  }
+
+  /// Colors with a chroma below this are filtered out.
+  pub fn cutoff_chroma(&self) -> f64 {
+    self.cutoff_chroma
+  }
+
+  /// Colors whose hue, including its neighboring hues, accounts for no more than this proportion
+  /// of the population are filtered out.
+  pub fn cutoff_excited_proportion(&self) -> f64 {
+    self.cutoff_excited_proportion
+  }
+
+  /// Colors the analyzer dislikes are filtered out, regardless of [`Score::filter`]. By default
+  /// there is no analyzer, and disliked colors are scored like any other color.
+  pub fn dislike_analyzer(&self) -> Option<&DislikeAnalyzer> {
+    self.dislike_analyzer.as_ref()
+  }
+
+  /// Given a map with keys of colors and values of how often the color appears, rank the colors
+  /// based on suitability for being used for a UI theme.
+  ///
+  /// Populations can be passed straight from a
+  /// [`QuantizerResult`](crate::quantize::QuantizerResult).
+  ///
+  /// Returns colors sorted by suitability for a UI theme. The most suitable color is the first
+  /// item, the least suitable is the last. There will always be at least one color returned. If
+  /// all the input colors were not suitable for a theme, the fallback color will be provided.
+  pub fn score(&self, colors_to_population: &HashMap<u32, usize>) -> Vec<u32> {
+    self.explain(colors_to_population).colors
   }
 
-  // Scores each HCT color based on usage and chroma, while optionally
-  // filtering out values that do not have enough chroma or usage.
-  let mut scored_hcts: Vec<ScoredHct> = vec![];
-  for hct in colors_hct {
-    let hue = utils::math::sanitize_degrees(hct.hue().round() as usize);
-    let proportion = hue_excited_proportions[hue];
-    if filter && (hct.chroma() < CUTOFF_CHROMA || proportion <= CUTOFF_EXCITED_PROPORTION) {
-      continue;
+  /// Like [`Score::score`], but also explains how each input color was scored, and why it was
+  /// chosen or rejected.
+  pub fn explain(&self, colors_to_population: &HashMap<u32, usize>) -> ScoreExplanation {
+    // Get the HCT color for each Argb value, while finding the per hue count and
+    // total count.
+    let mut colors_hct: Vec<(Hct, usize)> = vec![];
+    let mut hue_population: [usize; 360] = [0; 360];
+    let mut population_sum = 0.0;
+    for (&key, &value) in colors_to_population {
+      let hct = Hct::from_int(key);
+      let hue = hct.hue().floor() as usize;
+      colors_hct.push((hct, value));
+      hue_population[hue] = hue_population[hue].saturating_add(value);
+      population_sum += value as f64;
     }
 
-    let proportion_score = proportion * 100.0 * WEIGHT_PROPORTION;
-    let chroma_weight = if hct.chroma() < TARGET_CHROMA {
-      WEIGHT_CHROMA_BELOW
-    } else {
-      WEIGHT_CHROMA_ABOVE
-    };
-    let chroma_score = (hct.chroma() - TARGET_CHROMA) * chroma_weight;
-    let score = proportion_score + chroma_score;
-    scored_hcts.push(ScoredHct::new(hct, score));
-  }
-  // Sorted so that colors with higher scores come first.
-  scored_hcts.sort_by(|a, b| a.score.total_cmp(&b.score));
-
-  // Iterates through potential hue differences in degrees in order to select
-  // the colors with the largest distribution of hues possible. Starting at
-  // 90 degrees(maximum difference for 4 colors) then decreasing down to a
-  // 15 degree minimum.
-  let mut chosen_colors: Vec<Hct> = vec![];
-  // for (int differenceDegrees = 90; differenceDegrees >= 15; differenceDegrees--) {
-  for difference_degrees in (15..=90u8).rev() {
-    chosen_colors.clear();
-    for entry in scored_hcts.iter() {
-      let hct = &entry.hct;
-      let mut has_duplicate_hue = false;
-      for chosen_hct in chosen_colors.iter() {
-        if utils::math::difference_degrees(hct.hue(), chosen_hct.hue()) < difference_degrees as f64
-        {
-          has_duplicate_hue = true;
+    // Hues with more usage in neighboring 30 degree slice get a larger number.
+    let mut hue_excited_proportions = [0.0; 360];
+    for (hue, population) in hue_population.iter().enumerate() {
+      let proportion = *population as f64 / population_sum;
+      for i in hue as isize - 14_isize..hue as isize + 16 {
+        let neighbor_hue = utils::math::sanitize_degrees(i);
+        hue_excited_proportions[neighbor_hue as usize] += proportion;
+      }
+    }
+
+    // Scores each HCT color based on usage and chroma, while optionally
+    // filtering out values that do not have enough chroma or usage.
+    let mut candidates: Vec<ScoreCandidate> = vec![];
+    for (hct, population) in colors_hct {
+      let hue = utils::math::sanitize_degrees(hct.hue().round() as usize);
+      let proportion = hue_excited_proportions[hue];
+      let is_disliked = self
+        .dislike_analyzer
+        .as_ref()
+        .is_some_and(|dislike_analyzer| dislike_analyzer.is_disliked(&hct));
+      let filter_reason = if is_disliked {
+        Some(FilterReason::Disliked)
+      } else if !self.filter {
+        None
+      } else if hct.chroma() < self.cutoff_chroma {
+        Some(FilterReason::ChromaBelowCutoff)
+      } else if proportion <= self.cutoff_excited_proportion {
+        Some(FilterReason::ExcitedProportionBelowCutoff)
+      } else {
+        None
+      };
+
+      let proportion_score = proportion * 100.0 * self.weight_proportion;
+      let chroma_weight = if hct.chroma() < self.target_chroma {
+        self.weight_chroma_below
+      } else {
+        self.weight_chroma_above
+      };
+      let chroma_score = (hct.chroma() - self.target_chroma) * chroma_weight;
+      candidates.push(ScoreCandidate {
+        argb: hct.to_int(),
+        hct,
+        population,
+        excited_proportion: proportion,
+        proportion_score,
+        chroma_score,
+        score: proportion_score + chroma_score,
+        outcome: match filter_reason {
+          Some(reason) => ScoreOutcome::Filtered(reason),
+          None => ScoreOutcome::NotReached,
+        },
+      });
+    }
+    // Sorted so that colors with higher scores come first, and filtered colors come last.
+    candidates.sort_by(|a, b| {
+      let a_filtered = matches!(a.outcome, ScoreOutcome::Filtered(_));
+      let b_filtered = matches!(b.outcome, ScoreOutcome::Filtered(_));
+      a_filtered
+        .cmp(&b_filtered)
+        .then(b.score.total_cmp(&a.score))
+    });
+    let scored_count = candidates
+      .iter()
+      .take_while(|candidate| !matches!(candidate.outcome, ScoreOutcome::Filtered(_)))
+      .count();
+
+    // Iterates through potential hue differences in degrees in order to select
+    // the colors with the largest distribution of hues possible. Starting at
+    // 90 degrees(maximum difference for 4 colors) then decreasing down to a
+    // 15 degree minimum.
+    let mut chosen_indices: Vec<usize> = vec![];
+    let mut outcomes: Vec<ScoreOutcome> = vec![];
+    let mut final_difference_degrees = 15;
+    for difference_degrees in (15..=90u8).rev() {
+      final_difference_degrees = difference_degrees;
+      chosen_indices.clear();
+      outcomes.clear();
+      for (i, candidate) in candidates[..scored_count].iter().enumerate() {
+        let duplicate_hue = chosen_indices.iter().find(|&&chosen_index| {
+          utils::math::difference_degrees(candidate.hct.hue(), candidates[chosen_index].hct.hue())
+            < difference_degrees as f64
+        });
+        match duplicate_hue {
+          Some(&chosen_index) => outcomes.push(ScoreOutcome::DuplicateHue {
+            difference_degrees,
+            chosen_argb: candidates[chosen_index].argb,
+          }),
+          None => {
+            outcomes.push(ScoreOutcome::Chosen {
+              difference_degrees,
+              rank: chosen_indices.len(),
+            });
+            chosen_indices.push(i);
+          }
+        }
+        if chosen_indices.len() >= self.desired {
           break;
         }
       }
-      if !has_duplicate_hue {
-        chosen_colors.push(hct.clone());
-      }
-      if chosen_colors.len() >= desired {
+      if chosen_indices.len() >= self.desired {
         break;
       }
     }
-    if chosen_colors.len() >= desired {
-      break;
+    for (candidate, outcome) in candidates.iter_mut().zip(outcomes) {
+      candidate.outcome = outcome;
+    }
+
+    let colors = if chosen_indices.is_empty() {
+      vec![self.fallback_color_argb]
+    } else {
+      chosen_indices
+        .into_iter()
+        .map(|chosen_index| candidates[chosen_index].argb)
+        .collect()
+    };
+    ScoreExplanation {
+      colors,
+      difference_degrees: final_difference_degrees,
+      candidates,
     }
   }
-  if chosen_colors.is_empty() {
-    vec![fallback_color_argb]
-  } else {
-    chosen_colors
-      .into_iter()
-      .map(|chosen_hct| chosen_hct.to_int())
-      .collect()
+}
+
+/// How [`Score`] ranked colors, returned by [`Score::explain`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ScoreExplanation {
+  /// The ranked colors, as returned by [`Score::score`].
+  pub colors: Vec<u32>,
+  /// The minimum hue difference between chosen colors, in the round that decided the result.
+  pub difference_degrees: u8,
+  /// Every input color, with colors that passed the filter sorted by score first, followed by the
+  /// colors that were filtered out.
+  pub candidates: Vec<ScoreCandidate>,
+}
+
+impl ScoreExplanation {
+  /// Whether none of the input colors were suitable, and the fallback color was returned.
+  pub fn used_fallback(&self) -> bool {
+    !self
+      .candidates
+      .iter()
+      .any(|candidate| matches!(candidate.outcome, ScoreOutcome::Chosen { .. }))
   }
 }
 
-#[derive(Debug, Default, PartialEq)]
-struct ScoredHct {
+/// An input color of [`Score`], with the values it was scored on.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ScoreCandidate {
+  pub argb: u32,
   pub hct: Hct,
+  pub population: usize,
+  /// The proportion of the population within the 30 degree slice of hues around this color's hue.
+  pub excited_proportion: f64,
+  pub proportion_score: f64,
+  pub chroma_score: f64,
+  /// The sum of the proportion score and the chroma score.
   pub score: f64,
+  pub outcome: ScoreOutcome,
+}
+
+/// Why a [`ScoreCandidate`] was chosen or rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ScoreOutcome {
+  /// The color was chosen, at the given index of the ranked colors.
+  Chosen { difference_degrees: u8, rank: usize },
+  /// The color's hue was too close to the hue of an already chosen color with a higher score.
+  DuplicateHue {
+    difference_degrees: u8,
+    chosen_argb: u32,
+  },
+  /// Enough colors were chosen before this color was considered.
+  NotReached,
+  /// The color was filtered out before choosing colors.
+  Filtered(FilterReason),
 }
 
-impl ScoredHct {
-  pub fn new(hct: Hct, score: f64) -> Self {
-    Self { hct, score }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum FilterReason {
+  /// The color's chroma was below [`Score::cutoff_chroma`].
+  ChromaBelowCutoff,
+  /// The color's excited proportion was not above [`Score::cutoff_excited_proportion`].
+  ExcitedProportionBelowCutoff,
+  /// The color was disliked by [`Score::dislike_analyzer`].
+  Disliked,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ScoreBuilder {
+  desired: Option<usize>,
+  fallback_color_argb: Option<u32>,
+  filter: Option<bool>,
+  target_chroma: Option<f64>,
+  weight_proportion: Option<f64>,
+  weight_chroma_above: Option<f64>,
+  weight_chroma_below: Option<f64>,
+  cutoff_chroma: Option<f64>,
+  cutoff_excited_proportion: Option<f64>,
+  dislike_analyzer: Option<DislikeAnalyzer>,
+}
+
+impl ScoreBuilder {
+  pub fn desired(mut self, desired: usize) -> Self {
+    self.desired = Some(desired);
+    self
+  }
+
+  pub fn fallback_color_argb(mut self, fallback_color_argb: u32) -> Self {
+    self.fallback_color_argb = Some(fallback_color_argb);
+    self
+  }
+
+  pub fn filter(mut self, filter: bool) -> Self {
+    self.filter = Some(filter);
+    self
+  }
+
+  pub fn target_chroma(mut self, target_chroma: f64) -> Self {
+    self.target_chroma = Some(target_chroma);
+    self
+  }
+
+  pub fn weight_proportion(mut self, weight_proportion: f64) -> Self {
+    self.weight_proportion = Some(weight_proportion);
+    self
+  }
+
+  pub fn weight_chroma_above(mut self, weight_chroma_above: f64) -> Self {
+    self.weight_chroma_above = Some(weight_chroma_above);
+    self
+  }
+
+  pub fn weight_chroma_below(mut self, weight_chroma_below: f64) -> Self {
+    self.weight_chroma_below = Some(weight_chroma_below);
+    self
+  }
+
+  pub fn cutoff_chroma(mut self, cutoff_chroma: f64) -> Self {
+    self.cutoff_chroma = Some(cutoff_chroma);
+    self
+  }
+
+  pub fn cutoff_excited_proportion(mut self, cutoff_excited_proportion: f64) -> Self {
+    self.cutoff_excited_proportion = Some(cutoff_excited_proportion);
+    self
+  }
+
+  pub fn dislike_analyzer(mut self, dislike_analyzer: DislikeAnalyzer) -> Self {
+    self.dislike_analyzer = Some(dislike_analyzer);
+    self
+  }
+
+  pub fn build(self) -> Score {
+    Score {
+      desired: self.desired.unwrap_or(DESIRED),
+      fallback_color_argb: self.fallback_color_argb.unwrap_or(FALLBACK_COLOR_ARGB),
+      filter: self.filter.unwrap_or(true),
+      target_chroma: self.target_chroma.unwrap_or(TARGET_CHROMA),
+      weight_proportion: self.weight_proportion.unwrap_or(WEIGHT_PROPORTION),
+      weight_chroma_above: self.weight_chroma_above.unwrap_or(WEIGHT_CHROMA_ABOVE),
+      weight_chroma_below: self.weight_chroma_below.unwrap_or(WEIGHT_CHROMA_BELOW),
+      cutoff_chroma: self.cutoff_chroma.unwrap_or(CUTOFF_CHROMA),
+      cutoff_excited_proportion: self
+        .cutoff_excited_proportion
+        .unwrap_or(CUTOFF_EXCITED_PROPORTION),
+      dislike_analyzer: self.dislike_analyzer,
+    }
   }
 }
 
-// TODO: investigate test failures and implement remaining tests
-// #[cfg(test)]
-// mod tests {
-//   use super::*;
-
-//   #[test]
-//   fn scoring_prioritizes_chroma() {
-//     let mut colors_to_population = HashMap::new();
-//     colors_to_population.insert(0xff000000, 1);
-//     colors_to_population.insert(0xffffffff, 1);
-//     colors_to_population.insert(0xff0000ff, 1);
-
-//     let ranked = score_with(colors_to_population, Some(4), None, None);
-
-//     assert_eq!(ranked.len(), 1);
-//     assert_eq!(ranked[0], 0xff0000ff);
-//   }
-
-//   #[test]
-//   fn scoring_prioritizes_chroma_when_proportions_equal() {
-//     let mut colors_to_population = HashMap::new();
-//     colors_to_population.insert(0xffff0000, 1);
-//     colors_to_population.insert(0xff00ff00, 1);
-//     colors_to_population.insert(0xff0000ff, 1);
-
-//     let ranked = score_with(colors_to_population, Some(4), None, None);
-
-//     assert_eq!(ranked.len(), 3);
-//     assert_eq!(ranked[0], 0xffff0000);
-//     assert_eq!(ranked[1], 0xff00ff00);
-//     assert_eq!(ranked[2], 0xff0000ff);
-//   }
-// }
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn scoring_prioritizes_chroma() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xff000000, 1);
+    colors_to_population.insert(0xffffffff, 1);
+    colors_to_population.insert(0xff0000ff, 1);
+
+    let ranked = Score::builder()
+      .desired(4)
+      .build()
+      .score(&colors_to_population);
+
+    assert_eq!(ranked.len(), 1);
+    assert_eq!(ranked[0], 0xff0000ff);
+  }
+
+  #[test]
+  fn scoring_prioritizes_chroma_when_proportions_equal() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xffff0000, 1);
+    colors_to_population.insert(0xff00ff00, 1);
+    colors_to_population.insert(0xff0000ff, 1);
+
+    let ranked = Score::builder()
+      .desired(4)
+      .build()
+      .score(&colors_to_population);
+
+    assert_eq!(ranked.len(), 3);
+    assert_eq!(ranked[0], 0xffff0000);
+    assert_eq!(ranked[1], 0xff00ff00);
+    assert_eq!(ranked[2], 0xff0000ff);
+  }
+
+  #[test]
+  fn scoring_handles_large_populations() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xff0000ff, 70_000);
+    colors_to_population.insert(0xffff0000, 1_000_000);
+
+    let ranked = Score::default().score(&colors_to_population);
+
+    assert_eq!(ranked, [0xffff0000, 0xff0000ff]);
+  }
+
+  #[test]
+  fn scoring_saturates_hue_populations() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xffff0000, usize::MAX);
+    colors_to_population.insert(0xfffe0000, usize::MAX);
+
+    let ranked = Score::default().score(&colors_to_population);
+
+    assert_eq!(ranked[0] & 0xfffe0000, 0xfffe0000);
+  }
+
+  #[test]
+  fn scoring_uses_fallback_color() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xff808080, 1);
+
+    let ranked = Score::builder()
+      .fallback_color_argb(0xff6750a4)
+      .build()
+      .score(&colors_to_population);
+
+    assert_eq!(ranked, [0xff6750a4]);
+  }
+
+  #[test]
+  fn scoring_respects_cutoffs() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xff808080, 1);
+    colors_to_population.insert(0xff0000ff, 1);
+
+    let ranked = Score::builder()
+      .cutoff_chroma(0.0)
+      .target_chroma(0.0)
+      .build()
+      .score(&colors_to_population);
+
+    assert_eq!(ranked, [0xff0000ff, 0xff808080]);
+  }
+
+  #[test]
+  #[allow(deprecated)]
+  fn score_with_matches_score() {
+    let colors_to_population = HashMap::from([(0xffff0000, 1), (0xff00ff00, 2), (0xff0000ff, 3)]);
+    let ranked = score_with(colors_to_population.clone(), None, None, None);
+    let colors_to_population = colors_to_population
+      .into_iter()
+      .map(|(color, population)| (color, population as usize))
+      .collect();
+    assert_eq!(ranked, Score::default().score(&colors_to_population));
+  }
+
+  #[test]
+  fn explains_outcomes() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xff808080, 10);
+    colors_to_population.insert(0xffff0000, 5);
+    colors_to_population.insert(0xfffe0000, 5);
+    colors_to_population.insert(0xff0000ff, 5);
+
+    let explanation = Score::builder()
+      .desired(2)
+      .build()
+      .explain(&colors_to_population);
+
+    assert_eq!(explanation.colors, [0xffff0000, 0xff0000ff]);
+    assert_eq!(explanation.difference_degrees, 90);
+    assert!(!explanation.used_fallback());
+    let outcome = |argb: u32| {
+      explanation
+        .candidates
+        .iter()
+        .find(|candidate| candidate.argb == argb)
+        .unwrap()
+        .outcome
+    };
+    assert_eq!(
+      outcome(0xffff0000),
+      ScoreOutcome::Chosen {
+        difference_degrees: 90,
+        rank: 0
+      }
+    );
+    assert_eq!(
+      outcome(0xfffe0000),
+      ScoreOutcome::DuplicateHue {
+        difference_degrees: 90,
+        chosen_argb: 0xffff0000
+      }
+    );
+    assert_eq!(
+      outcome(0xff808080),
+      ScoreOutcome::Filtered(FilterReason::ChromaBelowCutoff)
+    );
+  }
+
+  #[test]
+  fn explains_fallback() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xff000000, 1);
+
+    let explanation = Score::default().explain(&colors_to_population);
+
+    assert_eq!(explanation.colors, [0xff4285f4]);
+    assert!(explanation.used_fallback());
+    assert_eq!(explanation.candidates.len(), 1);
+  }
+
+  #[test]
+  fn filters_disliked_colors() {
+    let mut colors_to_population = HashMap::new();
+    colors_to_population.insert(0xff716b40, 10);
+    colors_to_population.insert(0xff0000ff, 1);
+
+    let ranked = Score::default().score(&colors_to_population);
+    assert_eq!(ranked[0], 0xff716b40);
+
+    let explanation = Score::builder()
+      .dislike_analyzer(DislikeAnalyzer::default())
+      .build()
+      .explain(&colors_to_population);
+    assert_eq!(explanation.colors, [0xff0000ff]);
+    assert_eq!(
+      explanation.candidates[1].outcome,
+      ScoreOutcome::Filtered(FilterReason::Disliked)
+    );
+  }
+}
@@ -4,7 +4,10 @@ use std::collections::HashMap;
 
 use num_traits::Float;
 
-use crate::{hct::Hct, utils};
+use crate::{
+  hct::{Hct, hct_solver},
+  utils,
+};
 
 const HCT_HUES_LEN: usize = 361;
 
@@ -273,10 +276,14 @@ impl TemperatureCache {
 
   pub fn hcts_by_hue(&mut self) -> &Vec<Hct> {
     if self.hcts_by_hue_cache.is_empty() {
-      for hue in 0..=360 {
-        let color_at_hue = Hct::from(hue as f64, self.input.chroma(), self.input.tone());
-        self.hcts_by_hue_cache.push(color_at_hue);
-      }
+      let colors: Vec<[f64; 3]> = (0..=360)
+        .map(|hue| [hue as f64, self.input.chroma(), self.input.tone()])
+        .collect();
+      self.hcts_by_hue_cache.extend(
+        hct_solver::solve_to_int_batch(&colors)
+          .into_iter()
+          .map(Hct::from_int),
+      );
     }
     &self.hcts_by_hue_cache
   }
@@ -1,12 +1,14 @@
 use num_traits::{Float, FromPrimitive, ToPrimitive};
 
-const SRGB_TO_XYZ: [[f64; 3]; 3] = [
+use crate::utils::math;
+
+pub(crate) const SRGB_TO_XYZ: [[f64; 3]; 3] = [
   [0.41233895, 0.35762064, 0.18051042],
   [0.2126, 0.7152, 0.0722],
   [0.01932141, 0.11916382, 0.95034478],
 ];
 
-const XYZ_TO_SRGB: [[f64; 3]; 3] = [
+pub(crate) const XYZ_TO_SRGB: [[f64; 3]; 3] = [
   [
     3.2413774792388685,
     -1.5376652402851851,
@@ -21,15 +23,40 @@ const XYZ_TO_SRGB: [[f64; 3]; 3] = [
 ];
 
 const WHITE_POINT_D65: [f64; 3] = [95.047, 100.0, 108.883];
+const WHITE_POINT_D50: [f64; 3] = [96.422, 100.0, 82.521];
+
+const LINRGB_TO_LMS: [[f64; 3]; 3] = [
+  [0.4122214708, 0.5363325363, 0.0514459929],
+  [0.2119034982, 0.6806995451, 0.1073969566],
+  [0.0883024619, 0.2817188376, 0.6299787005],
+];
+
+const LMS_TO_OKLAB: [[f64; 3]; 3] = [
+  [0.2104542553, 0.7936177850, -0.0040720468],
+  [1.9779984951, -2.4285922050, 0.4505937099],
+  [0.0259040371, 0.7827717662, -0.8086757660],
+];
+
+const OKLAB_TO_LMS: [[f64; 3]; 3] = [
+  [1.0, 0.3963377774, 0.2158037573],
+  [1.0, -0.1055613458, -0.0638541728],
+  [1.0, -0.0894841775, -1.2914855480],
+];
+
+const LMS_TO_LINRGB: [[f64; 3]; 3] = [
+  [4.0767416621, -3.3077115913, 0.2309699292],
+  [-1.2684380046, 2.6097574011, -0.3413193965],
+  [-0.0041960863, -0.7034186147, 1.7076147010],
+];
 
 #[inline]
 pub fn argb_from_rgb(red: u8, green: u8, blue: u8) -> u32 {
   (255 << 24) | ((red as u32 & 255) << 16) | ((green as u32 & 255) << 8) | (blue as u32 & 255)
 }
 
-pub fn argb_from_linrgb<T>(linrgb: [f64; 3]) -> u32
+pub fn argb_from_linrgb<T>(linrgb: [T; 3]) -> u32
 where
-  T: Float,
+  T: Float + FromPrimitive,
 {
   let r = delinearized(linrgb[0]);
   let g = delinearized(linrgb[1]);
@@ -110,6 +137,49 @@ pub fn argb_from_lab<T>(l: T, a: T, b: T) -> u32
 where
   T: Float + FromPrimitive,
 {
+  let [x, y, z] = xyz_from_lab([l, a, b]);
+  argb_from_xyz(x, y, z)
+}
+
+pub fn lab_from_argb<T>(argb: u32) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  lab_from_xyz(xyz_from_argb(argb))
+}
+
+/// Converts a color from XYZ to L*a*b*, relative to the D65 white point.
+pub fn lab_from_xyz<T>(xyz: [T; 3]) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  lab_from_xyz_relative_to(xyz, white_point_d65())
+}
+
+/// Converts a color from XYZ to L*a*b*, relative to another white point, e.g. the D50 white point
+/// of `lab()` in CSS.
+pub(crate) fn lab_from_xyz_relative_to<T>(xyz: [T; 3], white_point: [T; 3]) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let x_normalized = xyz[0] / white_point[0];
+  let y_normalized = xyz[1] / white_point[1];
+  let z_normalized = xyz[2] / white_point[2];
+  let fx = lab_f(x_normalized);
+  let fy = lab_f(y_normalized);
+  let fz = lab_f(z_normalized);
+  let l = T::from_f64(116.0).unwrap() * fy - T::from_f64(16.0).unwrap();
+  let a = T::from_f64(500.0).unwrap() * (fx - fy);
+  let b = T::from_f64(200.0).unwrap() * (fy - fz);
+  [l, a, b]
+}
+
+/// Converts a color from L*a*b*, relative to the D65 white point, to XYZ.
+pub fn xyz_from_lab<T>(lab: [T; 3]) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let [l, a, b] = lab;
   let white_point = WHITE_POINT_D65;
   let fy = (l + T::from_f64(16.0).unwrap()) / T::from_f64(116.0).unwrap();
   let fx = a / T::from_f64(500.0).unwrap() + fy;
@@ -120,37 +190,53 @@ where
   let x = x_normalized * T::from_f64(white_point[0]).unwrap();
   let y = y_normalized * T::from_f64(white_point[1]).unwrap();
   let z = z_normalized * T::from_f64(white_point[2]).unwrap();
-  argb_from_xyz(x, y, z)
+  [x, y, z]
 }
 
-pub fn lab_from_argb<T>(argb: u32) -> [T; 3]
+/// Converts a color from ARGB representation to Oklab representation.
+///
+/// `L` ranges from 0 to 1, `a` and `b` are roughly within -0.4 and 0.4.
+pub fn oklab_from_argb<T>(argb: u32) -> [T; 3]
 where
   T: Float + FromPrimitive,
 {
-  let linear_r = linearized(red_from_argb(argb));
-  let linear_g = linearized(green_from_argb(argb));
-  let linear_b = linearized(blue_from_argb(argb));
-  let matrix = SRGB_TO_XYZ;
-  let x = T::from_f64(matrix[0][0]).unwrap() * linear_r
-    + T::from_f64(matrix[0][1]).unwrap() * linear_g
-    + T::from_f64(matrix[0][2]).unwrap() * linear_b;
-  let y = T::from_f64(matrix[1][0]).unwrap() * linear_r
-    + T::from_f64(matrix[1][1]).unwrap() * linear_g
-    + T::from_f64(matrix[1][2]).unwrap() * linear_b;
-  let z = T::from_f64(matrix[2][0]).unwrap() * linear_r
-    + T::from_f64(matrix[2][1]).unwrap() * linear_g
-    + T::from_f64(matrix[2][2]).unwrap() * linear_b;
-  let white_point = WHITE_POINT_D65;
-  let x_normalized = x / T::from_f64(white_point[0]).unwrap();
-  let y_normalized = y / T::from_f64(white_point[1]).unwrap();
-  let z_normalized = z / T::from_f64(white_point[2]).unwrap();
-  let fx = lab_f(x_normalized);
-  let fy = lab_f(y_normalized);
-  let fz = lab_f(z_normalized);
-  let l = T::from_f64(116.0).unwrap() * fy - T::from_f64(16.0).unwrap();
-  let a = T::from_f64(500.0).unwrap() * (fx - fy);
-  let b = T::from_f64(200.0).unwrap() * (fy - fz);
-  [l, a, b]
+  oklab_from_linrgb([
+    linearized(red_from_argb(argb)),
+    linearized(green_from_argb(argb)),
+    linearized(blue_from_argb(argb)),
+  ])
+}
+
+/// Converts a color from Oklab representation to ARGB representation.
+///
+/// Colors outside of the sRGB gamut are clipped.
+pub fn argb_from_oklab<T>(l: T, a: T, b: T) -> u32
+where
+  T: Float + FromPrimitive,
+{
+  argb_from_linrgb(linrgb_from_oklab([l, a, b]))
+}
+
+/// Converts a linear sRGB color, with components ranging from 0 to 100, to Oklab.
+pub fn oklab_from_linrgb<T>(linrgb: [T; 3]) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let hundred = T::from_f64(100.0).unwrap();
+  let linrgb = linrgb.map(|component| component / hundred);
+  let lms = math::matrix_multiply(&linrgb, &matrix_from_f64(LINRGB_TO_LMS)).map(T::cbrt);
+  math::matrix_multiply(&lms, &matrix_from_f64(LMS_TO_OKLAB))
+}
+
+/// Converts an Oklab color to linear sRGB, with components ranging from 0 to 100 for colors
+/// inside the gamut.
+pub fn linrgb_from_oklab<T>(oklab: [T; 3]) -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let hundred = T::from_f64(100.0).unwrap();
+  let lms = math::matrix_multiply(&oklab, &matrix_from_f64(OKLAB_TO_LMS)).map(|c| c * c * c);
+  math::matrix_multiply(&lms, &matrix_from_f64(LMS_TO_LINRGB)).map(|component| component * hundred)
 }
 
 pub fn argb_from_lstar<T>(lstar: T) -> u32
@@ -230,6 +316,26 @@ where
   ]
 }
 
+#[inline]
+pub fn white_point_d50<T>() -> [T; 3]
+where
+  T: Float + FromPrimitive,
+{
+  let white_point = WHITE_POINT_D50;
+  [
+    T::from_f64(white_point[0]).unwrap(),
+    T::from_f64(white_point[1]).unwrap(),
+    T::from_f64(white_point[2]).unwrap(),
+  ]
+}
+
+pub(crate) fn matrix_from_f64<T>(matrix: [[f64; 3]; 3]) -> [[T; 3]; 3]
+where
+  T: Float + FromPrimitive,
+{
+  matrix.map(|row| row.map(|value| T::from_f64(value).unwrap()))
+}
+
 fn lab_f<T>(t: T) -> T
 where
   T: Float + PartialOrd + FromPrimitive,
@@ -0,0 +1,261 @@
+#[cfg(feature = "image")]
+use std::path::Path;
+
+#[cfg(feature = "image")]
+use ::image::{DynamicImage, ImageResult, RgbaImage};
+
+#[cfg(feature = "image")]
+use crate::{dynamiccolor::DynamicSchemeBuilder, hct::Hct, quantize::WeightMask};
+use crate::{
+  quantize::{Quantizer, QuantizerCelebi},
+  score::Score,
+  utils,
+};
+
+/// The number of colors the image is quantized to before scoring.
+const MAX_COLORS: usize = 128;
+
+/// The maximum number of pixels a decoded image is downsampled to before quantization.
+///
+/// Matches the bitmap size Android uses when extracting wallpaper colors.
+#[cfg(feature = "image")]
+const MAX_IMAGE_PIXELS: u32 = 112 * 112;
+
+/// Get the source color from the pixels of an image.
+///
+/// The most suitable color for a UI theme is returned, see [`source_colors_from_pixels`].
+pub fn source_color_from_pixels(pixels: &[u32]) -> u32 {
+  source_colors_from_pixels(pixels)[0]
+}
+
+/// Get colors suitable for a UI theme from the pixels of an image, given as ARGB integers.
+///
+/// Pixels that are not fully opaque are ignored. The remaining pixels are quantized with
+/// [`QuantizerCelebi`], and the resulting colors are ranked by [`Score`].
+///
+/// Returns colors sorted by suitability for a UI theme. The most suitable color is the first item.
+/// There will always be at least one color returned.
+pub fn source_colors_from_pixels(pixels: &[u32]) -> Vec<u32> {
+  let opaque_pixels: Vec<u32> = pixels
+    .iter()
+    .copied()
+    .filter(|&pixel| utils::color::is_opaque(pixel))
+    .collect();
+  let result = QuantizerCelebi::default().quantize(&opaque_pixels, MAX_COLORS);
+  Score::default().score(&result.color_to_count)
+}
+
+/// Get colors suitable for a UI theme from the pixels of an image, given as ARGB integers, where
+/// each pixel counts according to its weight.
+///
+/// Weights let the parts of the image users actually look at outweigh the rest, see
+/// [`WeightMask`] for common presets. Otherwise, this is the same as
+/// [`source_colors_from_pixels`].
+///
+/// # Panics
+///
+/// Panics if there isn't exactly one weight per pixel.
+pub fn source_colors_from_weighted_pixels(pixels: &[u32], weights: &[f32]) -> Vec<u32> {
+  assert_eq!(pixels.len(), weights.len(), "expected one weight per pixel");
+  let (opaque_pixels, opaque_weights): (Vec<u32>, Vec<f32>) = pixels
+    .iter()
+    .copied()
+    .zip(weights.iter().copied())
+    .filter(|&(pixel, _)| utils::color::is_opaque(pixel))
+    .unzip();
+  let result =
+    QuantizerCelebi::default().quantize_weighted(&opaque_pixels, &opaque_weights, MAX_COLORS);
+  Score::default().score(&result.color_to_count)
+}
+
+/// Downsamples a decoded image to at most 112 × 112 pixels, preserving its aspect ratio.
+#[cfg(feature = "image")]
+fn downsampled_image(image: &DynamicImage) -> RgbaImage {
+  let (width, height) = (image.width(), image.height());
+  let pixel_count = width as u64 * height as u64;
+  if pixel_count > MAX_IMAGE_PIXELS as u64 {
+    let scale = (MAX_IMAGE_PIXELS as f64 / pixel_count as f64).sqrt();
+    let width = ((width as f64 * scale).floor() as u32).max(1);
+    let height = ((height as f64 * scale).floor() as u32).max(1);
+    image.thumbnail_exact(width, height).to_rgba8()
+  } else {
+    image.to_rgba8()
+  }
+}
+
+#[cfg(feature = "image")]
+fn pixels_from_rgba(rgba: &RgbaImage) -> Vec<u32> {
+  rgba
+    .pixels()
+    .map(|pixel| {
+      let [red, green, blue, alpha] = pixel.0;
+      u32::from_be_bytes([alpha, red, green, blue])
+    })
+    .collect()
+}
+
+/// Get the pixels of a decoded image as ARGB integers.
+///
+/// Images larger than 112 × 112 pixels are downsampled first, preserving their aspect ratio.
+#[cfg(feature = "image")]
+pub fn pixels_from_image(image: &DynamicImage) -> Vec<u32> {
+  pixels_from_rgba(&downsampled_image(image))
+}
+
+/// Get colors suitable for a UI theme from a decoded image, where each pixel counts according to
+/// its weight in `mask`.
+///
+/// See [`pixels_from_image`] and [`source_colors_from_weighted_pixels`].
+#[cfg(feature = "image")]
+pub fn source_colors_from_image_with_mask(image: &DynamicImage, mask: &WeightMask) -> Vec<u32> {
+  let rgba = downsampled_image(image);
+  let weights = mask.weights(rgba.width() as usize, rgba.height() as usize);
+  source_colors_from_weighted_pixels(&pixels_from_rgba(&rgba), &weights)
+}
+
+/// Get colors suitable for a UI theme from a decoded image.
+///
+/// See [`pixels_from_image`] and [`source_colors_from_pixels`].
+#[cfg(feature = "image")]
+pub fn source_colors_from_image(image: &DynamicImage) -> Vec<u32> {
+  source_colors_from_pixels(&pixels_from_image(image))
+}
+
+/// Get the source color from a decoded image.
+///
+/// The most suitable color for a UI theme is returned, see [`source_colors_from_image`].
+#[cfg(feature = "image")]
+pub fn source_color_from_image(image: &DynamicImage) -> u32 {
+  source_colors_from_image(image)[0]
+}
+
+/// Create a [`DynamicSchemeBuilder`] with its source color set to the source color of a decoded
+/// image.
+#[cfg(feature = "image")]
+pub fn scheme_builder_from_image(image: &DynamicImage) -> DynamicSchemeBuilder {
+  DynamicSchemeBuilder::default().source_color_hct(Hct::from_int(source_color_from_image(image)))
+}
+
+/// Decode an image from an in-memory encoded buffer, and create a [`DynamicSchemeBuilder`] with
+/// its source color set to the source color of the image.
+///
+/// The image format is guessed from the contents of the buffer.
+#[cfg(feature = "image")]
+pub fn scheme_builder_from_image_bytes(bytes: &[u8]) -> ImageResult<DynamicSchemeBuilder> {
+  let image = ::image::load_from_memory(bytes)?;
+  Ok(scheme_builder_from_image(&image))
+}
+
+/// Decode an image file, and create a [`DynamicSchemeBuilder`] with its source color set to the
+/// source color of the image.
+///
+/// The image format is guessed from the file contents, falling back to the path's file extension
+/// when the contents aren't recognized.
+#[cfg(feature = "image")]
+pub fn scheme_builder_from_image_path(path: impl AsRef<Path>) -> ImageResult<DynamicSchemeBuilder> {
+  let image = ::image::ImageReader::open(path)?
+    .with_guessed_format()?
+    .decode()?;
+  Ok(scheme_builder_from_image(&image))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ignores_transparent_pixels() {
+    let mut pixels = vec![0x000000ff; 1000];
+    pixels.extend([0xffff0000; 100]);
+    let colors = source_colors_from_pixels(&pixels);
+    assert_eq!(colors, [0xffff0000]);
+  }
+
+  #[test]
+  fn handles_large_populations() {
+    let mut pixels = vec![0xff0000ff; 200_000];
+    pixels.extend(std::iter::repeat_n(0xff00ff00, 100_000));
+    pixels.extend(std::iter::repeat_n(0xff808080, 100_000));
+    let colors = source_colors_from_pixels(&pixels);
+    assert_eq!(colors, [0xff0000ff, 0xff00ff00]);
+  }
+
+  #[test]
+  fn falls_back_without_pixels() {
+    assert_eq!(source_color_from_pixels(&[]), 0xff4285f4);
+  }
+
+  #[test]
+  fn weights_favor_subject() {
+    let mut pixels = vec![0xff0000ff; 300];
+    pixels.extend([0xffff0000; 100]);
+    let mut weights = vec![0.1; 300];
+    weights.extend([1.0; 100]);
+    assert_eq!(source_colors_from_pixels(&pixels)[0], 0xff0000ff);
+    assert_eq!(
+      source_colors_from_weighted_pixels(&pixels, &weights)[0],
+      0xffff0000
+    );
+  }
+
+  #[cfg(feature = "image")]
+  #[test]
+  fn center_weighted_mask_favors_center() {
+    // A red square on a slightly larger blue background.
+    let image = DynamicImage::ImageRgba8(::image::RgbaImage::from_fn(100, 100, |x, y| {
+      if (18..82).contains(&x) && (18..82).contains(&y) {
+        ::image::Rgba([255, 0, 0, 255])
+      } else {
+        ::image::Rgba([0, 0, 255, 255])
+      }
+    }));
+    assert_eq!(source_colors_from_image(&image)[0], 0xff0000ff);
+    assert_eq!(
+      source_colors_from_image_with_mask(&image, &WeightMask::CenterWeighted)[0],
+      0xffff0000
+    );
+  }
+
+  #[cfg(feature = "image")]
+  fn encode_png(image: &DynamicImage) -> Vec<u8> {
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    image
+      .write_to(&mut bytes, ::image::ImageFormat::Png)
+      .unwrap();
+    bytes.into_inner()
+  }
+
+  #[cfg(feature = "image")]
+  #[test]
+  fn downsamples_large_images() {
+    let image = DynamicImage::ImageRgba8(::image::RgbaImage::from_pixel(
+      1000,
+      500,
+      ::image::Rgba([0, 0, 255, 255]),
+    ));
+    let pixels = pixels_from_image(&image);
+    assert!(pixels.len() <= MAX_IMAGE_PIXELS as usize);
+    assert!(pixels.iter().all(|&pixel| pixel == 0xff0000ff));
+  }
+
+  #[cfg(feature = "image")]
+  #[test]
+  fn scheme_builder_from_png() {
+    let image = ::image::RgbaImage::from_fn(64, 64, |x, _| {
+      if x < 16 {
+        ::image::Rgba([255, 0, 0, 0])
+      } else {
+        ::image::Rgba([0, 0, 255, 255])
+      }
+    });
+    let bytes = encode_png(&DynamicImage::ImageRgba8(image));
+    let scheme = scheme_builder_from_image_bytes(&bytes).unwrap().build();
+    assert_eq!(scheme.source_color_argb(), 0xff0000ff);
+  }
+
+  #[cfg(feature = "image")]
+  #[test]
+  fn rejects_invalid_bytes() {
+    assert!(scheme_builder_from_image_bytes(&[0, 1, 2, 3]).is_err());
+  }
+}
@@ -48,3 +48,31 @@ where
   let c = row[0] * matrix[2][0] + row[1] * matrix[2][1] + row[2] * matrix[2][2];
   [a, b, c]
 }
+
+/// Multiplies two 3x3 matrices, so the result applies `right` first and `left` second.
+pub fn matrix_product<T>(left: &[[T; 3]; 3], right: &[[T; 3]; 3]) -> [[T; 3]; 3]
+where
+  T: Num + Copy,
+{
+  [0, 1, 2].map(|i| {
+    [0, 1, 2]
+      .map(|j| left[i][0] * right[0][j] + left[i][1] * right[1][j] + left[i][2] * right[2][j])
+  })
+}
+
+/// Inverts a 3x3 matrix.
+///
+/// The result isn't finite if the matrix isn't invertible.
+pub fn matrix_inverse<T>(matrix: &[[T; 3]; 3]) -> [[T; 3]; 3]
+where
+  T: Num + Copy,
+{
+  let [[a, b, c], [d, e, f], [g, h, i]] = *matrix;
+  let cofactors = [
+    [e * i - f * h, c * h - b * i, b * f - c * e],
+    [f * g - d * i, a * i - c * g, c * d - a * f],
+    [d * h - e * g, b * g - a * h, a * e - b * d],
+  ];
+  let determinant = a * cofactors[0][0] + b * cofactors[1][0] + c * cofactors[2][0];
+  cofactors.map(|row| row.map(|value| value / determinant))
+}
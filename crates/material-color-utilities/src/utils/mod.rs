@@ -1,5 +1,8 @@
 pub mod color;
+#[cfg(feature = "quantize")]
+pub mod image;
 pub mod math;
+#[cfg(feature = "std")]
 pub mod string;
 
 #[cfg(test)]
@@ -1,7 +1,16 @@
 use std::convert::Infallible;
 
-use crate::utils::color::{
-  alpha_from_argb, argb_from_rgb, blue_from_argb, green_from_argb, red_from_argb,
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  chromatic_adaptation::ChromaticAdaptation,
+  color::{Argb, Hsl, LinearRgb, Oklab, Oklch, Xyz},
+  hct::{Gamut, Hct},
+  utils::{
+    self,
+    color::{alpha_from_argb, argb_from_rgb, blue_from_argb, green_from_argb, red_from_argb},
+  },
 };
 
 use csscolorparser::{Color, ParseColorError};
@@ -30,6 +39,11 @@ pub fn css_hex_from_argb(argb: u32) -> String {
   Color::from_argb(argb).to_css_hex()
 }
 
+/// Serializes a color represented in ARGB to CSS, in the given format.
+pub fn css_color_from_argb(argb: u32, format: CssFormat) -> String {
+  css_color_from_xyz(Argb(argb), utils::color::xyz_from_argb(argb), format)
+}
+
 pub fn argb_from_css_hex(hex: &str) -> Result<u32, ParseColorError> {
   let hex = hex.trim();
   if hex.starts_with("#") {
@@ -109,3 +123,233 @@ impl FromArgb for Color {
     Self::from_rgba8(r, g, b, a)
   }
 }
+
+/// A notation from CSS Color 4 that colors can be serialized to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum CssNotation {
+  /// `#rrggbb`, or `#rrggbbaa` for translucent colors.
+  #[default]
+  Hex,
+  /// `rgb(r g b)`, with channels ranging from 0 to 255.
+  Rgb,
+  /// `hsl(h s% l%)`.
+  Hsl,
+  /// `lab(l a b)`. CSS measures L*a*b* relative to the D50 white point, so colors are adapted
+  /// to it first.
+  Lab,
+  /// `oklch(l c h)`.
+  Oklch,
+  /// `color(display-p3 r g b)`, with channels ranging from 0 to 1.
+  DisplayP3,
+}
+
+/// How colors are serialized to CSS: the notation, and how many decimal places numbers keep.
+///
+/// Trailing zeros are dropped, so `rgb()` channels are always integers. Translucent colors get an
+/// alpha, e.g. `rgb(66 133 244 / 0.5)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct CssFormat {
+  notation: CssNotation,
+  precision: usize,
+}
+
+impl Default for CssFormat {
+  fn default() -> Self {
+    Self::new(CssNotation::default())
+  }
+}
+
+impl CssFormat {
+  pub const DEFAULT_PRECISION: usize = 3;
+
+  pub fn new(notation: CssNotation) -> Self {
+    Self {
+      notation,
+      precision: Self::DEFAULT_PRECISION,
+    }
+  }
+
+  /// Keep at most `precision` decimal places. Hex codes ignore it.
+  pub fn with_precision(mut self, precision: usize) -> Self {
+    self.precision = precision;
+    self
+  }
+
+  pub fn notation(&self) -> CssNotation {
+    self.notation
+  }
+
+  pub fn precision(&self) -> usize {
+    self.precision
+  }
+
+  fn number(&self, value: f64) -> String {
+    let number = format!("{value:.*}", self.precision);
+    let number = if number.contains('.') {
+      number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+      &number
+    };
+    match number {
+      "-0" => "0".to_owned(),
+      number => number.to_owned(),
+    }
+  }
+}
+
+/// Colors that can be serialized to CSS, so an exporter can pick a [`CssFormat`] once and use it
+/// for every color.
+pub trait ToCssColor {
+  fn to_css_color(&self, format: CssFormat) -> String;
+}
+
+impl ToCssColor for u32 {
+  fn to_css_color(&self, format: CssFormat) -> String {
+    css_color_from_argb(*self, format)
+  }
+}
+
+impl ToCssColor for Argb {
+  fn to_css_color(&self, format: CssFormat) -> String {
+    css_color_from_argb(self.to_int(), format)
+  }
+}
+
+impl ToCssColor for Hct {
+  /// Colors solved in a wide gamut keep their wide-gamut color in the `lab()`, `oklch()` and
+  /// `color(display-p3)` notations. The other notations use the sRGB color.
+  fn to_css_color(&self, format: CssFormat) -> String {
+    let gamut = self.gamut();
+    let xyz = gamut.xyz_from_linrgb(gamut.linrgb_from_rgb(self.to_rgb()));
+    css_color_from_xyz(Argb(self.to_int()), xyz, format)
+  }
+}
+
+/// Serializes a color, using its ARGB representation for the sRGB notations and its XYZ
+/// coordinates for the others.
+fn css_color_from_xyz(argb: Argb, xyz: [f64; 3], format: CssFormat) -> String {
+  let components = match format.notation {
+    CssNotation::Hex => return css_hex_from_argb(argb.to_int()),
+    CssNotation::Rgb => format!("rgb({} {} {}", argb.red(), argb.green(), argb.blue()),
+    CssNotation::Hsl => {
+      let hsl = Hsl::from(argb);
+      format!(
+        "hsl({} {}% {}%",
+        format.number(hsl.hue),
+        format.number(hsl.saturation * 100.0),
+        format.number(hsl.lightness * 100.0)
+      )
+    }
+    CssNotation::Lab => {
+      let white_point = utils::color::white_point_d50();
+      let xyz =
+        ChromaticAdaptation::Bradford.adapt(xyz, utils::color::white_point_d65(), white_point);
+      let [l, a, b] = utils::color::lab_from_xyz_relative_to(xyz, white_point);
+      format!(
+        "lab({} {} {}",
+        format.number(l),
+        format.number(a),
+        format.number(b)
+      )
+    }
+    CssNotation::Oklch => {
+      let oklch = Oklch::from(Oklab::from(LinearRgb::from(Xyz::from(xyz))));
+      let chroma = format.number(oklch.c);
+      // The hue of a neutral color is noise, so it is pinned to keep exports stable.
+      let hue = if chroma == "0" { 0.0 } else { oklch.h };
+      format!(
+        "oklch({} {} {}",
+        format.number(oklch.l),
+        chroma,
+        format.number(hue)
+      )
+    }
+    CssNotation::DisplayP3 => {
+      let gamut = Gamut::DisplayP3;
+      let [r, g, b] = gamut
+        .linrgb_from_xyz(xyz)
+        .map(|component| gamut.delinearized(component));
+      format!(
+        "color(display-p3 {} {} {}",
+        format.number(r),
+        format.number(g),
+        format.number(b)
+      )
+    }
+  };
+  if argb.is_opaque() {
+    format!("{components})")
+  } else {
+    let alpha = format.number(argb.alpha() as f64 / 255.0);
+    format!("{components} / {alpha})")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serializes_srgb_notations() {
+    let blue = 0xff4285f4;
+    assert_eq!(blue.to_css_color(CssFormat::default()), "#4285f4");
+    assert_eq!(
+      blue.to_css_color(CssFormat::new(CssNotation::Rgb)),
+      "rgb(66 133 244)"
+    );
+    assert_eq!(
+      0x804285f4.to_css_color(CssFormat::new(CssNotation::Rgb)),
+      "rgb(66 133 244 / 0.502)"
+    );
+    assert_eq!(
+      Argb(blue).to_css_color(CssFormat::new(CssNotation::Hsl).with_precision(1)),
+      "hsl(217.4 89% 60.8%)"
+    );
+  }
+
+  #[test]
+  fn serializes_wide_notations() {
+    let red = 0xffff0000;
+    assert_eq!(
+      red.to_css_color(CssFormat::new(CssNotation::Lab).with_precision(1)),
+      "lab(54.3 80.8 69.9)"
+    );
+    assert_eq!(
+      red.to_css_color(CssFormat::new(CssNotation::Oklch)),
+      "oklch(0.628 0.258 29.234)"
+    );
+    assert_eq!(
+      0xffffffff.to_css_color(CssFormat::new(CssNotation::Oklch)),
+      "oklch(1 0 0)"
+    );
+    assert_eq!(
+      red.to_css_color(CssFormat::new(CssNotation::DisplayP3).with_precision(2)),
+      "color(display-p3 0.92 0.2 0.14)"
+    );
+  }
+
+  #[test]
+  fn keeps_wide_gamut_colors() {
+    let hct = Hct::from_in_gamut(27.0, 120.0, 50.0, Gamut::DisplayP3);
+    let display_p3 = hct.to_css_color(CssFormat::new(CssNotation::DisplayP3));
+    let [r, g, b] = hct.to_rgb();
+    let format = CssFormat::new(CssNotation::DisplayP3);
+    assert_eq!(
+      display_p3,
+      format!(
+        "color(display-p3 {} {} {})",
+        format.number(r),
+        format.number(g),
+        format.number(b)
+      )
+    );
+    assert_ne!(
+      display_p3,
+      hct
+        .to_int()
+        .to_css_color(CssFormat::new(CssNotation::DisplayP3))
+    );
+  }
+}